@@ -5,6 +5,7 @@
 use std::{
     fmt::{Debug, Display},
     net::SocketAddr,
+    path::PathBuf,
 };
 
 use crate::pipeline::QueueName;
@@ -22,6 +23,9 @@ pub enum ForeignInfo {
 
     /// In memory queue
     InMemoryQueue(QueueName),
+
+    /// File to load table rows from
+    File(PathBuf),
 }
 
 impl Display for ForeignInfo {
@@ -31,6 +35,7 @@ impl Display for ForeignInfo {
             ForeignInfo::Http(addr) => format!("HTTP connection to {:?}", addr),
             ForeignInfo::SocketCAN(interface) => format!("Socket CAN interface {}", interface),
             ForeignInfo::InMemoryQueue(queue_name) => format!("In-memory queue {}", queue_name),
+            ForeignInfo::File(path) => format!("File {}", path.display()),
         };
 
         write!(f, "[foreign info.] {}", detail)
@@ -70,4 +70,22 @@ impl SpringPipeline {
     pub fn push(&self, queue: &str, row: SpringSourceRow) -> Result<()> {
         self.0.push(queue, row.into_schemaless_row()?)
     }
+
+    /// Replace all the rows of a table (created by `CREATE TABLE`) with `rows`.
+    ///
+    /// Pumps joining the table look up the new rows from their next input row.
+    ///
+    /// # Failure
+    ///
+    /// - [SpringError::Sql](crate::api::error::SpringError::Sql) when:
+    ///   - table named `table` does not exist.
+    ///   - a row lacks any of NOT NULL columns of the table.
+    ///   - a value in a row cannot be converted into its column type.
+    pub fn replace_table(&self, table: &str, rows: Vec<SpringSourceRow>) -> Result<()> {
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_schemaless_row())
+            .collect::<Result<Vec<_>>>()?;
+        self.0.replace_table(table, rows)
+    }
 }
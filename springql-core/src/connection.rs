@@ -4,7 +4,7 @@ use std::{sync::Once, thread, time::Duration};
 
 use crate::{
     api::{error::Result, SpringConfig},
-    pipeline::QueueName,
+    pipeline::{QueueName, StreamName},
     sql_processor::SqlProcessor,
     stream_engine::{autonomous_executor::SchemalessRow, command::Command, EngineMutex},
 };
@@ -81,4 +81,9 @@ impl Connection {
         let mut engine = self.engine.get()?;
         engine.push_in_memory_queue(QueueName::new(queue.to_string()), row)
     }
+
+    pub fn replace_table(&self, table: &str, rows: Vec<SchemalessRow>) -> Result<()> {
+        let mut engine = self.engine.get()?;
+        engine.replace_table(StreamName::new(table.to_string()), rows)
+    }
 }
@@ -10,9 +10,9 @@ use anyhow::anyhow;
 use crate::{
     api::error::{Result, SpringError},
     expression::{AggrExpr, ValueExpr},
-    pipeline::{AggrAlias, ValueAlias},
+    pipeline::{AggrAlias, GroupByLabels, ValueAlias},
     sql_processor::SelectFieldSyntax,
-    stream_engine::{SqlValue, Tuple},
+    stream_engine::{autonomous_executor::AggregatedAndGroupingValues, SqlValue, Tuple},
 };
 
 /// ExprResolver is to:
@@ -47,6 +47,12 @@ impl ExprResolver {
             .into_iter()
             .map(|select_field| match select_field {
                 SelectFieldSyntax::ValueExpr { value_expr, alias } => {
+                    let value_expr = value_expr.replace_aggr_exprs(&mut |aggr_expr| {
+                        let label = label_gen.next_aggr();
+                        aggr_expressions.insert(label, aggr_expr);
+                        label
+                    });
+
                     let label = label_gen.next_value();
                     value_expressions.insert(label, value_expr);
                     if let Some(alias) = alias {
@@ -62,6 +68,9 @@ impl ExprResolver {
                     }
                     ExprLabel::Aggr(label)
                 }
+                SelectFieldSyntax::Wildcard { .. } => {
+                    unreachable!("wildcards must be expanded by SelectSyntaxAnalyzer")
+                }
             })
             .collect();
 
@@ -78,6 +87,13 @@ impl ExprResolver {
         )
     }
 
+    /// All the aggregate expressions, including ones inside value expressions in select_list, in registration order.
+    pub fn aggr_labels(&self) -> Vec<AggrExprLabel> {
+        let mut labels = self.aggr_expressions.keys().copied().collect::<Vec<_>>();
+        labels.sort();
+        labels
+    }
+
     /// # Failures
     ///
     /// - `SpringError::Sql` if alias is not in select_list.
@@ -93,12 +109,9 @@ impl ExprResolver {
             })
     }
 
-    /// TODO use in HAVING clause
-    ///
     /// # Failures
     ///
     /// - `SpringError::Sql` if alias is not in select_list.
-    #[allow(dead_code)]
     pub fn resolve_aggr_alias(&self, aggr_alias: AggrAlias) -> Result<AggrExprLabel> {
         self.aggr_aliased_labels
             .get(&aggr_alias)
@@ -128,14 +141,60 @@ impl ExprResolver {
         label
     }
 
-    /// TODO use in HAVING clause
-    ///
-    /// Register aggregate expression which is not in select_list
-    #[allow(dead_code)]
+    /// Register aggregate expression which is not in select_list.
+    /// Returns the existing label if the same aggregate expression is already registered (not to aggregate twice).
     pub fn register_aggr_expr(&mut self, aggr_expr: AggrExpr) -> AggrExprLabel {
-        let label = self.label_gen.next_aggr();
-        self.aggr_expressions.insert(label, aggr_expr);
-        label
+        let registered = self
+            .aggr_expressions
+            .iter()
+            .find(|(_, registered)| **registered == aggr_expr)
+            .map(|(label, _)| *label);
+
+        registered.unwrap_or_else(|| {
+            let label = self.label_gen.next_aggr();
+            self.aggr_expressions.insert(label, aggr_expr);
+            label
+        })
+    }
+
+    /// Register HAVING condition, which is evaluated with aggregation results.
+    ///
+    /// - Aggregate expressions inside are registered by `register_aggr_expr()`.
+    /// - Aliases are resolved into the aliased aggregate expressions, GROUP BY elements or value expressions.
+    /// - Column references same as GROUP BY elements are resolved into them.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` if an alias is not in select_list.
+    pub fn register_having_condition(
+        &mut self,
+        having_condition: ValueExpr,
+        group_by_labels: &GroupByLabels,
+    ) -> Result<ValueExprLabel> {
+        let group_by_labels = group_by_labels.as_labels();
+
+        let having_condition = having_condition
+            .replace_aggr_exprs(&mut |aggr_expr| self.register_aggr_expr(aggr_expr))
+            .try_map_leaves(&mut |leaf| match leaf {
+                ValueExpr::AliasRef(alias) => {
+                    if let Ok(label) = self.resolve_aggr_alias(AggrAlias::new(alias.clone())) {
+                        return Ok(ValueExpr::AggrExprRef(label));
+                    }
+                    let label = self.resolve_value_alias(ValueAlias::new(alias))?;
+                    if group_by_labels.contains(&label) {
+                        Ok(ValueExpr::GroupByRef(label))
+                    } else {
+                        Ok(self.value_expressions[&label].clone())
+                    }
+                }
+                ValueExpr::ColumnReference(_) => Ok(group_by_labels
+                    .iter()
+                    .find(|label| self.value_expressions[label] == leaf)
+                    .map_or(leaf, |label| ValueExpr::GroupByRef(*label))),
+                _ => Ok(leaf),
+            })?;
+
+        Ok(self.register_value_expr(having_condition))
     }
 
     /// label -> (internal) value expression + tuple (for ColumnReference) -> SqlValue.
@@ -160,6 +219,43 @@ impl ExprResolver {
         value_expr_ph2.eval()
     }
 
+    /// label -> (internal) value expression + aggregation result (for aggregate expressions and GROUP BY elements inside) -> SqlValue.
+    ///
+    /// # Panics
+    ///
+    /// -  `label` is not found
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - expression has column reference outside of aggregate expressions.
+    ///   - somehow failed to eval expression.
+    pub fn eval_value_expr_with_aggr(
+        &self,
+        label: ValueExprLabel,
+        aggregated_and_grouping_values: &AggregatedAndGroupingValues,
+    ) -> Result<SqlValue> {
+        let value_expr = self
+            .value_expressions
+            .get(&label)
+            .cloned()
+            .unwrap_or_else(|| panic!("label {:?} not found", label));
+
+        let value_expr_ph2 = value_expr.resolve_leaves(&mut |leaf| match leaf {
+            ValueExpr::AggrExprRef(aggr_label) => aggregated_and_grouping_values
+                .get_aggregated_value(&aggr_label)
+                .cloned(),
+            ValueExpr::GroupByRef(value_label) => aggregated_and_grouping_values
+                .get_group_by_value(&value_label)
+                .cloned(),
+            _ => Err(SpringError::Sql(anyhow!(
+                "column reference must be in GROUP BY clause or inside an aggregate function: {:?}",
+                leaf
+            ))),
+        })?;
+        value_expr_ph2.eval()
+    }
+
     /// label -> (internal) value expression inside aggr expr + tuple (for ColumnReference) -> SqlValue.
     ///
     /// _inner_ means: AGGR_FUNC(inner_value_expr)
@@ -179,6 +275,24 @@ impl ExprResolver {
         let value_expr_ph2 = value_expr.resolve_colref(tuple)?;
         value_expr_ph2.eval()
     }
+
+    /// Evaluates the `by` argument of ARG_MIN / ARG_MAX. `None` for other aggregate functions.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - column reference in expression is not found in `tuple`.
+    ///   - somehow failed to eval expression.
+    pub fn eval_aggr_expr_by(
+        &self,
+        label: AggrExprLabel,
+        tuple: &Tuple,
+    ) -> Result<Option<SqlValue>> {
+        self.resolve_aggr_expr(label)
+            .by
+            .map(|by| by.resolve_colref(tuple)?.eval())
+            .transpose()
+    }
 }
 
 #[cfg(test)]
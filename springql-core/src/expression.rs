@@ -3,18 +3,27 @@
 #![doc = include_str!("expression.md")]
 
 mod boolean_expression;
+mod case_expr;
 mod function_call;
+mod in_list;
+mod math_function;
 mod operator;
+mod string_function;
 
 pub use boolean_expression::{BinaryExpr, ComparisonFunction, LogicalFunction, NumericalFunction};
-pub use function_call::FunctionCall;
+pub use case_expr::CaseExpr;
+pub use function_call::{FunctionCall, TrimSide};
+pub use in_list::{ConstantSet, InList};
 pub use operator::{BinaryOperator, UnaryOperator};
 
 use anyhow::anyhow;
 
 use crate::{
     api::error::{Result, SpringError},
-    pipeline::{AggregateFunctionParameter, ColumnReference},
+    expr_resolver::{AggrExprLabel, ValueExprLabel},
+    pipeline::{
+        AggregateFunctionParameter, ColumnReference, NumericComparableType, Pipeline, SqlType,
+    },
     stream_engine::{
         time::{SpringDuration, SpringEventDuration},
         NnSqlValue, SqlCompareResult, SqlValue, Tuple,
@@ -33,105 +42,405 @@ pub enum ValueExpr {
     UnaryOperator(UnaryOperator, Box<Self>),
     BinaryExpr(BinaryExpr<Self>),
     FunctionCall(FunctionCall<Self>),
+    CaseExpr(CaseExpr<Self>),
 
     ColumnReference(ColumnReference),
+
+    /// Aggregate function call in a larger expression, such as `MAX(x) - MIN(x)`.
+    /// Replaced with `AggrExprRef` when registered to `ExprResolver`.
+    AggrExpr(Box<AggrExpr>),
+    /// Result of an aggregate expression registered to `ExprResolver`.
+    AggrExprRef(AggrExprLabel),
+
+    /// Alias of a select field, such as `avg_amount` in `HAVING avg_amount > 10`.
+    /// Only allowed in HAVING clause, and replaced with the aliased expression when registered to `ExprResolver`.
+    AliasRef(String),
+    /// Value of a GROUP BY element registered to `ExprResolver` (referenced from HAVING clause).
+    GroupByRef(ValueExprLabel),
 }
 impl ValueExprType for ValueExpr {}
 
 impl ValueExpr {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - column reference in expression is not found in `tuple`.
+    ///   - expression has an aggregate function.
     pub fn resolve_colref(self, tuple: &Tuple) -> Result<ValueExprPh2> {
+        self.resolve_leaves(&mut |leaf| match leaf {
+            Self::ColumnReference(colref) => tuple.get_value(&colref),
+            _ => Err(SpringError::Sql(anyhow!(
+                "aggregate function is not allowed here: {:?}",
+                leaf
+            ))),
+        })
+    }
+
+    /// Resolves leaves which depend on input (column references and aggregate expressions) into constants by `f`.
+    pub fn resolve_leaves<F>(self, f: &mut F) -> Result<ValueExprPh2>
+    where
+        F: FnMut(Self) -> Result<SqlValue>,
+    {
         match self {
             Self::Constant(value) => Ok(ValueExprPh2::Constant(value)),
+            Self::ColumnReference(_)
+            | Self::AggrExpr(_)
+            | Self::AggrExprRef(_)
+            | Self::AliasRef(_)
+            | Self::GroupByRef(_) => f(self).map(ValueExprPh2::Constant),
+            Self::UnaryOperator(op, child) => {
+                let child_ph2 = child.resolve_leaves(f)?;
+                Ok(ValueExprPh2::UnaryOperator(op, Box::new(child_ph2)))
+            }
+            Self::BinaryExpr(binary_expr) => binary_expr
+                .try_map(|operand| operand.resolve_leaves(f))
+                .map(ValueExprPh2::BinaryExpr),
+            Self::FunctionCall(function_call) => function_call
+                .try_map(|arg| arg.resolve_leaves(f))
+                .map(ValueExprPh2::FunctionCall),
+            Self::CaseExpr(case_expr) => case_expr
+                .try_map(|expr| expr.resolve_leaves(f))
+                .map(ValueExprPh2::CaseExpr),
+        }
+    }
 
-            Self::ColumnReference(colref) => {
-                let value = tuple.get_value(&colref)?;
-                Ok(ValueExprPh2::Constant(value))
+    /// Replaces leaves (constants, column references, aggregate expressions and references) with expressions given by `f`.
+    pub fn try_map_leaves<F>(self, f: &mut F) -> Result<Self>
+    where
+        F: FnMut(Self) -> Result<Self>,
+    {
+        match self {
+            Self::Constant(_)
+            | Self::ColumnReference(_)
+            | Self::AggrExpr(_)
+            | Self::AggrExprRef(_)
+            | Self::AliasRef(_)
+            | Self::GroupByRef(_) => f(self),
+            Self::UnaryOperator(op, child) => {
+                let child = child.try_map_leaves(f)?;
+                Ok(Self::UnaryOperator(op, Box::new(child)))
             }
+            Self::BinaryExpr(binary_expr) => binary_expr
+                .try_map(|operand| operand.try_map_leaves(f))
+                .map(Self::BinaryExpr),
+            Self::FunctionCall(function_call) => function_call
+                .try_map(|arg| arg.try_map_leaves(f))
+                .map(Self::FunctionCall),
+            Self::CaseExpr(case_expr) => case_expr
+                .try_map(|expr| expr.try_map_leaves(f))
+                .map(Self::CaseExpr),
+        }
+    }
 
-            Self::FunctionCall(function_call) => match function_call {
-                FunctionCall::DurationMillis { duration_millis } => {
-                    let duration_millis_ph2 = duration_millis.resolve_colref(tuple)?;
-                    Ok(ValueExprPh2::FunctionCall(FunctionCall::DurationMillis {
-                        duration_millis: Box::new(duration_millis_ph2),
-                    }))
+    /// Column references inside this expression.
+    pub fn column_references(&self) -> Vec<ColumnReference> {
+        let mut colrefs = vec![];
+        self.clone()
+            .try_map_leaves(&mut |leaf| {
+                if let Self::ColumnReference(colref) = &leaf {
+                    colrefs.push(colref.clone());
                 }
-                FunctionCall::DurationSecs { duration_secs } => {
-                    let duration_secs_ph2 = duration_secs.resolve_colref(tuple)?;
-                    Ok(ValueExprPh2::FunctionCall(FunctionCall::DurationSecs {
-                        duration_secs: Box::new(duration_secs_ph2),
-                    }))
-                }
-                FunctionCall::FloorTime { target, resolution } => {
-                    let target_ph2 = target.resolve_colref(tuple)?;
-                    let resolution_ph2 = resolution.resolve_colref(tuple)?;
-                    Ok(ValueExprPh2::FunctionCall(FunctionCall::FloorTime {
-                        target: Box::new(target_ph2),
-                        resolution: Box::new(resolution_ph2),
-                    }))
+                Ok(leaf)
+            })
+            .expect("collecting column references never fails");
+        colrefs
+    }
+
+    /// Replaces aggregate expressions inside with the labels given by `f`.
+    pub fn replace_aggr_exprs<F>(self, f: &mut F) -> Self
+    where
+        F: FnMut(AggrExpr) -> AggrExprLabel,
+    {
+        self.try_map_leaves(&mut |leaf| match leaf {
+            Self::AggrExpr(aggr_expr) => Ok(Self::AggrExprRef(f(*aggr_expr))),
+            _ => Ok(leaf),
+        })
+        .expect("replace never fails")
+    }
+
+    /// Whether this expression has an aggregate expression inside.
+    pub fn has_aggr_expr(&self) -> bool {
+        match self {
+            Self::AggrExpr(_) | Self::AggrExprRef(_) => true,
+            Self::Constant(_)
+            | Self::ColumnReference(_)
+            | Self::AliasRef(_)
+            | Self::GroupByRef(_) => false,
+            Self::UnaryOperator(_, child) => child.has_aggr_expr(),
+            Self::BinaryExpr(binary_expr) => {
+                let (left, right) = binary_expr.operands();
+                left.has_aggr_expr() || right.has_aggr_expr()
+            }
+            Self::FunctionCall(function_call) => function_call
+                .args()
+                .into_iter()
+                .any(|arg| arg.has_aggr_expr()),
+            Self::CaseExpr(CaseExpr {
+                operand,
+                when_thens,
+                else_result,
+            }) => {
+                operand.iter().any(|operand| operand.has_aggr_expr())
+                    || when_thens
+                        .iter()
+                        .any(|(when, then)| when.has_aggr_expr() || then.has_aggr_expr())
+                    || else_result
+                        .iter()
+                        .any(|else_result| else_result.has_aggr_expr())
+            }
+        }
+    }
+}
+
+impl ValueExpr {
+    /// Infers SQL type of this expression from column types in `pipeline`, before any row is supplied.
+    ///
+    /// Returns `None` when the type cannot be determined statically (e.g. NULL constant).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Branches of a CASE expression do not have a common type.
+    pub fn infer_type(&self, pipeline: &Pipeline) -> Result<Option<SqlType>> {
+        match self {
+            Self::Constant(SqlValue::Null) => Ok(None),
+            Self::Constant(SqlValue::NotNull(nn_sql_value)) => Ok(Some(nn_sql_value.sql_type())),
+
+            Self::ColumnReference(ColumnReference::Column {
+                stream_name,
+                column_name,
+            }) => Ok(pipeline.get_stream_or_table(stream_name).ok().and_then(|stream| {
+                stream
+                    .shape()
+                    .columns()
+                    .iter()
+                    .map(|column| column.column_data_type())
+                    .find(|data_type| data_type.column_name() == column_name)
+                    .map(|data_type| data_type.sql_type().clone())
+            })),
+            Self::ColumnReference(ColumnReference::PTime { .. }) => Ok(Some(SqlType::timestamp())),
+
+            Self::AggrExpr(aggr_expr) => aggr_expr.infer_type(pipeline),
+            Self::AggrExprRef(_) | Self::GroupByRef(_) => Ok(None),
+            Self::AliasRef(alias) => Err(SpringError::Sql(anyhow!(
+                "`{}` is not a column reference (`stream.column`). Aliases can only be referenced in HAVING clause",
+                alias
+            ))),
+
+            Self::UnaryOperator(UnaryOperator::Minus, child) => child.infer_type(pipeline),
+            Self::UnaryOperator(UnaryOperator::Not, child) => {
+                child.infer_type(pipeline)?;
+                Ok(Some(SqlType::boolean()))
+            }
+
+            Self::BinaryExpr(binary_expr) => {
+                let (left, right) = binary_expr.operands();
+                let (left_type, right_type) =
+                    (left.infer_type(pipeline)?, right.infer_type(pipeline)?);
+                match binary_expr {
+                    BinaryExpr::LogicalFunctionVariant(_)
+                    | BinaryExpr::ComparisonFunctionVariant(_) => Ok(Some(SqlType::boolean())),
+                    BinaryExpr::NumericalFunctionVariant(_) => {
+                        Ok(Self::infer_numerical_type(left_type, right_type))
+                    }
                 }
-            },
-            Self::UnaryOperator(op, expr_ph1) => {
-                let expr_ph2 = expr_ph1.resolve_colref(tuple)?;
-                Ok(ValueExprPh2::UnaryOperator(op, Box::new(expr_ph2)))
             }
-            Self::BinaryExpr(bool_expr) => match bool_expr {
-                BinaryExpr::LogicalFunctionVariant(logical_function) => match logical_function {
-                    LogicalFunction::AndVariant { left, right } => {
-                        let left_ph2 = left.resolve_colref(tuple)?;
-                        let right_ph2 = right.resolve_colref(tuple)?;
-                        Ok(ValueExprPh2::BinaryExpr(
-                            BinaryExpr::LogicalFunctionVariant(LogicalFunction::AndVariant {
-                                left: Box::new(left_ph2),
-                                right: Box::new(right_ph2),
-                            }),
-                        ))
+
+            Self::FunctionCall(function_call) => {
+                let arg_types = function_call
+                    .args()
+                    .into_iter()
+                    .map(|arg| arg.infer_type(pipeline))
+                    .collect::<Result<Vec<_>>>()?;
+
+                match function_call {
+                    FunctionCall::DurationMillis { .. } | FunctionCall::DurationSecs { .. } => {
+                        Ok(Some(SqlType::duration()))
                     }
-                },
-                BinaryExpr::ComparisonFunctionVariant(comparison_function) => {
-                    match comparison_function {
-                        ComparisonFunction::EqualVariant { left, right } => {
-                            let left_ph2 = left.resolve_colref(tuple)?;
-                            let right_ph2 = right.resolve_colref(tuple)?;
-                            Ok(ValueExprPh2::BinaryExpr(
-                                BinaryExpr::ComparisonFunctionVariant(
-                                    ComparisonFunction::EqualVariant {
-                                        left: Box::new(left_ph2),
-                                        right: Box::new(right_ph2),
-                                    },
-                                ),
-                            ))
-                        }
+                    FunctionCall::FloorTime { .. } => Ok(Some(SqlType::timestamp())),
+                    FunctionCall::Cast { sql_type, .. } => Ok(Some(sql_type.clone())),
+                    FunctionCall::IsNull { .. }
+                    | FunctionCall::IsNotNull { .. }
+                    | FunctionCall::Like { .. } => Ok(Some(SqlType::boolean())),
+                    FunctionCall::InList { list, .. } => {
+                        let constant_types = match list {
+                            InList::Constants(constants) => constants
+                                .values()
+                                .map(|value| Some(value.sql_type()))
+                                .collect(),
+                            InList::Exprs(_) => vec![],
+                        };
+                        arg_types.into_iter().chain(constant_types).try_fold(
+                            None,
+                            |result_type, arg_type| {
+                                Self::common_type(
+                                    "IN operand and list items",
+                                    result_type,
+                                    arg_type,
+                                )
+                            },
+                        )?;
+                        Ok(Some(SqlType::boolean()))
                     }
-                }
-                BinaryExpr::NumericalFunctionVariant(numerical_function) => {
-                    match numerical_function {
-                        NumericalFunction::AddVariant { left, right } => {
-                            let left_ph2 = left.resolve_colref(tuple)?;
-                            let right_ph2 = right.resolve_colref(tuple)?;
-                            Ok(ValueExprPh2::BinaryExpr(
-                                BinaryExpr::NumericalFunctionVariant(
-                                    NumericalFunction::AddVariant {
-                                        left: Box::new(left_ph2),
-                                        right: Box::new(right_ph2),
-                                    },
-                                ),
-                            ))
-                        }
-                        NumericalFunction::MulVariant { left, right } => {
-                            let left_ph2 = left.resolve_colref(tuple)?;
-                            let right_ph2 = right.resolve_colref(tuple)?;
-                            Ok(ValueExprPh2::BinaryExpr(
-                                BinaryExpr::NumericalFunctionVariant(
-                                    NumericalFunction::MulVariant {
-                                        left: Box::new(left_ph2),
-                                        right: Box::new(right_ph2),
-                                    },
-                                ),
-                            ))
-                        }
+                    FunctionCall::Between { .. } => {
+                        arg_types
+                            .into_iter()
+                            .try_fold(None, |result_type, arg_type| {
+                                Self::common_type("BETWEEN operands", result_type, arg_type)
+                            })?;
+                        Ok(Some(SqlType::boolean()))
+                    }
+                    FunctionCall::Coalesce { .. } => {
+                        arg_types
+                            .into_iter()
+                            .try_fold(None, |result_type, arg_type| {
+                                Self::common_type("COALESCE arguments", result_type, arg_type)
+                            })
+                    }
+                    FunctionCall::NullIf { .. } => {
+                        let left_type = arg_types[0].clone();
+                        Self::common_type(
+                            "NULLIF arguments",
+                            left_type.clone(),
+                            arg_types[1].clone(),
+                        )?;
+                        Ok(left_type)
                     }
+                    FunctionCall::Upper { .. }
+                    | FunctionCall::Lower { .. }
+                    | FunctionCall::Substring { .. }
+                    | FunctionCall::Concat { .. }
+                    | FunctionCall::Trim { .. }
+                    | FunctionCall::Replace { .. } => Ok(Some(SqlType::text())),
+                    FunctionCall::Length { .. } | FunctionCall::Position { .. } => {
+                        Ok(Some(SqlType::big_int()))
+                    }
+                    FunctionCall::Abs { .. }
+                    | FunctionCall::Ceil { .. }
+                    | FunctionCall::Floor { .. }
+                    | FunctionCall::Round { .. } => {
+                        Ok(arg_types[0].clone().map(|arg_type| match arg_type {
+                            SqlType::NumericComparable(NumericComparableType::I64Loose(_)) => {
+                                SqlType::big_int()
+                            }
+                            SqlType::NumericComparable(NumericComparableType::U64Loose(_)) => {
+                                SqlType::unsigned_big_int()
+                            }
+                            _ => SqlType::float(),
+                        }))
+                    }
+                    FunctionCall::Sqrt { .. }
+                    | FunctionCall::Power { .. }
+                    | FunctionCall::Ln { .. }
+                    | FunctionCall::Log10 { .. }
+                    | FunctionCall::Exp { .. }
+                    | FunctionCall::Sin { .. }
+                    | FunctionCall::Cos { .. }
+                    | FunctionCall::Atan2 { .. } => Ok(Some(SqlType::float())),
+                    FunctionCall::Greatest { .. } | FunctionCall::Least { .. } => arg_types
+                        .into_iter()
+                        .try_fold(None, |result_type, arg_type| {
+                            let common_type = Self::common_type(
+                                "GREATEST / LEAST arguments",
+                                result_type.clone(),
+                                arg_type.clone(),
+                            )?;
+                            match common_type {
+                                Some(SqlType::NumericComparable(_)) => {
+                                    Ok(Self::infer_numerical_type(result_type, arg_type))
+                                }
+                                _ => Ok(common_type),
+                            }
+                        }),
+                }
+            }
+
+            Self::CaseExpr(CaseExpr {
+                operand,
+                when_thens,
+                else_result,
+            }) => {
+                // simple CASE compares operand with WHEN values; searched CASE evaluates WHEN conditions.
+                let when_type = match operand {
+                    Some(operand) => operand.infer_type(pipeline)?,
+                    None => Some(SqlType::boolean()),
+                };
+
+                let mut result_type = None;
+                for (when, then) in when_thens {
+                    Self::common_type(
+                        "CASE operand and WHEN values",
+                        when_type.clone(),
+                        when.infer_type(pipeline)?,
+                    )?;
+                    result_type = Self::common_type(
+                        "CASE branches",
+                        result_type,
+                        then.infer_type(pipeline)?,
+                    )?;
+                }
+                if let Some(else_result) = else_result {
+                    result_type = Self::common_type(
+                        "CASE branches",
+                        result_type,
+                        else_result.infer_type(pipeline)?,
+                    )?;
+                }
+                Ok(result_type)
+            }
+        }
+    }
+
+    fn infer_numerical_type(
+        left_type: Option<SqlType>,
+        right_type: Option<SqlType>,
+    ) -> Option<SqlType> {
+        match (left_type, right_type) {
+            (Some(SqlType::TimestampComparable), Some(SqlType::TimestampComparable)) => {
+                Some(SqlType::duration())
+            }
+            (Some(SqlType::TimestampComparable), _) | (_, Some(SqlType::TimestampComparable)) => {
+                Some(SqlType::timestamp())
+            }
+            (
+                Some(SqlType::NumericComparable(left_n)),
+                Some(SqlType::NumericComparable(right_n)),
+            ) => match (left_n, right_n) {
+                (NumericComparableType::F32Loose(_), _)
+                | (_, NumericComparableType::F32Loose(_)) => Some(SqlType::float()),
+                (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
+                    Some(SqlType::unsigned_big_int())
                 }
+                _ => Some(SqlType::big_int()),
             },
+            (Some(typ), _) | (None, Some(typ)) => Some(typ),
+            (None, None) => None,
+        }
+    }
+
+    /// NULL (`None`) is compatible with any type.
+    ///
+    /// `subject` describes what must have the common type, for error messages.
+    fn common_type(
+        subject: &str,
+        type1: Option<SqlType>,
+        type2: Option<SqlType>,
+    ) -> Result<Option<SqlType>> {
+        match (type1, type2) {
+            (Some(type1), Some(type2)) => {
+                if type1.is_comparable_with(&type2) {
+                    Ok(Some(type1))
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "{} must have a common type but got {} and {}",
+                        subject,
+                        type1,
+                        type2
+                    )))
+                }
+            }
+            (Some(typ), None) | (None, Some(typ)) => Ok(Some(typ)),
+            (None, None) => Ok(None),
         }
     }
 }
@@ -145,6 +454,7 @@ pub enum ValueExprPh2 {
     UnaryOperator(UnaryOperator, Box<Self>),
     BinaryExpr(BinaryExpr<Self>),
     FunctionCall(FunctionCall<Self>),
+    CaseExpr(CaseExpr<Self>),
 }
 impl ValueExprType for ValueExprPh2 {}
 
@@ -159,41 +469,135 @@ impl ValueExprPh2 {
                     (UnaryOperator::Minus, SqlValue::NotNull(nn_sql_value)) => {
                         Ok(SqlValue::NotNull(nn_sql_value.negate()?))
                     }
+                    (UnaryOperator::Not, child_sql_value) => {
+                        let b = child_sql_value.to_nullable_bool()?.map(|b| !b);
+                        Ok(SqlValue::from_nullable_bool(b))
+                    }
                 }
             }
             Self::BinaryExpr(bool_expr) => match bool_expr {
                 BinaryExpr::ComparisonFunctionVariant(comparison_function) => {
-                    match comparison_function {
-                        ComparisonFunction::EqualVariant { left, right } => {
-                            let left_sql_value = left.eval()?;
-                            let right_sql_value = right.eval()?;
-                            left_sql_value
-                                .sql_compare(&right_sql_value)
-                                .map(|sql_compare_result| {
-                                    SqlValue::NotNull(NnSqlValue::Boolean(matches!(
-                                        sql_compare_result,
-                                        SqlCompareResult::Eq
-                                    )))
-                                })
-                        }
-                    }
+                    Self::eval_comparison_function(comparison_function)
+                }
+                BinaryExpr::LogicalFunctionVariant(logical_function) => {
+                    Self::eval_logical_function(logical_function)
                 }
-                BinaryExpr::LogicalFunctionVariant(logical_function) => match logical_function {
-                    LogicalFunction::AndVariant { left, right } => {
-                        let left_sql_value = left.eval()?;
-                        let right_sql_value = right.eval()?;
-
-                        let b = left_sql_value.to_bool()? && right_sql_value.to_bool()?;
-                        Ok(SqlValue::NotNull(NnSqlValue::Boolean(b)))
-                    }
-                },
                 BinaryExpr::NumericalFunctionVariant(numerical_function) => {
                     Self::eval_numerical_function(numerical_function)
                 }
             },
             Self::FunctionCall(function_call) => Self::eval_function_call(function_call),
+            Self::CaseExpr(case_expr) => Self::eval_case_expr(case_expr),
+        }
+    }
+
+    /// Only the matched branch is evaluated.
+    fn eval_case_expr(case_expr: CaseExpr<Self>) -> Result<SqlValue> {
+        let operand_value = case_expr
+            .operand
+            .map(|operand| operand.eval())
+            .transpose()?;
+
+        for (when, then) in case_expr.when_thens {
+            let when_value = when.eval()?;
+            let matched = match &operand_value {
+                // simple CASE
+                Some(operand_value) => matches!(
+                    operand_value.sql_compare(&when_value)?,
+                    SqlCompareResult::Eq
+                ),
+                // searched CASE
+                None => when_value.to_bool()?,
+            };
+            if matched {
+                return then.eval();
+            }
         }
+
+        case_expr
+            .else_result
+            .map_or(Ok(SqlValue::Null), |else_result| else_result.eval())
     }
+
+    /// Three-valued logic: NULL is treated as UNKNOWN.
+    fn eval_logical_function(logical_function: LogicalFunction<Self>) -> Result<SqlValue> {
+        match logical_function {
+            LogicalFunction::AndVariant { left, right } => {
+                let left_b = left.eval()?.to_nullable_bool()?;
+                let right_b = right.eval()?.to_nullable_bool()?;
+
+                let b = match (left_b, right_b) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                };
+                Ok(SqlValue::from_nullable_bool(b))
+            }
+            LogicalFunction::OrVariant { left, right } => {
+                let left_b = left.eval()?.to_nullable_bool()?;
+                let right_b = right.eval()?.to_nullable_bool()?;
+
+                let b = match (left_b, right_b) {
+                    (Some(true), _) | (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                };
+                Ok(SqlValue::from_nullable_bool(b))
+            }
+        }
+    }
+
+    fn eval_comparison_function(comparison_function: ComparisonFunction<Self>) -> Result<SqlValue> {
+        match comparison_function {
+            ComparisonFunction::EqualVariant { left, right } => {
+                Self::eval_comparison(*left, *right, |res| matches!(res, SqlCompareResult::Eq))
+            }
+            ComparisonFunction::NotEqualVariant { left, right } => {
+                Self::eval_comparison(*left, *right, |res| !matches!(res, SqlCompareResult::Eq))
+            }
+            ComparisonFunction::LessThanVariant { left, right } => {
+                Self::eval_comparison(*left, *right, |res| {
+                    matches!(res, SqlCompareResult::LessThan)
+                })
+            }
+            ComparisonFunction::LessThanOrEqualVariant { left, right } => {
+                Self::eval_comparison(*left, *right, |res| {
+                    matches!(res, SqlCompareResult::LessThan | SqlCompareResult::Eq)
+                })
+            }
+            ComparisonFunction::GreaterThanVariant { left, right } => {
+                Self::eval_comparison(*left, *right, |res| {
+                    matches!(res, SqlCompareResult::GreaterThan)
+                })
+            }
+            ComparisonFunction::GreaterThanOrEqualVariant { left, right } => {
+                Self::eval_comparison(*left, *right, |res| {
+                    matches!(res, SqlCompareResult::GreaterThan | SqlCompareResult::Eq)
+                })
+            }
+        }
+    }
+
+    /// Comparison with NULL is evaluated to NULL.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `left` and `right` are not in comparable types.
+    fn eval_comparison<F>(left: Self, right: Self, f: F) -> Result<SqlValue>
+    where
+        F: FnOnce(SqlCompareResult) -> bool,
+    {
+        let left_sql_value = left.eval()?;
+        let right_sql_value = right.eval()?;
+        left_sql_value
+            .sql_compare(&right_sql_value)
+            .map(|sql_compare_result| match sql_compare_result {
+                SqlCompareResult::Null => SqlValue::Null,
+                _ => SqlValue::NotNull(NnSqlValue::Boolean(f(sql_compare_result))),
+            })
+    }
+
     fn eval_numerical_function(numerical_function: NumericalFunction<Self>) -> Result<SqlValue> {
         match numerical_function {
             NumericalFunction::AddVariant { left, right } => {
@@ -206,6 +610,21 @@ impl ValueExprPh2 {
                 let right_sql_value = right.eval()?;
                 left_sql_value * right_sql_value
             }
+            NumericalFunction::SubVariant { left, right } => {
+                let left_sql_value = left.eval()?;
+                let right_sql_value = right.eval()?;
+                left_sql_value - right_sql_value
+            }
+            NumericalFunction::DivVariant { left, right } => {
+                let left_sql_value = left.eval()?;
+                let right_sql_value = right.eval()?;
+                left_sql_value / right_sql_value
+            }
+            NumericalFunction::ModVariant { left, right } => {
+                let left_sql_value = left.eval()?;
+                let right_sql_value = right.eval()?;
+                left_sql_value % right_sql_value
+            }
         }
     }
 
@@ -220,6 +639,89 @@ impl ValueExprPh2 {
             FunctionCall::DurationSecs { duration_secs } => {
                 Self::eval_function_duration_secs(*duration_secs)
             }
+            FunctionCall::Cast { target, sql_type } => Self::eval_function_cast(*target, &sql_type),
+            FunctionCall::IsNull { target } => {
+                let value = target.eval()?;
+                Ok(SqlValue::from_nullable_bool(Some(matches!(
+                    value,
+                    SqlValue::Null
+                ))))
+            }
+            FunctionCall::IsNotNull { target } => {
+                let value = target.eval()?;
+                Ok(SqlValue::from_nullable_bool(Some(!matches!(
+                    value,
+                    SqlValue::Null
+                ))))
+            }
+            FunctionCall::Coalesce { args } => Self::eval_function_coalesce(args),
+            FunctionCall::NullIf { left, right } => Self::eval_function_nullif(*left, *right),
+            FunctionCall::Like { target, pattern } => {
+                string_function::like(target.eval()?, pattern.eval()?)
+            }
+            FunctionCall::Upper { target } => string_function::upper(target.eval()?),
+            FunctionCall::Lower { target } => string_function::lower(target.eval()?),
+            FunctionCall::Length { target } => string_function::length(target.eval()?),
+            FunctionCall::Substring {
+                target,
+                start,
+                length,
+            } => string_function::substring(
+                target.eval()?,
+                start.eval()?,
+                length.map(|length| length.eval()).transpose()?,
+            ),
+            FunctionCall::Concat { args } => string_function::concat(
+                args.into_iter()
+                    .map(|arg| arg.eval())
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            FunctionCall::Trim {
+                target,
+                chars,
+                trim_side,
+            } => string_function::trim(
+                target.eval()?,
+                chars.map(|chars| chars.eval()).transpose()?,
+                trim_side,
+            ),
+            FunctionCall::Position { substring, target } => {
+                string_function::position(substring.eval()?, target.eval()?)
+            }
+            FunctionCall::Replace { target, from, to } => {
+                string_function::replace(target.eval()?, from.eval()?, to.eval()?)
+            }
+            FunctionCall::Abs { target } => math_function::abs(target.eval()?),
+            FunctionCall::Ceil { target } => math_function::ceil(target.eval()?),
+            FunctionCall::Floor { target } => math_function::floor(target.eval()?),
+            FunctionCall::Round { target, digits } => math_function::round(
+                target.eval()?,
+                digits.map(|digits| digits.eval()).transpose()?,
+            ),
+            FunctionCall::Sqrt { target } => math_function::sqrt(target.eval()?),
+            FunctionCall::Power { base, exponent } => {
+                math_function::power(base.eval()?, exponent.eval()?)
+            }
+            FunctionCall::Ln { target } => math_function::ln(target.eval()?),
+            FunctionCall::Log10 { target } => math_function::log10(target.eval()?),
+            FunctionCall::Exp { target } => math_function::exp(target.eval()?),
+            FunctionCall::Sin { target } => math_function::sin(target.eval()?),
+            FunctionCall::Cos { target } => math_function::cos(target.eval()?),
+            FunctionCall::Atan2 { y, x } => math_function::atan2(y.eval()?, x.eval()?),
+            FunctionCall::Greatest { args } => math_function::greatest(
+                args.into_iter()
+                    .map(|arg| arg.eval())
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            FunctionCall::Least { args } => math_function::least(
+                args.into_iter()
+                    .map(|arg| arg.eval())
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            FunctionCall::InList { target, list } => Self::eval_function_in_list(*target, list),
+            FunctionCall::Between { target, low, high } => {
+                Self::eval_function_between(*target, *low, *high)
+            }
         }
     }
 
@@ -269,11 +771,156 @@ impl ValueExprPh2 {
             )))
         }
     }
+
+    /// Arguments after the first non-NULL one are not evaluated.
+    fn eval_function_coalesce(args: Vec<Self>) -> Result<SqlValue> {
+        for arg in args {
+            let value = arg.eval()?;
+            if !matches!(value, SqlValue::Null) {
+                return Ok(value);
+            }
+        }
+        Ok(SqlValue::Null)
+    }
+
+    fn eval_function_nullif(left: Self, right: Self) -> Result<SqlValue> {
+        let left_value = left.eval()?;
+        let right_value = right.eval()?;
+        match left_value.sql_compare(&right_value)? {
+            SqlCompareResult::Eq => Ok(SqlValue::Null),
+            _ => Ok(left_value),
+        }
+    }
+
+    /// NULL when `target` is NULL, or when no item matches but the list contains NULL.
+    ///
+    /// Items after the first match are not evaluated.
+    fn eval_function_in_list(target: Self, list: InList<Self>) -> Result<SqlValue> {
+        let target_value = match target.eval()? {
+            SqlValue::Null => return Ok(SqlValue::Null),
+            SqlValue::NotNull(nn_sql_value) => nn_sql_value,
+        };
+
+        let b = match list {
+            InList::Constants(constants) => constants.contains(&target_value),
+            InList::Exprs(exprs) => {
+                let mut has_null = false;
+                let mut found = false;
+                for expr in exprs {
+                    match expr.eval()? {
+                        SqlValue::Null => has_null = true,
+                        SqlValue::NotNull(item) => {
+                            if matches!(target_value.sql_compare(&item)?, SqlCompareResult::Eq) {
+                                found = true;
+                                break;
+                            }
+                        }
+                    }
+                }
+                match (found, has_null) {
+                    (true, _) => Some(true),
+                    (false, true) => None,
+                    (false, false) => Some(false),
+                }
+            }
+        };
+        Ok(SqlValue::from_nullable_bool(b))
+    }
+
+    /// `low <= target AND target <= high` in three-valued logic.
+    fn eval_function_between(target: Self, low: Self, high: Self) -> Result<SqlValue> {
+        let target_value = target.eval()?;
+        let low_value = low.eval()?;
+        let high_value = high.eval()?;
+
+        let ge_low = match target_value.sql_compare(&low_value)? {
+            SqlCompareResult::Null => None,
+            res => Some(matches!(
+                res,
+                SqlCompareResult::GreaterThan | SqlCompareResult::Eq
+            )),
+        };
+        let le_high = match target_value.sql_compare(&high_value)? {
+            SqlCompareResult::Null => None,
+            res => Some(matches!(
+                res,
+                SqlCompareResult::LessThan | SqlCompareResult::Eq
+            )),
+        };
+
+        let b = match (ge_low, le_high) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        };
+        Ok(SqlValue::from_nullable_bool(b))
+    }
+
+    fn eval_function_cast(target: Self, sql_type: &SqlType) -> Result<SqlValue> {
+        match target.eval()? {
+            SqlValue::Null => Ok(SqlValue::Null),
+            SqlValue::NotNull(nn_sql_value) => {
+                nn_sql_value.try_cast(sql_type).map(SqlValue::NotNull)
+            }
+        }
+    }
 }
 
 /// Aggregate expression.
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Hash, Debug)]
 pub struct AggrExpr {
     pub func: AggregateFunctionParameter,
     pub aggregated: ValueExpr,
+    /// Second argument of ARG_MIN and ARG_MAX, whose minimum / maximum selects the aggregated value.
+    pub by: Option<ValueExpr>,
+}
+
+impl AggrExpr {
+    /// Infers the result type of the aggregate function. `None` when the type cannot be determined statically.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `aggregated` is ill-typed.
+    ///   - A numeric aggregate function (SUM, AVG, STDDEV_*, VAR_* or PERCENTILE_APPROX) takes a non-numeric value.
+    pub fn infer_type(&self, pipeline: &Pipeline) -> Result<Option<SqlType>> {
+        let aggregated_type = self.aggregated.infer_type(pipeline)?;
+        if let Some(by) = &self.by {
+            by.infer_type(pipeline)?;
+        }
+        match self.func {
+            AggregateFunctionParameter::Count
+            | AggregateFunctionParameter::CountDistinct
+            | AggregateFunctionParameter::ApproxCountDistinct => Ok(Some(SqlType::big_int())),
+            AggregateFunctionParameter::Min
+            | AggregateFunctionParameter::Max
+            | AggregateFunctionParameter::FirstValue
+            | AggregateFunctionParameter::LastValue
+            | AggregateFunctionParameter::ArgMin
+            | AggregateFunctionParameter::ArgMax => Ok(aggregated_type),
+            AggregateFunctionParameter::Avg
+            | AggregateFunctionParameter::Sum
+            | AggregateFunctionParameter::StddevPop
+            | AggregateFunctionParameter::StddevSamp
+            | AggregateFunctionParameter::VarPop
+            | AggregateFunctionParameter::VarSamp
+            | AggregateFunctionParameter::PercentileApprox { .. } => match aggregated_type {
+                None => Ok(None),
+                Some(SqlType::NumericComparable(n)) => Ok(Some(match (self.func, n) {
+                    (AggregateFunctionParameter::Sum, NumericComparableType::I64Loose(_)) => {
+                        SqlType::big_int()
+                    }
+                    (AggregateFunctionParameter::Sum, NumericComparableType::U64Loose(_)) => {
+                        SqlType::unsigned_big_int()
+                    }
+                    _ => SqlType::float(),
+                })),
+                Some(typ) => Err(SpringError::Sql(anyhow!(
+                    "{:?} takes a numeric value but got {}",
+                    self.func,
+                    typ
+                ))),
+            },
+        }
+    }
 }
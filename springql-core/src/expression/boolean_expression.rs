@@ -8,7 +8,7 @@ pub use comparison_function::ComparisonFunction;
 pub use logical_function::LogicalFunction;
 pub use numerical_function::NumericalFunction;
 
-use crate::expression::ValueExprType;
+use crate::{api::error::Result, expression::ValueExprType};
 
 /// Boolean expression.
 #[allow(clippy::enum_variant_names)]
@@ -25,3 +25,133 @@ where
 
     NumericalFunctionVariant(NumericalFunction<E>),
 }
+
+impl<E> BinaryExpr<E>
+where
+    E: ValueExprType,
+{
+    /// Left and right operands
+    pub fn operands(&self) -> (&E, &E) {
+        match self {
+            BinaryExpr::LogicalFunctionVariant(logical_function) => match logical_function {
+                LogicalFunction::AndVariant { left, right }
+                | LogicalFunction::OrVariant { left, right } => (left, right),
+            },
+            BinaryExpr::ComparisonFunctionVariant(comparison_function) => match comparison_function
+            {
+                ComparisonFunction::EqualVariant { left, right }
+                | ComparisonFunction::NotEqualVariant { left, right }
+                | ComparisonFunction::LessThanVariant { left, right }
+                | ComparisonFunction::LessThanOrEqualVariant { left, right }
+                | ComparisonFunction::GreaterThanVariant { left, right }
+                | ComparisonFunction::GreaterThanOrEqualVariant { left, right } => (left, right),
+            },
+            BinaryExpr::NumericalFunctionVariant(numerical_function) => match numerical_function {
+                NumericalFunction::AddVariant { left, right }
+                | NumericalFunction::SubVariant { left, right }
+                | NumericalFunction::MulVariant { left, right }
+                | NumericalFunction::DivVariant { left, right }
+                | NumericalFunction::ModVariant { left, right } => (left, right),
+            },
+        }
+    }
+
+    /// Converts both operands by `f`, keeping the operator.
+    pub fn try_map<E2, F>(self, mut f: F) -> Result<BinaryExpr<E2>>
+    where
+        E2: ValueExprType,
+        F: FnMut(E) -> Result<E2>,
+    {
+        let mut map_box = |e: Box<E>| f(*e).map(Box::new);
+
+        let binary_expr = match self {
+            BinaryExpr::LogicalFunctionVariant(logical_function) => {
+                BinaryExpr::LogicalFunctionVariant(match logical_function {
+                    LogicalFunction::AndVariant { left, right } => LogicalFunction::AndVariant {
+                        left: map_box(left)?,
+                        right: map_box(right)?,
+                    },
+                    LogicalFunction::OrVariant { left, right } => LogicalFunction::OrVariant {
+                        left: map_box(left)?,
+                        right: map_box(right)?,
+                    },
+                })
+            }
+            BinaryExpr::ComparisonFunctionVariant(comparison_function) => {
+                BinaryExpr::ComparisonFunctionVariant(match comparison_function {
+                    ComparisonFunction::EqualVariant { left, right } => {
+                        ComparisonFunction::EqualVariant {
+                            left: map_box(left)?,
+                            right: map_box(right)?,
+                        }
+                    }
+                    ComparisonFunction::NotEqualVariant { left, right } => {
+                        ComparisonFunction::NotEqualVariant {
+                            left: map_box(left)?,
+                            right: map_box(right)?,
+                        }
+                    }
+                    ComparisonFunction::LessThanVariant { left, right } => {
+                        ComparisonFunction::LessThanVariant {
+                            left: map_box(left)?,
+                            right: map_box(right)?,
+                        }
+                    }
+                    ComparisonFunction::LessThanOrEqualVariant { left, right } => {
+                        ComparisonFunction::LessThanOrEqualVariant {
+                            left: map_box(left)?,
+                            right: map_box(right)?,
+                        }
+                    }
+                    ComparisonFunction::GreaterThanVariant { left, right } => {
+                        ComparisonFunction::GreaterThanVariant {
+                            left: map_box(left)?,
+                            right: map_box(right)?,
+                        }
+                    }
+                    ComparisonFunction::GreaterThanOrEqualVariant { left, right } => {
+                        ComparisonFunction::GreaterThanOrEqualVariant {
+                            left: map_box(left)?,
+                            right: map_box(right)?,
+                        }
+                    }
+                })
+            }
+            BinaryExpr::NumericalFunctionVariant(numerical_function) => {
+                BinaryExpr::NumericalFunctionVariant(match numerical_function {
+                    NumericalFunction::AddVariant { left, right } => {
+                        NumericalFunction::AddVariant {
+                            left: map_box(left)?,
+                            right: map_box(right)?,
+                        }
+                    }
+                    NumericalFunction::SubVariant { left, right } => {
+                        NumericalFunction::SubVariant {
+                            left: map_box(left)?,
+                            right: map_box(right)?,
+                        }
+                    }
+                    NumericalFunction::MulVariant { left, right } => {
+                        NumericalFunction::MulVariant {
+                            left: map_box(left)?,
+                            right: map_box(right)?,
+                        }
+                    }
+                    NumericalFunction::DivVariant { left, right } => {
+                        NumericalFunction::DivVariant {
+                            left: map_box(left)?,
+                            right: map_box(right)?,
+                        }
+                    }
+                    NumericalFunction::ModVariant { left, right } => {
+                        NumericalFunction::ModVariant {
+                            left: map_box(left)?,
+                            right: map_box(right)?,
+                        }
+                    }
+                })
+            }
+        };
+        Ok(binary_expr)
+    }
+}
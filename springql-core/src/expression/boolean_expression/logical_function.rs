@@ -15,4 +15,12 @@ where
         /// Right operand
         right: Box<E>,
     },
+
+    /// `OR` operation
+    OrVariant {
+        /// Left operand
+        left: Box<E>,
+        /// Right operand
+        right: Box<E>,
+    },
 }
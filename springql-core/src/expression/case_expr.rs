@@ -0,0 +1,58 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{api::error::Result, expression::ValueExprType};
+
+/// `CASE` expression.
+///
+/// ```text
+/// -- searched CASE
+/// CASE WHEN s.speed > 100 THEN 'fast' WHEN s.speed > 50 THEN 'normal' ELSE 'slow' END
+///
+/// -- simple CASE
+/// CASE s.gear WHEN 0 THEN 'neutral' WHEN -1 THEN 'reverse' ELSE 'drive' END
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CaseExpr<E>
+where
+    E: ValueExprType,
+{
+    /// Compared with each `WHEN` value in simple CASE. None for searched CASE.
+    pub operand: Option<Box<E>>,
+
+    /// Pairs of `WHEN` condition (value for simple CASE) and `THEN` result, evaluated in order.
+    pub when_thens: Vec<(E, E)>,
+
+    /// Evaluated to NULL if `ELSE` is omitted and no `WHEN` matches.
+    pub else_result: Option<Box<E>>,
+}
+
+impl<E> CaseExpr<E>
+where
+    E: ValueExprType,
+{
+    /// Converts every operand, condition and result by `f`.
+    pub fn try_map<E2, F>(self, mut f: F) -> Result<CaseExpr<E2>>
+    where
+        E2: ValueExprType,
+        F: FnMut(E) -> Result<E2>,
+    {
+        let operand = self
+            .operand
+            .map(|operand| f(*operand).map(Box::new))
+            .transpose()?;
+        let when_thens = self
+            .when_thens
+            .into_iter()
+            .map(|(when, then)| Ok((f(when)?, f(then)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let else_result = self
+            .else_result
+            .map(|else_result| f(*else_result).map(Box::new))
+            .transpose()?;
+        Ok(CaseExpr {
+            operand,
+            when_thens,
+            else_result,
+        })
+    }
+}
@@ -1,6 +1,10 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::expression::ValueExprType;
+use crate::{
+    api::error::Result,
+    expression::{InList, ValueExprType},
+    pipeline::SqlType,
+};
 
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum FunctionCall<E>
@@ -21,4 +25,447 @@ where
     /// FLOOR_TIME("2020-01-01 01:11:11.000000000", DURATION_SECS(10 * 60)) -> "2020-01-01 01:10:00.000000000"
     /// ```
     FloorTime { target: Box<E>, resolution: Box<E> },
+
+    /// ```text
+    /// CAST('42' AS INTEGER) -> 42
+    /// ```
+    Cast { target: Box<E>, sql_type: SqlType },
+
+    /// ```text
+    /// NULL IS NULL -> TRUE
+    /// ```
+    IsNull { target: Box<E> },
+
+    /// ```text
+    /// NULL IS NOT NULL -> FALSE
+    /// ```
+    IsNotNull { target: Box<E> },
+
+    /// ```text
+    /// COALESCE(NULL, 1, 2) -> 1
+    /// ```
+    Coalesce { args: Vec<E> },
+
+    /// ```text
+    /// NULLIF(1, 1) -> NULL
+    /// NULLIF(1, 2) -> 1
+    /// ```
+    NullIf { left: Box<E>, right: Box<E> },
+
+    /// `%` matches any sequence of characters and `_` matches any single character. `\` escapes them.
+    ///
+    /// ```text
+    /// 'sensor/room1/temp' LIKE 'sensor/%' -> TRUE
+    /// ```
+    Like { target: Box<E>, pattern: Box<E> },
+
+    /// ```text
+    /// UPPER('abc') -> 'ABC'
+    /// ```
+    Upper { target: Box<E> },
+
+    /// ```text
+    /// LOWER('ABC') -> 'abc'
+    /// ```
+    Lower { target: Box<E> },
+
+    /// Number of characters.
+    ///
+    /// ```text
+    /// LENGTH('🚔🚓') -> 2
+    /// ```
+    Length { target: Box<E> },
+
+    /// `start` is 1-origin. Takes the rest of `target` when `length` is omitted.
+    ///
+    /// ```text
+    /// SUBSTRING('abcde', 2, 3) -> 'bcd'
+    /// SUBSTRING('abcde' FROM 2) -> 'bcde'
+    /// ```
+    Substring {
+        target: Box<E>,
+        start: Box<E>,
+        length: Option<Box<E>>,
+    },
+
+    /// Non-TEXT arguments are casted into TEXT.
+    ///
+    /// ```text
+    /// CONCAT('id-', 42) -> 'id-42'
+    /// 'id-' || 42 -> 'id-42'
+    /// ```
+    Concat { args: Vec<E> },
+
+    /// Removes `chars` (a space when omitted) from `target`.
+    ///
+    /// ```text
+    /// TRIM('  abc  ') -> 'abc'
+    /// TRIM(LEADING 'x' FROM 'xxabcxx') -> 'abcxx'
+    /// ```
+    Trim {
+        target: Box<E>,
+        chars: Option<Box<E>>,
+        trim_side: TrimSide,
+    },
+
+    /// 1-origin position of `substring` in `target`. 0 when not found.
+    ///
+    /// ```text
+    /// POSITION('c' IN 'abc') -> 3
+    /// ```
+    Position { substring: Box<E>, target: Box<E> },
+
+    /// ```text
+    /// REPLACE('a/b/c', '/', '.') -> 'a.b.c'
+    /// ```
+    Replace {
+        target: Box<E>,
+        from: Box<E>,
+        to: Box<E>,
+    },
+
+    /// ```text
+    /// ABS(-1) -> 1
+    /// ```
+    Abs { target: Box<E> },
+
+    /// ```text
+    /// CEIL(1.2) -> 2.0
+    /// ```
+    Ceil { target: Box<E> },
+
+    /// ```text
+    /// FLOOR(1.8) -> 1.0
+    /// ```
+    Floor { target: Box<E> },
+
+    /// Rounds half away from zero into `digits` decimal places (0 when omitted). Negative `digits` rounds integer part.
+    ///
+    /// ```text
+    /// ROUND(1.25, 1) -> 1.3
+    /// ROUND(1250, -2) -> 1300
+    /// ```
+    Round {
+        target: Box<E>,
+        digits: Option<Box<E>>,
+    },
+
+    /// ```text
+    /// SQRT(4) -> 2.0
+    /// ```
+    Sqrt { target: Box<E> },
+
+    /// ```text
+    /// POWER(2, 10) -> 1024.0
+    /// ```
+    Power { base: Box<E>, exponent: Box<E> },
+
+    /// Natural logarithm.
+    ///
+    /// ```text
+    /// LN(1) -> 0.0
+    /// ```
+    Ln { target: Box<E> },
+
+    /// ```text
+    /// LOG10(100) -> 2.0
+    /// ```
+    Log10 { target: Box<E> },
+
+    /// ```text
+    /// EXP(0) -> 1.0
+    /// ```
+    Exp { target: Box<E> },
+
+    /// Sine of radians.
+    ///
+    /// ```text
+    /// SIN(0) -> 0.0
+    /// ```
+    Sin { target: Box<E> },
+
+    /// Cosine of radians.
+    ///
+    /// ```text
+    /// COS(0) -> 1.0
+    /// ```
+    Cos { target: Box<E> },
+
+    /// Arc tangent of `y / x` in radians, using signs of both to determine the quadrant.
+    ///
+    /// ```text
+    /// ATAN2(1, 1) -> 0.7853982
+    /// ```
+    Atan2 { y: Box<E>, x: Box<E> },
+
+    /// Largest value ignoring NULLs. NULL when all arguments are NULL.
+    ///
+    /// ```text
+    /// GREATEST(1, NULL, 2.5) -> 2.5
+    /// ```
+    Greatest { args: Vec<E> },
+
+    /// Smallest value ignoring NULLs. NULL when all arguments are NULL.
+    ///
+    /// ```text
+    /// LEAST(1, NULL, 2.5) -> 1.0
+    /// ```
+    Least { args: Vec<E> },
+
+    /// `NOT IN` is represented as `UnaryOperator::Not` wrapping this.
+    ///
+    /// ```text
+    /// 2 IN (1, 2, 3) -> TRUE
+    /// 4 IN (1, 2, NULL) -> NULL
+    /// ```
+    InList { target: Box<E>, list: InList<E> },
+
+    /// `NOT BETWEEN` is represented as `UnaryOperator::Not` wrapping this.
+    ///
+    /// ```text
+    /// 2 BETWEEN 1 AND 3 -> TRUE
+    /// ```
+    Between {
+        target: Box<E>,
+        low: Box<E>,
+        high: Box<E>,
+    },
+}
+
+/// Which side of a string `TRIM` removes characters from.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum TrimSide {
+    Leading,
+    Trailing,
+    Both,
+}
+
+impl<E> FunctionCall<E>
+where
+    E: ValueExprType,
+{
+    /// Arguments of this function call.
+    pub fn args(&self) -> Vec<&E> {
+        match self {
+            FunctionCall::DurationMillis {
+                duration_millis: arg,
+            }
+            | FunctionCall::DurationSecs { duration_secs: arg }
+            | FunctionCall::Cast { target: arg, .. }
+            | FunctionCall::IsNull { target: arg }
+            | FunctionCall::IsNotNull { target: arg }
+            | FunctionCall::Upper { target: arg }
+            | FunctionCall::Lower { target: arg }
+            | FunctionCall::Length { target: arg }
+            | FunctionCall::Abs { target: arg }
+            | FunctionCall::Ceil { target: arg }
+            | FunctionCall::Floor { target: arg }
+            | FunctionCall::Sqrt { target: arg }
+            | FunctionCall::Ln { target: arg }
+            | FunctionCall::Log10 { target: arg }
+            | FunctionCall::Exp { target: arg }
+            | FunctionCall::Sin { target: arg }
+            | FunctionCall::Cos { target: arg } => vec![arg],
+            FunctionCall::FloorTime {
+                target: arg1,
+                resolution: arg2,
+            }
+            | FunctionCall::NullIf {
+                left: arg1,
+                right: arg2,
+            }
+            | FunctionCall::Like {
+                target: arg1,
+                pattern: arg2,
+            }
+            | FunctionCall::Position {
+                substring: arg1,
+                target: arg2,
+            }
+            | FunctionCall::Power {
+                base: arg1,
+                exponent: arg2,
+            }
+            | FunctionCall::Atan2 { y: arg1, x: arg2 } => vec![arg1, arg2],
+            FunctionCall::Coalesce { args }
+            | FunctionCall::Concat { args }
+            | FunctionCall::Greatest { args }
+            | FunctionCall::Least { args } => args.iter().collect(),
+            FunctionCall::Substring {
+                target,
+                start,
+                length,
+            } => [Some(target), Some(start), length.as_ref()]
+                .into_iter()
+                .flatten()
+                .map(AsRef::as_ref)
+                .collect(),
+            FunctionCall::Trim {
+                target,
+                chars: optional_arg,
+                ..
+            }
+            | FunctionCall::Round {
+                target,
+                digits: optional_arg,
+            } => [Some(target), optional_arg.as_ref()]
+                .into_iter()
+                .flatten()
+                .map(AsRef::as_ref)
+                .collect(),
+            FunctionCall::Replace { target, from, to }
+            | FunctionCall::Between {
+                target,
+                low: from,
+                high: to,
+            } => vec![target, from, to],
+            FunctionCall::InList { target, list } => {
+                let mut args = vec![target.as_ref()];
+                if let InList::Exprs(exprs) = list {
+                    args.extend(exprs);
+                }
+                args
+            }
+        }
+    }
+
+    /// Converts every argument by `f`, keeping the function and its parameters.
+    pub fn try_map<E2, F>(self, mut f: F) -> Result<FunctionCall<E2>>
+    where
+        E2: ValueExprType,
+        F: FnMut(E) -> Result<E2>,
+    {
+        let function_call = match self {
+            FunctionCall::DurationMillis { duration_millis } => FunctionCall::DurationMillis {
+                duration_millis: f(*duration_millis).map(Box::new)?,
+            },
+            FunctionCall::DurationSecs { duration_secs } => FunctionCall::DurationSecs {
+                duration_secs: f(*duration_secs).map(Box::new)?,
+            },
+            FunctionCall::FloorTime { target, resolution } => FunctionCall::FloorTime {
+                target: f(*target).map(Box::new)?,
+                resolution: f(*resolution).map(Box::new)?,
+            },
+            FunctionCall::Cast { target, sql_type } => FunctionCall::Cast {
+                target: f(*target).map(Box::new)?,
+                sql_type,
+            },
+            FunctionCall::IsNull { target } => FunctionCall::IsNull {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::IsNotNull { target } => FunctionCall::IsNotNull {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Coalesce { args } => FunctionCall::Coalesce {
+                args: args.into_iter().map(&mut f).collect::<Result<_>>()?,
+            },
+            FunctionCall::NullIf { left, right } => FunctionCall::NullIf {
+                left: f(*left).map(Box::new)?,
+                right: f(*right).map(Box::new)?,
+            },
+            FunctionCall::Like { target, pattern } => FunctionCall::Like {
+                target: f(*target).map(Box::new)?,
+                pattern: f(*pattern).map(Box::new)?,
+            },
+            FunctionCall::Upper { target } => FunctionCall::Upper {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Lower { target } => FunctionCall::Lower {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Length { target } => FunctionCall::Length {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Substring {
+                target,
+                start,
+                length,
+            } => FunctionCall::Substring {
+                target: f(*target).map(Box::new)?,
+                start: f(*start).map(Box::new)?,
+                length: length.map(|length| f(*length).map(Box::new)).transpose()?,
+            },
+            FunctionCall::Concat { args } => FunctionCall::Concat {
+                args: args.into_iter().map(&mut f).collect::<Result<_>>()?,
+            },
+            FunctionCall::Trim {
+                target,
+                chars,
+                trim_side,
+            } => FunctionCall::Trim {
+                target: f(*target).map(Box::new)?,
+                chars: chars.map(|chars| f(*chars).map(Box::new)).transpose()?,
+                trim_side,
+            },
+            FunctionCall::Position { substring, target } => FunctionCall::Position {
+                substring: f(*substring).map(Box::new)?,
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Replace { target, from, to } => FunctionCall::Replace {
+                target: f(*target).map(Box::new)?,
+                from: f(*from).map(Box::new)?,
+                to: f(*to).map(Box::new)?,
+            },
+            FunctionCall::Abs { target } => FunctionCall::Abs {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Ceil { target } => FunctionCall::Ceil {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Floor { target } => FunctionCall::Floor {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Round { target, digits } => FunctionCall::Round {
+                target: f(*target).map(Box::new)?,
+                digits: digits.map(|digits| f(*digits).map(Box::new)).transpose()?,
+            },
+            FunctionCall::Sqrt { target } => FunctionCall::Sqrt {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Power { base, exponent } => FunctionCall::Power {
+                base: f(*base).map(Box::new)?,
+                exponent: f(*exponent).map(Box::new)?,
+            },
+            FunctionCall::Ln { target } => FunctionCall::Ln {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Log10 { target } => FunctionCall::Log10 {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Exp { target } => FunctionCall::Exp {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Sin { target } => FunctionCall::Sin {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Cos { target } => FunctionCall::Cos {
+                target: f(*target).map(Box::new)?,
+            },
+            FunctionCall::Atan2 { y, x } => FunctionCall::Atan2 {
+                y: f(*y).map(Box::new)?,
+                x: f(*x).map(Box::new)?,
+            },
+            FunctionCall::Greatest { args } => FunctionCall::Greatest {
+                args: args.into_iter().map(&mut f).collect::<Result<_>>()?,
+            },
+            FunctionCall::Least { args } => FunctionCall::Least {
+                args: args.into_iter().map(&mut f).collect::<Result<_>>()?,
+            },
+            FunctionCall::InList { target, list } => FunctionCall::InList {
+                target: f(*target).map(Box::new)?,
+                list: match list {
+                    InList::Constants(constants) => InList::Constants(constants),
+                    InList::Exprs(exprs) => {
+                        InList::Exprs(exprs.into_iter().map(&mut f).collect::<Result<_>>()?)
+                    }
+                },
+            },
+            FunctionCall::Between { target, low, high } => FunctionCall::Between {
+                target: f(*target).map(Box::new)?,
+                low: f(*low).map(Box::new)?,
+                high: f(*high).map(Box::new)?,
+            },
+        };
+        Ok(function_call)
+    }
 }
@@ -0,0 +1,111 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{
+    collections::HashSet,
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use crate::{
+    expression::ValueExprType,
+    stream_engine::{NnSqlValue, SqlValue},
+};
+
+/// Right-hand side of `IN (...)`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum InList<E>
+where
+    E: ValueExprType,
+{
+    /// All the items are constants, which are looked up by hash.
+    Constants(ConstantSet),
+
+    /// Some of the items need evaluation, which are compared one by one.
+    Exprs(Vec<E>),
+}
+
+/// Set of constants in an IN list.
+///
+/// Shared by `Arc` since value expressions are cloned for every row to evaluate.
+#[derive(Clone, Debug)]
+pub struct ConstantSet {
+    values: Arc<HashSet<NnSqlValue>>,
+    has_null: bool,
+}
+
+impl ConstantSet {
+    pub fn new(constants: Vec<SqlValue>) -> Self {
+        let has_null = constants.iter().any(|v| matches!(v, SqlValue::Null));
+        let values = constants
+            .into_iter()
+            .filter_map(|v| match v {
+                SqlValue::Null => None,
+                SqlValue::NotNull(nn_sql_value) => Some(nn_sql_value),
+            })
+            .collect();
+        Self {
+            values: Arc::new(values),
+            has_null,
+        }
+    }
+
+    /// Non-NULL constants in the set.
+    pub fn values(&self) -> impl Iterator<Item = &NnSqlValue> {
+        self.values.iter()
+    }
+
+    /// `Some(true)` if `value` is in the set, `None` (NULL) if not found but the set has NULL, `Some(false)` otherwise.
+    pub fn contains(&self, value: &NnSqlValue) -> Option<bool> {
+        if self.values.contains(value) {
+            Some(true)
+        } else if self.has_null {
+            None
+        } else {
+            Some(false)
+        }
+    }
+}
+
+impl PartialEq for ConstantSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.has_null == other.has_null
+            && (Arc::ptr_eq(&self.values, &other.values) || self.values == other.values)
+    }
+}
+impl Eq for ConstantSet {}
+
+impl Hash for ConstantSet {
+    /// Cheap hash (only from the size) which is consistent with `Eq`.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.values.len().hash(state);
+        self.has_null.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ordered_float::OrderedFloat;
+
+    use super::*;
+
+    #[test]
+    fn test_constant_set_contains() {
+        let set = ConstantSet::new(vec![
+            SqlValue::NotNull(NnSqlValue::SmallInt(0x123)),
+            SqlValue::NotNull(NnSqlValue::Integer(0x456)),
+        ]);
+        assert_eq!(set.contains(&NnSqlValue::BigInt(0x123)), Some(true));
+        assert_eq!(
+            set.contains(&NnSqlValue::Float(OrderedFloat(1110.0))),
+            Some(true)
+        );
+        assert_eq!(set.contains(&NnSqlValue::UnsignedInteger(1)), Some(false));
+
+        let set_with_null = ConstantSet::new(vec![
+            SqlValue::NotNull(NnSqlValue::Integer(1)),
+            SqlValue::Null,
+        ]);
+        assert_eq!(set_with_null.contains(&NnSqlValue::Integer(1)), Some(true));
+        assert_eq!(set_with_null.contains(&NnSqlValue::Integer(2)), None);
+    }
+}
@@ -0,0 +1,378 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Evaluation of math functions.
+//!
+//! Type promotion follows arithmetic operators:
+//!
+//! - `ABS`, `CEIL`, `FLOOR` and `ROUND` keep integers as integers (BIGINT or UNSIGNED BIGINT) and FLOAT as FLOAT.
+//! - `SQRT`, `POWER`, `LN`, `LOG10`, `EXP`, `SIN`, `COS` and `ATAN2` are calculated in FLOAT.
+//! - `GREATEST` and `LEAST` widen numeric values of different types into a common type.
+//!
+//! All functions but `GREATEST` and `LEAST` return NULL if any of their arguments is NULL.
+
+use anyhow::anyhow;
+use ordered_float::OrderedFloat;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{I64LooseType, NumericComparableType, SqlType},
+    stream_engine::{NnSqlValue, SqlCompareResult, SqlValue},
+};
+
+/// Loosely typed numeric value.
+#[derive(Copy, Clone, Debug)]
+enum Numeric {
+    I64(i64),
+    U64(u64),
+    F32(f32),
+}
+
+impl Numeric {
+    fn to_f64(self) -> f64 {
+        match self {
+            Numeric::I64(i) => i as f64,
+            Numeric::U64(u) => u as f64,
+            Numeric::F32(f) => f as f64,
+        }
+    }
+
+    fn into_sql_value(self) -> SqlValue {
+        let nn_sql_value = match self {
+            Numeric::I64(i) => NnSqlValue::BigInt(i),
+            Numeric::U64(u) => NnSqlValue::UnsignedBigInt(u),
+            Numeric::F32(f) => NnSqlValue::Float(OrderedFloat(f)),
+        };
+        SqlValue::NotNull(nn_sql_value)
+    }
+}
+
+pub(super) fn abs(target: SqlValue) -> Result<SqlValue> {
+    map_numeric(target, "ABS", |n| match n {
+        Numeric::I64(i) => i
+            .checked_abs()
+            .map(Numeric::I64)
+            .ok_or_else(|| out_of_range_err("ABS")),
+        Numeric::U64(_) => Ok(n),
+        Numeric::F32(f) => Ok(Numeric::F32(f.abs())),
+    })
+}
+
+pub(super) fn ceil(target: SqlValue) -> Result<SqlValue> {
+    map_numeric(target, "CEIL", |n| match n {
+        Numeric::I64(_) | Numeric::U64(_) => Ok(n),
+        Numeric::F32(f) => Ok(Numeric::F32(f.ceil())),
+    })
+}
+
+pub(super) fn floor(target: SqlValue) -> Result<SqlValue> {
+    map_numeric(target, "FLOOR", |n| match n {
+        Numeric::I64(_) | Numeric::U64(_) => Ok(n),
+        Numeric::F32(f) => Ok(Numeric::F32(f.floor())),
+    })
+}
+
+/// # Failures
+///
+/// - `SpringError::Sql` when:
+///   - Rounded value is out of range.
+pub(super) fn round(target: SqlValue, digits: Option<SqlValue>) -> Result<SqlValue> {
+    let digits = match digits {
+        Some(digits) => match numeric_arg(digits, "ROUND")? {
+            Some(Numeric::I64(d)) => d,
+            Some(Numeric::U64(d)) => i64::try_from(d).unwrap_or(i64::MAX),
+            Some(Numeric::F32(_)) => {
+                return Err(SpringError::Sql(anyhow!(
+                    "ROUND takes integer as decimal places"
+                )))
+            }
+            None => return Ok(SqlValue::Null),
+        },
+        None => 0,
+    };
+    let digits = digits.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+
+    map_numeric(target, "ROUND", |n| match n {
+        Numeric::I64(_) | Numeric::U64(_) if digits >= 0 => Ok(n),
+        Numeric::I64(i) => {
+            let unit = 10_i64.checked_pow(digits.unsigned_abs());
+            match unit {
+                Some(unit) => {
+                    let (quotient, remainder) = (i / unit, i % unit);
+                    let rounded = if remainder.abs() >= unit - remainder.abs() {
+                        quotient + i.signum()
+                    } else {
+                        quotient
+                    };
+                    rounded
+                        .checked_mul(unit)
+                        .map(Numeric::I64)
+                        .ok_or_else(|| out_of_range_err("ROUND"))
+                }
+                None => Ok(Numeric::I64(0)),
+            }
+        }
+        Numeric::U64(u) => {
+            let unit = 10_u64.checked_pow(digits.unsigned_abs());
+            match unit {
+                Some(unit) => {
+                    let (quotient, remainder) = (u / unit, u % unit);
+                    let rounded = if remainder >= unit - remainder {
+                        quotient + 1
+                    } else {
+                        quotient
+                    };
+                    rounded
+                        .checked_mul(unit)
+                        .map(Numeric::U64)
+                        .ok_or_else(|| out_of_range_err("ROUND"))
+                }
+                None => Ok(Numeric::U64(0)),
+            }
+        }
+        Numeric::F32(f) => {
+            let scale = 10_f64.powi(digits);
+            let rounded = ((f as f64) * scale).round() / scale;
+            if rounded.is_finite() {
+                Ok(Numeric::F32(rounded as f32))
+            } else {
+                // too many digits to scale; nothing to round in f32 precision
+                Ok(n)
+            }
+        }
+    })
+}
+
+/// # Failures
+///
+/// - `SpringError::Sql` when:
+///   - `target` is negative.
+pub(super) fn sqrt(target: SqlValue) -> Result<SqlValue> {
+    float_function(target, "SQRT", |x| {
+        if x < 0.0 {
+            Err(SpringError::Sql(anyhow!(
+                "cannot take square root of a negative number: {}",
+                x
+            )))
+        } else {
+            Ok(x.sqrt())
+        }
+    })
+}
+
+/// # Failures
+///
+/// - `SpringError::Sql` when:
+///   - Result is not a real number or out of range (e.g. `POWER(-1, 0.5)`).
+pub(super) fn power(base: SqlValue, exponent: SqlValue) -> Result<SqlValue> {
+    match (numeric_arg(base, "POWER")?, numeric_arg(exponent, "POWER")?) {
+        (Some(base), Some(exponent)) => float_value("POWER", base.to_f64().powf(exponent.to_f64())),
+        _ => Ok(SqlValue::Null),
+    }
+}
+
+/// # Failures
+///
+/// - `SpringError::Sql` when:
+///   - `target` is zero or negative.
+pub(super) fn ln(target: SqlValue) -> Result<SqlValue> {
+    float_function(target, "LN", |x| check_positive("LN", x).map(|x| x.ln()))
+}
+
+/// # Failures
+///
+/// - `SpringError::Sql` when:
+///   - `target` is zero or negative.
+pub(super) fn log10(target: SqlValue) -> Result<SqlValue> {
+    float_function(target, "LOG10", |x| {
+        check_positive("LOG10", x).map(|x| x.log10())
+    })
+}
+
+pub(super) fn exp(target: SqlValue) -> Result<SqlValue> {
+    float_function(target, "EXP", |x| Ok(x.exp()))
+}
+
+pub(super) fn sin(target: SqlValue) -> Result<SqlValue> {
+    float_function(target, "SIN", |x| Ok(x.sin()))
+}
+
+pub(super) fn cos(target: SqlValue) -> Result<SqlValue> {
+    float_function(target, "COS", |x| Ok(x.cos()))
+}
+
+pub(super) fn atan2(y: SqlValue, x: SqlValue) -> Result<SqlValue> {
+    match (numeric_arg(y, "ATAN2")?, numeric_arg(x, "ATAN2")?) {
+        (Some(y), Some(x)) => float_value("ATAN2", y.to_f64().atan2(x.to_f64())),
+        _ => Ok(SqlValue::Null),
+    }
+}
+
+pub(super) fn greatest(args: Vec<SqlValue>) -> Result<SqlValue> {
+    extremum(args, SqlCompareResult::GreaterThan)
+}
+
+pub(super) fn least(args: Vec<SqlValue>) -> Result<SqlValue> {
+    extremum(args, SqlCompareResult::LessThan)
+}
+
+/// # Failures
+///
+/// - `SpringError::Sql` when:
+///   - Arguments are not comparable with each other.
+fn extremum(args: Vec<SqlValue>, preferred: SqlCompareResult) -> Result<SqlValue> {
+    let mut extremum: Option<NnSqlValue> = None;
+    let mut common_numeric_type: Option<NumericComparableType> = None;
+
+    for arg in args {
+        if let SqlValue::NotNull(v) = arg {
+            if let SqlType::NumericComparable(n) = v.sql_type() {
+                common_numeric_type = Some(match (common_numeric_type, n) {
+                    (None, n) => n,
+                    (Some(NumericComparableType::F32Loose(f)), _)
+                    | (_, NumericComparableType::F32Loose(f)) => NumericComparableType::F32Loose(f),
+                    (
+                        Some(NumericComparableType::U64Loose(u)),
+                        NumericComparableType::U64Loose(_),
+                    ) => NumericComparableType::U64Loose(u),
+                    (Some(_), _) => NumericComparableType::I64Loose(I64LooseType::BigInt),
+                });
+            }
+
+            extremum = match extremum {
+                Some(current) if current.sql_compare(&v)? != preferred => Some(v),
+                Some(current) => Some(current),
+                None => Some(v),
+            };
+        }
+    }
+
+    match (extremum, common_numeric_type) {
+        (Some(v), Some(n)) => v
+            .try_cast(&SqlType::NumericComparable(n))
+            .map(SqlValue::NotNull),
+        (Some(v), None) => Ok(SqlValue::NotNull(v)),
+        (None, _) => Ok(SqlValue::Null),
+    }
+}
+
+fn numeric_arg(value: SqlValue, function_name: &str) -> Result<Option<Numeric>> {
+    match value {
+        SqlValue::Null => Ok(None),
+        SqlValue::NotNull(nn_sql_value) => match nn_sql_value.sql_type() {
+            SqlType::NumericComparable(NumericComparableType::I64Loose(_)) => {
+                nn_sql_value.unpack::<i64>().map(|i| Some(Numeric::I64(i)))
+            }
+            SqlType::NumericComparable(NumericComparableType::U64Loose(_)) => {
+                nn_sql_value.unpack::<u64>().map(|u| Some(Numeric::U64(u)))
+            }
+            SqlType::NumericComparable(NumericComparableType::F32Loose(_)) => {
+                nn_sql_value.unpack::<f32>().map(|f| Some(Numeric::F32(f)))
+            }
+            _ => Err(SpringError::Sql(anyhow!(
+                "{} takes numeric value but got {}",
+                function_name,
+                nn_sql_value
+            ))),
+        },
+    }
+}
+
+fn map_numeric<F>(value: SqlValue, function_name: &str, f: F) -> Result<SqlValue>
+where
+    F: FnOnce(Numeric) -> Result<Numeric>,
+{
+    match numeric_arg(value, function_name)? {
+        Some(n) => f(n).map(Numeric::into_sql_value),
+        None => Ok(SqlValue::Null),
+    }
+}
+
+fn float_function<F>(value: SqlValue, function_name: &str, f: F) -> Result<SqlValue>
+where
+    F: FnOnce(f64) -> Result<f64>,
+{
+    match numeric_arg(value, function_name)? {
+        Some(n) => float_value(function_name, f(n.to_f64())?),
+        None => Ok(SqlValue::Null),
+    }
+}
+
+/// # Failures
+///
+/// - `SpringError::Sql` when:
+///   - `v` is NaN or out of FLOAT range.
+fn float_value(function_name: &str, v: f64) -> Result<SqlValue> {
+    let f = v as f32;
+    if f.is_finite() {
+        Ok(Numeric::F32(f).into_sql_value())
+    } else {
+        Err(out_of_range_err(function_name))
+    }
+}
+
+fn check_positive(function_name: &str, x: f64) -> Result<f64> {
+    if x > 0.0 {
+        Ok(x)
+    } else {
+        Err(SpringError::Sql(anyhow!(
+            "{} takes positive number but got {}",
+            function_name,
+            x
+        )))
+    }
+}
+
+fn out_of_range_err(function_name: &str) -> SpringError {
+    SpringError::Sql(anyhow!("value out of range in {}", function_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn i(v: i64) -> SqlValue {
+        SqlValue::NotNull(NnSqlValue::BigInt(v))
+    }
+    fn u(v: u64) -> SqlValue {
+        SqlValue::NotNull(NnSqlValue::UnsignedBigInt(v))
+    }
+    fn f(v: f32) -> SqlValue {
+        SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(v)))
+    }
+
+    #[test]
+    fn test_round() -> Result<()> {
+        assert_eq!(round(f(1.25), Some(i(1)))?, f(1.3));
+        assert_eq!(round(f(-1.5), None)?, f(-2.0));
+        assert_eq!(round(f(1234.5), Some(i(-2)))?, f(1200.0));
+        assert_eq!(round(i(1250), Some(i(-2)))?, i(1300));
+        assert_eq!(round(i(-1249), Some(i(-2)))?, i(-1200));
+        assert_eq!(round(u(1250), Some(i(-2)))?, u(1300));
+        assert_eq!(round(i(42), Some(i(-30)))?, i(0));
+        assert!(round(i(i64::MAX), Some(i(-1))).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_domain_errors() {
+        assert!(sqrt(i(-1)).is_err());
+        assert!(ln(i(0)).is_err());
+        assert!(log10(f(-1.0)).is_err());
+        assert!(power(i(-1), f(0.5)).is_err());
+        assert!(exp(i(1000)).is_err());
+        assert!(abs(i(i64::MIN)).is_err());
+    }
+
+    #[test]
+    fn test_greatest_least() -> Result<()> {
+        assert_eq!(greatest(vec![i(1), SqlValue::Null, f(2.5)])?, f(2.5));
+        assert_eq!(least(vec![i(1), SqlValue::Null, f(2.5)])?, f(1.0));
+        assert_eq!(greatest(vec![u(3), i(-1)])?, i(3));
+        assert!(matches!(
+            least(vec![SqlValue::Null, SqlValue::Null])?,
+            SqlValue::Null
+        ));
+        assert!(greatest(vec![i(1), SqlValue::NotNull(NnSqlValue::Boolean(true))]).is_err());
+        Ok(())
+    }
+}
@@ -5,6 +5,9 @@
 pub enum UnaryOperator {
     /// -
     Minus,
+
+    /// NOT
+    Not,
 }
 
 /// binary operator for an expression
@@ -13,12 +16,48 @@ pub enum BinaryOperator {
     /// =
     Equal,
 
+    /// <> or !=
+    NotEqual,
+
+    /// <
+    LessThan,
+
+    /// <=
+    LessThanOrEqual,
+
+    /// >
+    GreaterThan,
+
+    /// >=
+    GreaterThanOrEqual,
+
     /// +
     Add,
 
+    /// -
+    Sub,
+
     /// *
     Mul,
 
+    /// /
+    Div,
+
+    /// %
+    Mod,
+
     /// AND
     And,
+
+    /// OR
+    Or,
+
+    /// LIKE
+    Like,
+
+    /// NOT LIKE
+    NotLike,
+
+    /// ||
+    Concat,
 }
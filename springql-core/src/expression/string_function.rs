@@ -0,0 +1,296 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+//! Evaluation of string functions.
+//!
+//! All functions return NULL if any of their arguments is NULL.
+
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    expression::TrimSide,
+    pipeline::SqlType,
+    stream_engine::{NnSqlValue, SqlValue},
+};
+
+pub(super) fn like(target: SqlValue, pattern: SqlValue) -> Result<SqlValue> {
+    match (text_arg(target, "LIKE")?, text_arg(pattern, "LIKE")?) {
+        (Some(target), Some(pattern)) => Ok(SqlValue::NotNull(NnSqlValue::Boolean(like_match(
+            &target, &pattern,
+        )))),
+        _ => Ok(SqlValue::Null),
+    }
+}
+
+pub(super) fn upper(target: SqlValue) -> Result<SqlValue> {
+    Ok(text_arg(target, "UPPER")?
+        .map(|target| text_value(target.to_uppercase()))
+        .unwrap_or(SqlValue::Null))
+}
+
+pub(super) fn lower(target: SqlValue) -> Result<SqlValue> {
+    Ok(text_arg(target, "LOWER")?
+        .map(|target| text_value(target.to_lowercase()))
+        .unwrap_or(SqlValue::Null))
+}
+
+pub(super) fn length(target: SqlValue) -> Result<SqlValue> {
+    Ok(text_arg(target, "LENGTH")?
+        .map(|target| SqlValue::NotNull(NnSqlValue::BigInt(target.chars().count() as i64)))
+        .unwrap_or(SqlValue::Null))
+}
+
+/// # Failures
+///
+/// - `SpringError::Sql` when:
+///   - `length` is negative.
+pub(super) fn substring(
+    target: SqlValue,
+    start: SqlValue,
+    length: Option<SqlValue>,
+) -> Result<SqlValue> {
+    let target = text_arg(target, "SUBSTRING")?;
+    let start = integer_arg(start, "SUBSTRING")?;
+    let length = match length {
+        Some(length) => match integer_arg(length, "SUBSTRING")? {
+            Some(length) if length < 0 => {
+                return Err(SpringError::Sql(anyhow!(
+                    "negative substring length not allowed: {}",
+                    length
+                )))
+            }
+            Some(length) => Some(Some(length)),
+            None => None,
+        },
+        None => Some(None),
+    };
+
+    match (target, start, length) {
+        (Some(target), Some(start), Some(length)) => {
+            let chars = target.chars().collect::<Vec<_>>();
+            // 1-origin, half-open range [begin, end) clipped into the target
+            let end = length.map_or(i64::MAX, |length| start.saturating_add(length));
+            let begin = start.max(1);
+            let end = end.min(chars.len() as i64 + 1);
+            let s = if begin < end {
+                chars[(begin - 1) as usize..(end - 1) as usize]
+                    .iter()
+                    .collect()
+            } else {
+                String::new()
+            };
+            Ok(text_value(s))
+        }
+        _ => Ok(SqlValue::Null),
+    }
+}
+
+/// # Failures
+///
+/// - `SpringError::Sql` when:
+///   - An argument cannot be casted into TEXT.
+pub(super) fn concat(args: Vec<SqlValue>) -> Result<SqlValue> {
+    let mut s = String::new();
+    for arg in args {
+        match arg {
+            SqlValue::Null => return Ok(SqlValue::Null),
+            SqlValue::NotNull(nn_sql_value) => s.push_str(
+                &nn_sql_value
+                    .try_cast(&SqlType::text())?
+                    .unpack::<String>()?,
+            ),
+        }
+    }
+    Ok(text_value(s))
+}
+
+pub(super) fn trim(
+    target: SqlValue,
+    chars: Option<SqlValue>,
+    trim_side: TrimSide,
+) -> Result<SqlValue> {
+    let target = text_arg(target, "TRIM")?;
+    let chars = match chars {
+        Some(chars) => text_arg(chars, "TRIM")?,
+        None => Some(" ".to_string()),
+    };
+
+    match (target, chars) {
+        (Some(target), Some(chars)) => {
+            let is_trimmed = |c: char| chars.contains(c);
+            let s = match trim_side {
+                TrimSide::Leading => target.trim_start_matches(is_trimmed),
+                TrimSide::Trailing => target.trim_end_matches(is_trimmed),
+                TrimSide::Both => target.trim_matches(is_trimmed),
+            };
+            Ok(text_value(s.to_string()))
+        }
+        _ => Ok(SqlValue::Null),
+    }
+}
+
+pub(super) fn position(substring: SqlValue, target: SqlValue) -> Result<SqlValue> {
+    match (
+        text_arg(substring, "POSITION")?,
+        text_arg(target, "POSITION")?,
+    ) {
+        (Some(substring), Some(target)) => {
+            let pos = target
+                .find(&substring)
+                .map_or(0, |byte_idx| target[..byte_idx].chars().count() as i64 + 1);
+            Ok(SqlValue::NotNull(NnSqlValue::BigInt(pos)))
+        }
+        _ => Ok(SqlValue::Null),
+    }
+}
+
+pub(super) fn replace(target: SqlValue, from: SqlValue, to: SqlValue) -> Result<SqlValue> {
+    match (
+        text_arg(target, "REPLACE")?,
+        text_arg(from, "REPLACE")?,
+        text_arg(to, "REPLACE")?,
+    ) {
+        (Some(target), Some(from), Some(to)) => {
+            if from.is_empty() {
+                Ok(text_value(target))
+            } else {
+                Ok(text_value(target.replace(&from, &to)))
+            }
+        }
+        _ => Ok(SqlValue::Null),
+    }
+}
+
+fn text_value(s: String) -> SqlValue {
+    SqlValue::NotNull(NnSqlValue::Text(s))
+}
+
+fn text_arg(value: SqlValue, function_name: &str) -> Result<Option<String>> {
+    match value {
+        SqlValue::Null => Ok(None),
+        SqlValue::NotNull(NnSqlValue::Text(s)) => Ok(Some(s)),
+        SqlValue::NotNull(nn_sql_value) => Err(SpringError::Sql(anyhow!(
+            "{} takes TEXT but got {}",
+            function_name,
+            nn_sql_value
+        ))),
+    }
+}
+
+fn integer_arg(value: SqlValue, function_name: &str) -> Result<Option<i64>> {
+    match value {
+        SqlValue::Null => Ok(None),
+        SqlValue::NotNull(nn_sql_value) => nn_sql_value.unpack::<i64>().map(Some).map_err(|_| {
+            SpringError::Sql(anyhow!(
+                "{} takes integer but got {}",
+                function_name,
+                nn_sql_value
+            ))
+        }),
+    }
+}
+
+#[derive(Eq, PartialEq, Debug)]
+enum LikeToken {
+    /// `%`
+    AnySequence,
+    /// `_`
+    AnyChar,
+    Char(char),
+}
+
+fn like_tokens(pattern: &str) -> Vec<LikeToken> {
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '%' => LikeToken::AnySequence,
+            '_' => LikeToken::AnyChar,
+            '\\' => LikeToken::Char(chars.next().unwrap_or('\\')),
+            c => LikeToken::Char(c),
+        };
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// Wildcard matching which backtracks only to the last `%`, running in O(|target| * |pattern|) at worst.
+fn like_match(target: &str, pattern: &str) -> bool {
+    let tokens = like_tokens(pattern);
+    let chars = target.chars().collect::<Vec<_>>();
+
+    let (mut i_token, mut i_char) = (0, 0);
+    // (token index next to the last `%`, char index the `%` has matched up to)
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while i_char < chars.len() {
+        match tokens.get(i_token) {
+            Some(LikeToken::AnySequence) => {
+                i_token += 1;
+                backtrack = Some((i_token, i_char));
+            }
+            Some(LikeToken::AnyChar) => {
+                i_token += 1;
+                i_char += 1;
+            }
+            Some(LikeToken::Char(c)) if *c == chars[i_char] => {
+                i_token += 1;
+                i_char += 1;
+            }
+            _ => match backtrack {
+                Some((i_token_after_any, i_char_matched)) => {
+                    i_token = i_token_after_any;
+                    i_char = i_char_matched + 1;
+                    backtrack = Some((i_token_after_any, i_char));
+                }
+                None => return false,
+            },
+        }
+    }
+    tokens[i_token..]
+        .iter()
+        .all(|token| *token == LikeToken::AnySequence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_like_match() {
+        assert!(like_match("sensor/room1/temp", "sensor/%"));
+        assert!(like_match("sensor/room1/temp", "%/temp"));
+        assert!(like_match("sensor/room1/temp", "sensor/room_/%"));
+        assert!(like_match("", "%"));
+        assert!(like_match("abcabd", "%abd"));
+        assert!(like_match("100%", "100\\%"));
+
+        assert!(!like_match("sensor/room1/temp", "sensor/"));
+        assert!(!like_match("sensor/room10/temp", "sensor/room_/%"));
+        assert!(!like_match("1000", "100\\%"));
+        assert!(!like_match("", "_"));
+    }
+
+    #[test]
+    fn test_substring() -> Result<()> {
+        let s = || text_value("abcde".to_string());
+        let i = |i: i64| SqlValue::NotNull(NnSqlValue::BigInt(i));
+
+        assert_eq!(
+            substring(s(), i(2), Some(i(3)))?,
+            text_value("bcd".to_string())
+        );
+        assert_eq!(substring(s(), i(2), None)?, text_value("bcde".to_string()));
+        assert_eq!(
+            substring(s(), i(0), Some(i(2)))?,
+            text_value("a".to_string())
+        );
+        assert_eq!(substring(s(), i(10), None)?, text_value("".to_string()));
+        assert!(matches!(
+            substring(s(), i(2), Some(SqlValue::Null))?,
+            SqlValue::Null
+        ));
+        assert!(substring(s(), i(2), Some(i(-1))).is_err());
+        Ok(())
+    }
+}
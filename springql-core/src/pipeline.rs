@@ -12,6 +12,7 @@ mod relation;
 mod sink_writer_model;
 mod source_reader_model;
 mod stream_model;
+mod table_model;
 
 #[cfg(test)]
 pub mod test_support;
@@ -23,13 +24,14 @@ pub use name::{
 };
 pub use option::{
     CANOptions, Http1ClientOptions, HttpMethod, InMemoryQueueOptions, NetClientOptions,
-    NetProtocol, NetServerOptions, Options, OptionsBuilder,
+    NetProtocol, NetServerOptions, Options, OptionsBuilder, TableFileFormat, TableFileOptions,
 };
 pub use pipeline_graph::{Edge, PipelineGraph};
 pub use pipeline_version::PipelineVersion;
 pub use pump_model::{
-    AggregateFunctionParameter, AggregateParameter, GroupByLabels, JoinParameter, JoinType,
-    PumpInputType, PumpModel, WindowOperationParameter, WindowParameter,
+    AggregateExprParameter, AggregateFunctionParameter, AggregateParameter, EmitPolicy,
+    GroupByLabels, JoinParameter, JoinType, PumpInputType, PumpModel, WindowOperationParameter,
+    WindowParameter,
 };
 pub use relation::{
     ColumnConstraint, ColumnDataType, ColumnDefinition, F32LooseType, I64LooseType,
@@ -38,8 +40,12 @@ pub use relation::{
 pub use sink_writer_model::{SinkWriterModel, SinkWriterType};
 pub use source_reader_model::{SourceReaderModel, SourceReaderType};
 pub use stream_model::{StreamModel, StreamShape};
+pub use table_model::TableModel;
 
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use anyhow::anyhow;
 
@@ -50,6 +56,7 @@ pub struct Pipeline {
     version: PipelineVersion,
     object_names: HashSet<String>,
     graph: PipelineGraph,
+    tables: HashMap<StreamName, Arc<TableModel>>,
 }
 
 impl Pipeline {
@@ -58,6 +65,7 @@ impl Pipeline {
             version,
             object_names: HashSet::default(),
             graph: PipelineGraph::default(),
+            tables: HashMap::default(),
         }
     }
 
@@ -77,6 +85,30 @@ impl Pipeline {
         self.graph.get_stream(stream)
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Table is not registered in pipeline
+    pub fn get_table(&self, table: &StreamName) -> Result<Arc<TableModel>> {
+        self.tables
+            .get(table)
+            .cloned()
+            .ok_or_else(|| SpringError::Sql(anyhow!(r#"table "{}" is not found"#, table)))
+    }
+
+    /// Shape of a stream or a table, from which columns are referenced as `name.column`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Neither stream nor table is registered in pipeline
+    pub fn get_stream_or_table(&self, name: &StreamName) -> Result<Arc<StreamModel>> {
+        match self.tables.get(name) {
+            Some(table) => Ok(table.stream_model()),
+            None => self.get_stream(name),
+        }
+    }
+
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
@@ -99,6 +131,17 @@ impl Pipeline {
         self.graph.add_stream(stream)
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Name of table is already used in the same pipeline
+    pub fn add_table(&mut self, table: Arc<TableModel>) -> Result<()> {
+        self.update_version();
+        self.register_name(table.name().as_ref())?;
+        let _ = self.tables.insert(table.name().clone(), table);
+        Ok(())
+    }
+
     /// # Failure
     ///
     /// TODO
@@ -114,6 +157,23 @@ impl Pipeline {
         self.graph.add_sink_writer(sink_writer)
     }
 
+    /// Key columns of `table` which pumps look up (without duplicates).
+    pub fn table_key_columns(&self, table: &StreamName) -> Vec<ColumnName> {
+        let mut key_columns = Vec::new();
+        for edge in self.graph.as_petgraph().edge_weights() {
+            if let Edge::Pump { pump_model, .. } = edge {
+                for table_lookup in &pump_model.query_plan().lower_ops.table_lookups {
+                    if &table_lookup.table == table
+                        && !key_columns.contains(&table_lookup.key_column)
+                    {
+                        key_columns.push(table_lookup.key_column.clone());
+                    }
+                }
+            }
+        }
+        key_columns
+    }
+
     pub fn all_sources(&self) -> Vec<&SourceReaderModel> {
         self.graph.all_sources()
     }
@@ -7,6 +7,7 @@ mod net_client_options;
 mod net_protocol;
 mod net_server_options;
 mod options_builder;
+mod table_file_options;
 
 pub use can_options::CANOptions;
 pub use http1_client_options::{Http1ClientOptions, HttpMethod};
@@ -15,6 +16,7 @@ pub use net_client_options::NetClientOptions;
 pub use net_protocol::NetProtocol;
 pub use net_server_options::NetServerOptions;
 pub use options_builder::OptionsBuilder;
+pub use table_file_options::{TableFileFormat, TableFileOptions};
 
 use std::collections::HashMap;
 
@@ -46,6 +48,10 @@ impl Options {
             })
     }
 
+    pub fn contains(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
     pub fn as_key_values(&self) -> Vec<(&str, &str)> {
         self.0
             .iter()
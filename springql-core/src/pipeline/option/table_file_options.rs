@@ -0,0 +1,47 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+
+use crate::{api::error::Result, pipeline::option::Options};
+
+/// File to load table rows from.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TableFileOptions {
+    pub path: PathBuf,
+    pub format: TableFileFormat,
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum TableFileFormat {
+    /// Header line with column names, followed by records. Empty fields are NULL.
+    Csv,
+    /// JSON array of objects, or an object per line.
+    Json,
+}
+
+impl TableFileOptions {
+    /// `None` if `options` does not have `PATH`.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::InvalidOption` when:
+    ///   - `FORMAT` is not found or not one of `CSV` and `JSON`.
+    pub fn from_options(options: &Options) -> Result<Option<Self>> {
+        if !options.contains("PATH") {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            path: options.get("PATH", |path| Ok(PathBuf::from(path)))?,
+            format: options.get("FORMAT", |format| {
+                match format.to_ascii_uppercase().as_str() {
+                    "CSV" => Ok(TableFileFormat::Csv),
+                    "JSON" => Ok(TableFileFormat::Json),
+                    _ => Err(anyhow!("FORMAT must be CSV or JSON")),
+                }
+            })?,
+        }))
+    }
+}
@@ -85,7 +85,7 @@ impl PipelineGraph {
         self.graph
             .edge_references()
             .filter_map(|edge| match edge.weight() {
-                Edge::Pump { .. } | Edge::Sink(_) => None,
+                Edge::Pump { .. } | Edge::LateRows(_) | Edge::Sink(_) => None,
                 Edge::Source(s) => Some(s),
             })
             .collect()
@@ -94,7 +94,7 @@ impl PipelineGraph {
         self.graph
             .edge_references()
             .filter_map(|edge| match edge.weight() {
-                Edge::Pump { .. } | Edge::Source(_) => None,
+                Edge::Pump { .. } | Edge::LateRows(_) | Edge::Source(_) => None,
                 Edge::Sink(s) => Some(s),
             })
             .collect()
@@ -128,6 +128,26 @@ impl PipelineGraph {
             );
         }
 
+        if let Some(late_stream_name) = pump.late_rows_into() {
+            let late_stream_node = self.stream_nodes.get(late_stream_name).ok_or_else(|| {
+                SpringError::Sql(anyhow!(
+                    r#"late rows stream "{}" does not exist in pipeline"#,
+                    late_stream_name
+                ))
+            })?;
+            let first_upstream_node = pump
+                .upstreams()
+                .first()
+                .and_then(|upstream_name| self.stream_nodes.get(*upstream_name))
+                .expect("upstreams are checked above");
+
+            let _ = self.graph.add_edge(
+                *first_upstream_node,
+                *late_stream_node,
+                Edge::LateRows(pump.clone()),
+            );
+        }
+
         Ok(())
     }
 
@@ -14,6 +14,9 @@ pub enum Edge {
         pump_model: Arc<PumpModel>,
         upstream: StreamName,
     },
+    /// Routes late rows of a pump to another stream (`LATE ROWS INTO`).
+    /// The edge goes from the pump's first upstream to the late stream so that tasks reading the late stream get their input queues from the pump.
+    LateRows(Arc<PumpModel>),
     Source(SourceReaderModel),
     Sink(SinkWriterModel),
 }
@@ -1,13 +1,15 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+mod emit_policy;
 mod pump_input_type;
 mod window_operation_parameter;
 mod window_parameter;
 
+pub use emit_policy::EmitPolicy;
 pub use pump_input_type::PumpInputType;
 pub use window_operation_parameter::{
-    AggregateFunctionParameter, AggregateParameter, GroupByLabels, JoinParameter, JoinType,
-    WindowOperationParameter,
+    AggregateExprParameter, AggregateFunctionParameter, AggregateParameter, GroupByLabels,
+    JoinParameter, JoinType, WindowOperationParameter,
 };
 pub use window_parameter::WindowParameter;
 
@@ -21,6 +23,8 @@ pub struct PumpModel {
     name: PumpName,
     query_plan: QueryPlan,
     insert_plan: InsertPlan,
+    /// `LATE ROWS INTO <stream>`
+    late_rows_into: Option<StreamName>,
 }
 
 impl PumpModel {
@@ -48,4 +52,9 @@ impl PumpModel {
     pub fn downstream(&self) -> &StreamName {
         self.insert_plan.stream()
     }
+
+    /// Stream to which rows dropped by windows as too late are routed.
+    pub fn late_rows_into(&self) -> Option<&StreamName> {
+        self.late_rows_into.as_ref()
+    }
 }
@@ -0,0 +1,22 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::stream_engine::time::SpringEventDuration;
+
+/// When a window emits results of its panes.
+///
+/// Results of a pane are always emitted when it closes.
+/// Other policies emit provisional results of open panes in addition; each emission of a group supersedes the previous one.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Default)]
+pub enum EmitPolicy {
+    /// `EMIT FINAL`: only when a pane closes (default).
+    #[default]
+    Final,
+
+    /// `EMIT EVERY <duration>`: panes updated since the last emission, every `period` in event-time (rowtime).
+    ///
+    /// Late rows (with rowtime before the last emission) are emitted immediately as updates of the panes they are accepted into.
+    Every(SpringEventDuration),
+
+    /// `EMIT ON EACH ROW`: panes a row is dispatched to, on each row.
+    OnEachRow,
+}
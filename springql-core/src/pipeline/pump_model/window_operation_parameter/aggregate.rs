@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use ordered_float::OrderedFloat;
+
 use crate::expr_resolver::{AggrExprLabel, ValueExprLabel};
 
 /// [GROUP BY c1, c2, c3...]
@@ -14,23 +16,55 @@ impl GroupByLabels {
     }
 }
 
-/// TODO `support complex expression with aggregations`
-///
 /// ```sql
-/// SELECT group_by, aggr_expr.func(aggr_expr.aggregated)
+/// SELECT group_by, aggr_expr1.func(aggr_expr1.aggregated), aggr_expr2.func(aggr_expr2.aggregated) - 1, ...
 ///   FROM s
 ///   [GROUP BY group_by]
+///   [HAVING having]
 ///   SLIDING WINDOW ...;
 /// ```
 #[derive(Clone, PartialEq, Eq, Debug, new)]
 pub struct AggregateParameter {
-    // TODO multiple aggr_expr
+    /// All the aggregate expressions in the select list, including ones inside value expressions.
+    pub aggr_exprs: Vec<AggregateExprParameter>,
+    pub group_by: GroupByLabels,
+    /// None when HAVING clause is not supplied.
+    pub having: Option<ValueExprLabel>,
+}
+
+/// An aggregate function and the label of its expression.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, new)]
+pub struct AggregateExprParameter {
     pub aggr_func: AggregateFunctionParameter,
     pub aggr_expr: AggrExprLabel,
-    pub group_by: GroupByLabels,
 }
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum AggregateFunctionParameter {
     Avg,
+    Sum,
+    /// `COUNT(*)` is represented as `COUNT(1)`.
+    Count,
+    /// `COUNT(DISTINCT x)`, counted exactly.
+    CountDistinct,
+    /// `APPROX_COUNT_DISTINCT(x)`, estimated by HyperLogLog.
+    ApproxCountDistinct,
+    Min,
+    Max,
+    /// Value of the row with the earliest `rowtime()`.
+    FirstValue,
+    /// Value of the row with the latest `rowtime()`.
+    LastValue,
+    /// `ARG_MIN(x, by)`: `x` of the row with the minimum `by`.
+    ArgMin,
+    /// `ARG_MAX(x, by)`: `x` of the row with the maximum `by`.
+    ArgMax,
+    StddevPop,
+    StddevSamp,
+    VarPop,
+    VarSamp,
+    /// `PERCENTILE_APPROX(x, percentile)`, where `percentile` is in [0, 1].
+    PercentileApprox {
+        percentile: OrderedFloat<f64>,
+    },
 }
@@ -20,9 +20,30 @@ pub struct JoinParameter {
     pub right_colrefs: Vec<ColumnReference>,
 
     pub on_expr: ValueExprLabel,
+
+    /// `(left key, right key)` of each `left_expr = right_expr` conjunct in `on_expr`.
+    ///
+    /// Right tuples are probed by the hash of these keys before `on_expr` is evaluated.
+    /// Every pair of left and right tuples is evaluated (nested loop join) if empty.
+    pub equi_keys: Vec<(ValueExprLabel, ValueExprLabel)>,
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum JoinType {
+    Inner,
     LeftOuter,
+    RightOuter,
+    FullOuter,
+}
+
+impl JoinType {
+    /// Whether left tuples without matching right tuples are emitted (with NULL-padded right fields).
+    pub fn keeps_unmatched_left(&self) -> bool {
+        matches!(self, JoinType::LeftOuter | JoinType::FullOuter)
+    }
+
+    /// Whether right tuples without matching left tuples are emitted (with NULL-padded left fields).
+    pub fn keeps_unmatched_right(&self) -> bool {
+        matches!(self, JoinType::RightOuter | JoinType::FullOuter)
+    }
 }
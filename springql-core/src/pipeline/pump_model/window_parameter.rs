@@ -1,8 +1,9 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::stream_engine::time::SpringEventDuration;
+use crate::stream_engine::time::{SpringDuration, SpringEventDuration};
 
 /// Window parameters
+#[allow(clippy::enum_variant_names)]
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum WindowParameter {
     /// Time-based sliding window
@@ -39,20 +40,72 @@ pub enum WindowParameter {
         length: SpringEventDuration,
         allowed_delay: SpringEventDuration,
     },
+
+    /// Session window
+    ///
+    /// A pane is opened per session (per group for aggregation) and extended while rows keep arriving.
+    /// It closes when watermark passes `gap` after the last row.
+    /// Panes of a session are merged when a (late) row bridges them.
+    ///
+    /// ```text
+    /// gap = 5sec, allowed_delay = 0;
+    ///
+    /// rows  * **         *  *
+    /// pane1 |       |
+    /// pane2              |       |
+    ///
+    /// -----------------------------------> t
+    ///      :00  :05  :10  :15  :20
+    /// ```
+    SessionWindow {
+        gap: SpringEventDuration,
+        allowed_delay: SpringEventDuration,
+    },
+
+    /// Count-based sliding window
+    ///
+    /// Panes are opened every `period` rows and closed when they get `length` rows, in the arrival order of rows.
+    /// Rowtime and watermark are not used.
+    ///
+    /// ```text
+    /// length = 3, period = 2;
+    ///
+    /// rows   1 2 3 4 5 6 7
+    /// pane1 |     |
+    /// pane2     |     |
+    /// pane3         |     |
+    /// ```
+    RowsSlidingWindow { length: u64, period: u64 },
+
+    /// Count-based fixed window
+    ///
+    /// ```text
+    /// length = 3;
+    ///
+    /// rows   1 2 3 4 5 6 7
+    /// pane1 |     |
+    /// pane2       |     |
+    /// pane3             |     |
+    /// ```
+    RowsFixedWindow { length: u64 },
 }
 
 impl WindowParameter {
-    pub fn length(&self) -> SpringEventDuration {
+    /// `(length, period)` of time-based windows. `None` for session windows and count-based windows.
+    pub fn length_and_period(&self) -> Option<(SpringEventDuration, SpringEventDuration)> {
         match self {
-            WindowParameter::TimedSlidingWindow { length, .. } => *length,
-            WindowParameter::TimedFixedWindow { length, .. } => *length,
+            WindowParameter::TimedSlidingWindow { length, period, .. } => Some((*length, *period)),
+            WindowParameter::TimedFixedWindow { length, .. } => Some((*length, *length)),
+            _ => None,
         }
     }
 
-    pub fn period(&self) -> SpringEventDuration {
+    /// `(length, period)` in rows of count-based windows. `None` for other windows.
+    pub fn rows_length_and_period(&self) -> Option<(u64, u64)> {
         match self {
-            WindowParameter::TimedSlidingWindow { period, .. } => *period,
-            WindowParameter::TimedFixedWindow { length, .. } => *length,
+            WindowParameter::RowsSlidingWindow { length, period } => Some((*length, *period)),
+            WindowParameter::RowsFixedWindow { length } => Some((*length, *length)),
+            _ => None,
         }
     }
 
@@ -60,6 +113,11 @@ impl WindowParameter {
         match self {
             WindowParameter::TimedSlidingWindow { allowed_delay, .. } => *allowed_delay,
             WindowParameter::TimedFixedWindow { allowed_delay, .. } => *allowed_delay,
+            WindowParameter::SessionWindow { allowed_delay, .. } => *allowed_delay,
+            // watermark is not used
+            WindowParameter::RowsSlidingWindow { .. } | WindowParameter::RowsFixedWindow { .. } => {
+                SpringEventDuration::from_secs(0)
+            }
         }
     }
 }
@@ -1,5 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::fmt::Display;
+
 /// SQL type.
 ///
 /// SQL types are hierarchically categorized as follows:
@@ -84,6 +86,36 @@ impl SqlType {
     pub fn duration() -> SqlType {
         SqlType::DurationComparable
     }
+
+    /// Whether values of `self` and `other` are **comparable** (have the same top-level variant).
+    pub fn is_comparable_with(&self, other: &SqlType) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+impl Display for SqlType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            SqlType::NumericComparable(n) => match n {
+                NumericComparableType::I64Loose(i) => match i {
+                    I64LooseType::SmallInt => "SMALLINT",
+                    I64LooseType::Integer => "INTEGER",
+                    I64LooseType::BigInt => "BIGINT",
+                },
+                NumericComparableType::U64Loose(u) => match u {
+                    U64LooseType::UnsignedInteger => "UNSIGNED INTEGER",
+                    U64LooseType::UnsignedBigInt => "UNSIGNED BIGINT",
+                },
+                NumericComparableType::F32Loose(F32LooseType::Float) => "FLOAT",
+            },
+            SqlType::StringComparableLoose(StringComparableLoseType::Text) => "TEXT",
+            SqlType::BinaryComparable => "BLOB",
+            SqlType::BooleanComparable => "BOOLEAN",
+            SqlType::TimestampComparable => "TIMESTAMP",
+            SqlType::DurationComparable => "DURATION",
+        };
+        write!(f, "{}", s)
+    }
 }
 
 /// Numeric types (comparable).
@@ -0,0 +1,34 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::sync::Arc;
+
+use crate::pipeline::{field::ColumnReference, name::StreamName, option::Options, StreamModel};
+
+/// Table of reference rows, which streams look up by key (`JOIN table ON table.key = ...`).
+///
+/// A table shares the name space and the shape with streams so that its columns are referenced as `table.column`.
+/// Rows are loaded from a file (`PATH` and `FORMAT` options) on creation, and replaced by `SpringPipeline::replace_table()`.
+#[derive(Clone, PartialEq, Eq, Debug, new)]
+pub struct TableModel {
+    stream_model: Arc<StreamModel>,
+    options: Options,
+}
+
+impl TableModel {
+    pub fn name(&self) -> &StreamName {
+        self.stream_model.name()
+    }
+
+    /// Shape of rows in the table.
+    pub fn stream_model(&self) -> Arc<StreamModel> {
+        self.stream_model.clone()
+    }
+
+    pub fn column_references(&self) -> Vec<ColumnReference> {
+        self.stream_model.column_references()
+    }
+
+    pub fn options(&self) -> &Options {
+        &self.options
+    }
+}
@@ -40,6 +40,33 @@ impl ValueExpr {
         Self::BinaryExpr(BinaryExpr::factory_eq(left, right))
     }
 
+    pub fn factory_ne(left: ValueExpr, right: ValueExpr) -> Self {
+        Self::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+            ComparisonFunction::NotEqualVariant {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        ))
+    }
+
+    pub fn factory_lt(left: ValueExpr, right: ValueExpr) -> Self {
+        Self::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+            ComparisonFunction::LessThanVariant {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        ))
+    }
+
+    pub fn factory_ge(left: ValueExpr, right: ValueExpr) -> Self {
+        Self::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+            ComparisonFunction::GreaterThanOrEqualVariant {
+                left: Box::new(left),
+                right: Box::new(right),
+            },
+        ))
+    }
+
     pub fn factory_add(left: ValueExpr, right: ValueExpr) -> Self {
         Self::BinaryExpr(BinaryExpr::factory_add(left, right))
     }
@@ -9,9 +9,14 @@ pub use sql_parser::{
     SubFromItemSyntax,
 };
 
+use anyhow::anyhow;
+
 use crate::{
-    api::error::Result,
-    pipeline::{Pipeline, PumpModel, SinkWriterModel, SourceReaderModel, StreamModel},
+    api::error::{Result, SpringError},
+    pipeline::{
+        Pipeline, PumpInputType, PumpModel, SinkWriterModel, SourceReaderModel, StreamModel,
+        TableFileOptions, TableModel,
+    },
     sql_processor::query_planner::QueryPlanner,
     stream_engine::command::{AlterPipelineCommand, Command, QueryPlan},
 };
@@ -40,6 +45,9 @@ impl SqlProcessor {
             ParseSuccess::CreateSinkWriter(sink_writer_model) => {
                 self.compile_create_sink_writer(sink_writer_model, pipeline)?
             }
+            ParseSuccess::CreateTable(table_model) => {
+                self.compile_create_table(table_model, pipeline)?
+            }
             ParseSuccess::CreatePump(create_pump) => {
                 self.compile_create_pump(*create_pump, pipeline)?
             }
@@ -102,14 +110,147 @@ impl SqlProcessor {
         ))
     }
 
+    fn compile_create_table(
+        &self,
+        table_model: TableModel,
+        _pipeline: &Pipeline,
+    ) -> Result<Command> {
+        TableFileOptions::from_options(table_model.options())?;
+        Ok(Command::AlterPipeline(AlterPipelineCommand::CreateTable(
+            table_model,
+        )))
+    }
+
     fn compile_create_pump(&self, create_pump: CreatePump, pipeline: &Pipeline) -> Result<Command> {
         let query_plan = self.compile_select_stream(create_pump.select_stream_syntax, pipeline)?;
-        let pump = PumpModel::new(create_pump.pump_name, query_plan, create_pump.insert_plan);
+        let pump = PumpModel::new(
+            create_pump.pump_name,
+            query_plan,
+            create_pump.insert_plan,
+            create_pump.late_rows_into,
+        );
+        Self::check_insert_columns(&pump, pipeline)?;
+        Self::check_late_rows_into(&pump, pipeline)?;
         Ok(Command::AlterPipeline(AlterPipelineCommand::CreatePump(
             Box::new(pump),
         )))
     }
 
+    /// Each field in select list (after `*` is expanded) is inserted into the column at the same position in INSERT column list.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the downstream is not found in pipeline.
+    ///   - a column in INSERT column list is not in the downstream or is duplicated.
+    ///   - the number of columns in INSERT column list differs from the number of fields in select list.
+    fn check_insert_columns(pump: &PumpModel, pipeline: &Pipeline) -> Result<()> {
+        let downstream = pipeline.get_stream(pump.downstream())?;
+        let downstream_column_names = downstream.shape().column_names();
+
+        let column_order = pump.insert_plan().column_order();
+        for (i, column_name) in column_order.iter().enumerate() {
+            if !downstream_column_names.contains(column_name) {
+                return Err(SpringError::Sql(anyhow!(
+                    r#"column "{}" is not in stream "{}""#,
+                    column_name,
+                    pump.downstream()
+                )));
+            }
+            if column_order[..i].contains(column_name) {
+                return Err(SpringError::Sql(anyhow!(
+                    r#"column "{}" is inserted twice"#,
+                    column_name
+                )));
+            }
+        }
+
+        let n_fields = pump.query_plan().upper_ops.projection.expr_labels.len();
+        if column_order.len() != n_fields {
+            return Err(SpringError::Sql(anyhow!(
+                r#"INSERT INTO "{}" has {} columns but SELECT has {} fields"#,
+                pump.downstream(),
+                column_order.len(),
+                n_fields
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Rows routed by `LATE ROWS INTO` are made of the columns of an upstream stream,
+    /// so the late rows stream must be able to accept rows from every upstream.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the pump does not have a window.
+    ///   - the late rows stream is not found in pipeline.
+    ///   - the late rows stream is an upstream or the downstream of the pump.
+    ///   - a column of the late rows stream has a different type from the same-named column of an upstream.
+    ///   - a NOT NULL column of the late rows stream is missing in an upstream.
+    ///   - a NOT NULL column of the late rows stream is nullable in an upstream.
+    fn check_late_rows_into(pump: &PumpModel, pipeline: &Pipeline) -> Result<()> {
+        if let Some(late_stream_name) = pump.late_rows_into() {
+            if pump.input_type() != PumpInputType::Window {
+                return Err(SpringError::Sql(anyhow!(
+                    "LATE ROWS INTO requires a window clause"
+                )));
+            }
+
+            let late_stream = pipeline.get_stream(late_stream_name)?;
+            if pump.downstream() == late_stream_name || pump.upstreams().contains(&late_stream_name)
+            {
+                return Err(SpringError::Sql(anyhow!(
+                    r#"late rows stream "{}" must not be an upstream nor the downstream of the pump"#,
+                    late_stream_name
+                )));
+            }
+
+            for upstream_name in pump.upstreams() {
+                let upstream = pipeline.get_stream(upstream_name)?;
+                for late_coldef in late_stream.shape().columns() {
+                    let late_cdt = late_coldef.column_data_type();
+                    let upstream_cdt = upstream
+                        .shape()
+                        .columns()
+                        .iter()
+                        .map(|coldef| coldef.column_data_type())
+                        .find(|cdt| cdt.column_name() == late_cdt.column_name());
+
+                    match upstream_cdt {
+                        Some(upstream_cdt) if upstream_cdt.sql_type() != late_cdt.sql_type() => {
+                            return Err(SpringError::Sql(anyhow!(
+                                r#"column "{}" of late rows stream "{}" has a different type from upstream "{}""#,
+                                late_cdt.column_name(),
+                                late_stream_name,
+                                upstream_name
+                            )))
+                        }
+                        Some(upstream_cdt) if upstream_cdt.nullable() && !late_cdt.nullable() => {
+                            return Err(SpringError::Sql(anyhow!(
+                                r#"NOT NULL column "{}" of late rows stream "{}" is nullable in upstream "{}""#,
+                                late_cdt.column_name(),
+                                late_stream_name,
+                                upstream_name
+                            )))
+                        }
+                        None if !late_cdt.nullable() => {
+                            return Err(SpringError::Sql(anyhow!(
+                                r#"NOT NULL column "{}" of late rows stream "{}" is missing in upstream "{}""#,
+                                late_cdt.column_name(),
+                                late_stream_name,
+                                upstream_name
+                            )))
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn compile_select_stream(
         &self,
         select_stream_syntax: SelectStreamSyntax,
@@ -122,12 +263,14 @@ impl SqlProcessor {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
     use crate::{
         pipeline::{
-            OptionsBuilder, PipelineVersion, SinkWriterModel, SinkWriterName, SinkWriterType,
-            SourceReaderModel, SourceReaderName, SourceReaderType, StreamModel, StreamName,
-            StreamShape,
+            JoinType, OptionsBuilder, PipelineVersion, SinkWriterModel, SinkWriterName,
+            SinkWriterType, SourceReaderModel, SourceReaderName, SourceReaderType, StreamModel,
+            StreamName, StreamShape, TableModel,
         },
         stream_engine::command::AlterPipelineCommand,
     };
@@ -241,4 +384,175 @@ mod tests {
             Command::AlterPipeline(AlterPipelineCommand::CreateSinkWriter(expected_sink))
         );
     }
+
+    #[test]
+    fn test_create_table() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        let sql = "
+            CREATE TABLE trade_master (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              ticker TEXT NOT NULL,
+              amount INTEGER NOT NULL
+            ) OPTIONS (
+              PATH '/tmp/trade_master.csv',
+              FORMAT 'CSV'
+            );
+            ";
+        let command = processor.compile(sql, &pipeline).unwrap();
+
+        let expected_options = OptionsBuilder::default()
+            .add("PATH", "/tmp/trade_master.csv")
+            .add("FORMAT", "CSV")
+            .build();
+        let expected_table = TableModel::new(
+            Arc::new(StreamModel::new(
+                StreamName::new("trade_master".to_string()),
+                StreamShape::fx_trade(),
+            )),
+            expected_options,
+        );
+
+        assert_eq!(
+            command,
+            Command::AlterPipeline(AlterPipelineCommand::CreateTable(expected_table))
+        );
+    }
+
+    #[test]
+    fn test_create_table_invalid_format() {
+        let processor = SqlProcessor::default();
+        let pipeline = Pipeline::new(PipelineVersion::new());
+
+        let sql = "
+            CREATE TABLE trade_master (
+              ticker TEXT NOT NULL
+            ) OPTIONS (
+              PATH '/tmp/trade_master.xml',
+              FORMAT 'XML'
+            );
+            ";
+        assert!(processor.compile(sql, &pipeline).is_err());
+    }
+
+    #[test]
+    fn test_parse_select_wildcards() {
+        let parser = SqlParser::default();
+
+        let sql = "
+            CREATE PUMP pu_wildcard AS
+              INSERT INTO sink_joined (ts, amount, temperature, doubled_amount)
+              SELECT STREAM *, trade.*, trade.amount * 2
+              FROM trade;
+            ";
+        let create_pump = match parser.parse(sql).unwrap() {
+            ParseSuccess::CreatePump(create_pump) => create_pump,
+            _ => unreachable!(),
+        };
+
+        let fields = create_pump.select_stream_syntax.fields;
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0], SelectFieldSyntax::Wildcard { stream_name: None });
+        assert_eq!(
+            fields[1],
+            SelectFieldSyntax::Wildcard {
+                stream_name: Some(StreamName::new("trade".to_string()))
+            }
+        );
+        assert!(matches!(fields[2], SelectFieldSyntax::ValueExpr { .. }));
+    }
+
+    #[test]
+    fn test_parse_join_types() {
+        let parser = SqlParser::default();
+
+        for (join_clause, expected_join_type) in [
+            ("JOIN", JoinType::Inner),
+            ("INNER JOIN", JoinType::Inner),
+            ("LEFT OUTER JOIN", JoinType::LeftOuter),
+            ("right  outer\n join", JoinType::RightOuter),
+            ("FULL OUTER JOIN", JoinType::FullOuter),
+        ] {
+            let sql = format!(
+                "
+                CREATE PUMP pu_join AS
+                  INSERT INTO sink_joined (amount, temperature)
+                  SELECT STREAM trade.amount, city_temperature.temperature
+                  FROM trade
+                  {} city_temperature
+                    ON trade.ts = city_temperature.ts
+                  FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+                ",
+                join_clause
+            );
+            let create_pump = match parser.parse(sql).unwrap() {
+                ParseSuccess::CreatePump(create_pump) => create_pump,
+                _ => unreachable!(),
+            };
+            match create_pump.select_stream_syntax.from_item {
+                sql_parser::FromItemSyntax::JoinVariant { join_type, .. } => {
+                    assert_eq!(join_type, expected_join_type, "{}", join_clause)
+                }
+                sql_parser::FromItemSyntax::StreamVariant(_) => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_left_deep_join() {
+        let parser = SqlParser::default();
+
+        let sql = "
+            CREATE PUMP pu_join AS
+              INSERT INTO sink_joined (amount, temperature, humidity)
+              SELECT STREAM trade.amount, city_temperature.temperature, city_humidity.humidity
+              FROM trade
+              JOIN city_temperature
+                ON trade.ts = city_temperature.ts
+              LEFT OUTER JOIN city_humidity
+                ON trade.ts = city_humidity.ts
+              FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+            ";
+        let create_pump = match parser.parse(sql).unwrap() {
+            ParseSuccess::CreatePump(create_pump) => create_pump,
+            _ => unreachable!(),
+        };
+
+        let (left, right, join_type) = match create_pump.select_stream_syntax.from_item {
+            sql_parser::FromItemSyntax::JoinVariant {
+                left,
+                right,
+                join_type,
+                ..
+            } => (left, right, join_type),
+            sql_parser::FromItemSyntax::StreamVariant(_) => unreachable!(),
+        };
+        assert_eq!(join_type, JoinType::LeftOuter);
+        assert_eq!(
+            right.stream_name,
+            StreamName::new("city_humidity".to_string())
+        );
+
+        let (left, right, join_type) = match *left {
+            sql_parser::FromItemSyntax::JoinVariant {
+                left,
+                right,
+                join_type,
+                ..
+            } => (left, right, join_type),
+            sql_parser::FromItemSyntax::StreamVariant(_) => unreachable!(),
+        };
+        assert_eq!(join_type, JoinType::Inner);
+        assert_eq!(
+            right.stream_name,
+            StreamName::new("city_temperature".to_string())
+        );
+        match *left {
+            sql_parser::FromItemSyntax::StreamVariant(sub) => {
+                assert_eq!(sub.stream_name, StreamName::new("trade".to_string()))
+            }
+            sql_parser::FromItemSyntax::JoinVariant { .. } => unreachable!(),
+        }
+    }
 }
@@ -59,16 +59,17 @@ mod select_syntax_analyzer;
 
 use crate::{
     api::error::Result,
-    expr_resolver::{ExprLabel, ExprResolver},
+    expr_resolver::ExprResolver,
     pipeline::{
-        AggregateParameter, GroupByLabels, Pipeline, WindowOperationParameter, WindowParameter,
+        AggregateExprParameter, AggregateParameter, GroupByLabels, Pipeline,
+        WindowOperationParameter, WindowParameter,
     },
     sql_processor::{
         query_planner::select_syntax_analyzer::SelectSyntaxAnalyzer,
         sql_parser::{GroupingElementSyntax, SelectStreamSyntax},
     },
     stream_engine::command::{
-        GroupAggregateWindowOp, JoinOp, LowerOps, ProjectionOp, QueryPlan, UpperOps,
+        GroupAggregateWindowOp, JoinOp, LowerOps, ProjectionOp, QueryPlan, SelectionOp, UpperOps,
     },
 };
 
@@ -84,15 +85,19 @@ impl QueryPlanner {
         }
     }
 
-    pub fn plan(self, pipeline: &Pipeline) -> Result<QueryPlan> {
+    pub fn plan(mut self, pipeline: &Pipeline) -> Result<QueryPlan> {
+        self.analyzer.expand_wildcards(pipeline)?;
+        self.analyzer.check_aggregates()?;
+        self.analyzer.check_emit_clause()?;
+        self.analyzer.check_types(pipeline)?;
+
         let (mut expr_resolver, labels_select_list) =
             ExprResolver::new(self.analyzer.select_list().to_vec());
         let projection = ProjectionOp {
             expr_labels: labels_select_list,
         };
 
-        let group_aggr_window =
-            self.create_group_aggr_window_op(&projection, &mut expr_resolver)?;
+        let group_aggr_window = self.create_group_aggr_window_op(&mut expr_resolver)?;
 
         let upper_ops = UpperOps {
             projection,
@@ -100,22 +105,30 @@ impl QueryPlanner {
         };
 
         let join = self.create_join_op(&mut expr_resolver, pipeline)?;
-        let lower_ops = LowerOps { join };
+        let table_lookups = self
+            .analyzer
+            .table_lookup_ops(&mut expr_resolver, pipeline)?;
+        let selection = self.create_selection_op(&mut expr_resolver);
+        let lower_ops = LowerOps {
+            join,
+            table_lookups,
+            selection,
+        };
 
         Ok(QueryPlan::new(upper_ops, lower_ops, expr_resolver))
     }
 
     fn create_group_aggr_window_op(
         &self,
-        projection_op: &ProjectionOp,
         expr_resolver: &mut ExprResolver,
     ) -> Result<Option<GroupAggregateWindowOp>> {
         let window_param = self.create_window_param();
-        let group_aggr_param = self.create_group_aggr_param(expr_resolver, projection_op)?;
+        let group_aggr_param = self.create_group_aggr_param(expr_resolver)?;
 
         match (window_param, group_aggr_param) {
             (Some(window_param), Some(group_aggr_param)) => Ok(Some(GroupAggregateWindowOp {
                 window_param,
+                emit_policy: self.analyzer.emit_policy(),
                 op_param: WindowOperationParameter::Aggregate(group_aggr_param),
             })),
             _ => Ok(None),
@@ -129,47 +142,58 @@ impl QueryPlanner {
     fn create_group_aggr_param(
         &self,
         expr_resolver: &mut ExprResolver,
-        projection_op: &ProjectionOp,
     ) -> Result<Option<AggregateParameter>> {
         let grouping_elements = self.analyzer.grouping_elements();
-        let aggr_labels = projection_op
-            .expr_labels
-            .iter()
-            .filter_map(|label| {
-                if let ExprLabel::Aggr(aggr_label) = label {
-                    Some(*aggr_label)
-                } else {
-                    None
-                }
+
+        let group_by_labels = GroupByLabels::new(
+            grouping_elements
+                .iter()
+                .map(|grouping_elem| match grouping_elem {
+                    GroupingElementSyntax::ValueExpr(expr) => {
+                        Ok(expr_resolver.register_value_expr(expr.clone()))
+                    }
+                    GroupingElementSyntax::ValueAlias(alias) => {
+                        expr_resolver.resolve_value_alias(alias.clone())
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?,
+        );
+
+        // aggregate expressions only in HAVING clause are registered here
+        let having_label = self
+            .analyzer
+            .having_condition()
+            .map(|having_condition| {
+                expr_resolver.register_having_condition(having_condition, &group_by_labels)
             })
-            .collect::<Vec<_>>();
-
-        match aggr_labels.len() {
-            1 => {
-                let aggr_label = aggr_labels.get(0).expect("len checked");
-                let aggr_func = expr_resolver.resolve_aggr_expr(*aggr_label).func;
-
-                let group_by_labels = grouping_elements
-                    .iter()
-                    .map(|grouping_elem| match grouping_elem {
-                        GroupingElementSyntax::ValueExpr(expr) => {
-                            Ok(expr_resolver.register_value_expr(expr.clone()))
-                        }
-                        GroupingElementSyntax::ValueAlias(alias) => {
-                            expr_resolver.resolve_value_alias(alias.clone())
-                        }
-                    })
-                    .collect::<Result<Vec<_>>>()?;
-
-                Ok(Some(AggregateParameter::new(
-                    aggr_func,
-                    *aggr_label,
-                    GroupByLabels::new(group_by_labels),
-                )))
-            }
-            0 => Ok(None),
-            _ => unimplemented!("2 or more aggregate expressions"),
+            .transpose()?;
+
+        let aggr_labels = expr_resolver.aggr_labels();
+        if aggr_labels.is_empty() {
+            return Ok(None);
         }
+
+        let aggr_exprs = aggr_labels
+            .into_iter()
+            .map(|aggr_label| {
+                let aggr_func = expr_resolver.resolve_aggr_expr(aggr_label).func;
+                AggregateExprParameter::new(aggr_func, aggr_label)
+            })
+            .collect();
+
+        Ok(Some(AggregateParameter::new(
+            aggr_exprs,
+            group_by_labels,
+            having_label,
+        )))
+    }
+
+    fn create_selection_op(&self, expr_resolver: &mut ExprResolver) -> Option<SelectionOp> {
+        self.analyzer
+            .where_condition()
+            .map(|where_condition| SelectionOp {
+                where_condition: expr_resolver.register_value_expr(where_condition),
+            })
     }
 
     fn create_join_op(
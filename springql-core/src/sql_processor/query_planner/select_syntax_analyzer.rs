@@ -3,6 +3,8 @@
 mod field;
 mod from_item;
 mod group_aggregate;
+mod selection;
+mod type_check;
 mod window;
 
 use crate::sql_processor::sql_parser::SelectStreamSyntax;
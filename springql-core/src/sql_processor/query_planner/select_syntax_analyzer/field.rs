@@ -1,11 +1,77 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::sql_processor::{
-    query_planner::select_syntax_analyzer::SelectSyntaxAnalyzer, sql_parser::SelectFieldSyntax,
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    expression::ValueExpr,
+    pipeline::{Pipeline, StreamName},
+    sql_processor::{
+        query_planner::select_syntax_analyzer::SelectSyntaxAnalyzer,
+        sql_parser::{FromItemSyntax, SelectFieldSyntax},
+    },
 };
 
 impl SelectSyntaxAnalyzer {
     pub fn select_list(&self) -> &[SelectFieldSyntax] {
         &self.select_syntax.fields
     }
+
+    /// Replaces `*` and `stream_name.*` in select list with the column references of streams (and tables) in FROM clause,
+    /// in the order of FROM clause and then in the order of their columns.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - a stream in FROM clause is not found in pipeline.
+    ///   - `stream_name.*` refers to a stream not in FROM clause.
+    pub fn expand_wildcards(&mut self, pipeline: &Pipeline) -> Result<()> {
+        let from_stream_names = Self::from_stream_names(&self.select_syntax.from_item);
+
+        let mut fields = Vec::with_capacity(self.select_syntax.fields.len());
+        for field in self.select_syntax.fields.drain(..) {
+            match field {
+                SelectFieldSyntax::Wildcard { stream_name } => {
+                    let stream_names = match stream_name {
+                        Some(stream_name) if from_stream_names.contains(&stream_name) => {
+                            vec![stream_name]
+                        }
+                        Some(stream_name) => {
+                            return Err(SpringError::Sql(anyhow!(
+                                r#""{}.*" refers to a stream not in FROM clause"#,
+                                stream_name
+                            )))
+                        }
+                        None => from_stream_names.clone(),
+                    };
+
+                    for stream_name in stream_names {
+                        let stream = pipeline.get_stream_or_table(&stream_name)?;
+                        fields.extend(stream.column_references().into_iter().map(|colref| {
+                            SelectFieldSyntax::ValueExpr {
+                                value_expr: ValueExpr::ColumnReference(colref),
+                                alias: None,
+                            }
+                        }));
+                    }
+                }
+                _ => fields.push(field),
+            }
+        }
+        self.select_syntax.fields = fields;
+
+        Ok(())
+    }
+
+    /// Streams in FROM clause from the leftmost.
+    fn from_stream_names(from_item: &FromItemSyntax) -> Vec<StreamName> {
+        match from_item {
+            FromItemSyntax::StreamVariant(sub_from_item) => vec![sub_from_item.stream_name.clone()],
+            FromItemSyntax::JoinVariant { left, right, .. } => {
+                let mut stream_names = Self::from_stream_names(left);
+                stream_names.push(right.stream_name.clone());
+                stream_names
+            }
+        }
+    }
 }
@@ -1,60 +1,334 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use anyhow::anyhow;
+
 use crate::{
-    api::error::Result,
+    api::error::{Result, SpringError},
     expr_resolver::ExprResolver,
-    pipeline::{JoinParameter, Pipeline},
+    expression::{BinaryExpr, ComparisonFunction, LogicalFunction, ValueExpr},
+    pipeline::{
+        ColumnName, ColumnReference, EmitPolicy, JoinParameter, JoinType, Pipeline, TableModel,
+    },
     sql_processor::{
         query_planner::SelectSyntaxAnalyzer,
         sql_parser::{FromItemSyntax, SubFromItemSyntax},
     },
-    stream_engine::command::{CollectOp, JoinOp, JoinWindowOp},
+    stream_engine::command::{CollectOp, JoinOp, JoinWindowOp, TableLookupOp},
 };
 
+/// `JOIN table ON condition`
+type TableJoin = (SubFromItemSyntax, JoinType, ValueExpr);
+
 impl SelectSyntaxAnalyzer {
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - a stream is not found in pipeline.
+    ///   - 3 or more streams are joined with a count-based window.
+    ///   - FROM clause starts with a table, or a stream is joined after a table.
     pub fn join_op(&self, expr_resolver: &mut ExprResolver, pipeline: &Pipeline) -> Result<JoinOp> {
-        match self.select_syntax.from_item.clone() {
+        let (from_item, _) = self.split_table_joins(pipeline)?;
+
+        if let FromItemSyntax::JoinVariant { left, .. } = &from_item {
+            if matches!(left.as_ref(), FromItemSyntax::JoinVariant { .. })
+                && self
+                    .window_parameter()
+                    .and_then(|window_param| window_param.rows_length_and_period())
+                    .is_some()
+            {
+                return Err(SpringError::Sql(anyhow!(
+                    "count-based windows are not supported for joins of 3 or more streams"
+                )));
+            }
+        }
+
+        let (join_op, _) = self.join_op_from_item(from_item, true, expr_resolver, pipeline)?;
+        Ok(join_op)
+    }
+
+    /// Lookups of tables joined to the joined streams, from the innermost.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - FROM clause starts with a table, or a stream is joined after a table.
+    ///   - a table is joined by other than `INNER JOIN` and `LEFT OUTER JOIN`.
+    ///   - ON condition of a table join is not `table.column = expression` (where expression does not refer to the table).
+    ///   - the key column is not in the table.
+    pub fn table_lookup_ops(
+        &self,
+        expr_resolver: &mut ExprResolver,
+        pipeline: &Pipeline,
+    ) -> Result<Vec<TableLookupOp>> {
+        let (_, table_joins) = self.split_table_joins(pipeline)?;
+
+        table_joins
+            .into_iter()
+            .map(|(sub_from_item, join_type, on_expr)| {
+                let table = pipeline.get_table(&sub_from_item.stream_name)?;
+
+                if !matches!(join_type, JoinType::Inner | JoinType::LeftOuter) {
+                    return Err(SpringError::Sql(anyhow!(
+                        r#"table "{}" can only be joined by INNER JOIN or LEFT OUTER JOIN"#,
+                        table.name()
+                    )));
+                }
+
+                let (key_column, probe) = Self::table_lookup_key(&table, on_expr)?;
+
+                Ok(TableLookupOp {
+                    table: table.name().clone(),
+                    join_type,
+                    key_column,
+                    probe: expr_resolver.register_value_expr(probe),
+                    table_colrefs: table.column_references(),
+                })
+            })
+            .collect()
+    }
+
+    /// Splits FROM clause into the joins of streams and the joins of tables following them.
+    ///
+    /// Tables are looked up by each row from the joined streams, so they must come after all the streams.
+    fn split_table_joins(&self, pipeline: &Pipeline) -> Result<(FromItemSyntax, Vec<TableJoin>)> {
+        let is_table = |sub_from_item: &SubFromItemSyntax| {
+            pipeline.get_table(&sub_from_item.stream_name).is_ok()
+        };
+
+        let mut table_joins = vec![];
+        let mut from_item = self.select_syntax.from_item.clone();
+        while let FromItemSyntax::JoinVariant {
+            left,
+            right,
+            join_type,
+            on_expr,
+        } = from_item
+        {
+            if is_table(&right) {
+                table_joins.push((right, join_type, on_expr));
+                from_item = *left;
+            } else {
+                from_item = FromItemSyntax::JoinVariant {
+                    left,
+                    right,
+                    join_type,
+                    on_expr,
+                };
+                break;
+            }
+        }
+        table_joins.reverse();
+
+        let mut inner = &from_item;
+        loop {
+            match inner {
+                FromItemSyntax::StreamVariant(sub_from_item) => {
+                    if is_table(sub_from_item) {
+                        return Err(SpringError::Sql(anyhow!(
+                            r#"FROM clause must start with a stream but "{}" is a table"#,
+                            sub_from_item.stream_name
+                        )));
+                    }
+                    break;
+                }
+                FromItemSyntax::JoinVariant { left, right, .. } => {
+                    if is_table(right) {
+                        return Err(SpringError::Sql(anyhow!(
+                            r#"table "{}" must be joined after all the streams"#,
+                            right.stream_name
+                        )));
+                    }
+                    inner = left;
+                }
+            }
+        }
+
+        Ok((from_item, table_joins))
+    }
+
+    /// `(key column of the table, expression to probe the key)` from `table.column = expression` or `expression = table.column`.
+    fn table_lookup_key(table: &TableModel, on_expr: ValueExpr) -> Result<(ColumnName, ValueExpr)> {
+        let table_name = table.name();
+        let refers_table = |expr: &ValueExpr| {
+            expr.column_references().iter().any(|colref| {
+                matches!(colref, ColumnReference::Column { stream_name, .. } if stream_name == table_name)
+            })
+        };
+
+        let key_and_probe = match on_expr {
+            ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::EqualVariant { left, right },
+            )) => match (*left, *right) {
+                (
+                    ValueExpr::ColumnReference(ColumnReference::Column {
+                        stream_name,
+                        column_name,
+                    }),
+                    probe,
+                )
+                | (
+                    probe,
+                    ValueExpr::ColumnReference(ColumnReference::Column {
+                        stream_name,
+                        column_name,
+                    }),
+                ) if &stream_name == table_name && !refers_table(&probe) => {
+                    Some((column_name, probe))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let (key_column, probe) = key_and_probe.ok_or_else(|| {
+            SpringError::Sql(anyhow!(
+                r#"ON condition of table "{}" must be `{}.column = expression`"#,
+                table_name,
+                table_name
+            ))
+        })?;
+
+        if !table
+            .stream_model()
+            .shape()
+            .column_names()
+            .contains(&key_column)
+        {
+            return Err(SpringError::Sql(anyhow!(
+                r#"column "{}" is not in table "{}""#,
+                key_column,
+                table_name
+            )));
+        }
+
+        Ok((key_column, probe))
+    }
+
+    /// # Returns
+    ///
+    /// Join op and column references of all the streams in it.
+    ///
+    /// Only the `outermost` join window emits provisional results (`EMIT` clause).
+    /// Inner join windows pass their final results to the outer one.
+    fn join_op_from_item(
+        &self,
+        from_item: FromItemSyntax,
+        outermost: bool,
+        expr_resolver: &mut ExprResolver,
+        pipeline: &Pipeline,
+    ) -> Result<(JoinOp, Vec<ColumnReference>)> {
+        match from_item {
             FromItemSyntax::StreamVariant(sub_from_item) => {
+                let colrefs = pipeline
+                    .get_stream(&sub_from_item.stream_name)?
+                    .column_references();
                 let collect_op = Self::sub_from_item_to_collect_op(sub_from_item);
-                Ok(JoinOp::Collect(collect_op))
+                Ok((JoinOp::Collect(collect_op), colrefs))
             }
             FromItemSyntax::JoinVariant {
-                left: left_sub,
-                right,
+                left,
+                right: right_sub,
                 join_type,
                 on_expr,
             } => {
-                let right_sub = match right.as_ref() {
-                    FromItemSyntax::StreamVariant(sub_from_item) => sub_from_item,
-                    FromItemSyntax::JoinVariant { .. } => unimplemented!("recursive join"),
-                };
-
-                let left_collect_op = Self::sub_from_item_to_collect_op(left_sub.clone());
-                let right_collect_op = Self::sub_from_item_to_collect_op(right_sub.clone());
+                let (left_join_op, left_colrefs) =
+                    self.join_op_from_item(*left, false, expr_resolver, pipeline)?;
 
-                let left_colrefs = pipeline
-                    .get_stream(&left_sub.stream_name)?
-                    .column_references();
                 let right_colrefs = pipeline
                     .get_stream(&right_sub.stream_name)?
                     .column_references();
+                let right_collect_op = Self::sub_from_item_to_collect_op(right_sub);
 
+                let colrefs: Vec<ColumnReference> = left_colrefs
+                    .iter()
+                    .chain(right_colrefs.iter())
+                    .cloned()
+                    .collect();
+                if let Some(colref) = on_expr.column_references().into_iter().find(|colref| {
+                    matches!(colref, ColumnReference::Column { .. }) && !colrefs.contains(colref)
+                }) {
+                    return Err(SpringError::Sql(anyhow!(
+                        "ON condition refers to {:?}, which is not in the joined streams",
+                        colref
+                    )));
+                }
+
+                let equi_keys = Self::join_equi_keys(&on_expr, &left_colrefs, &right_colrefs)
+                    .into_iter()
+                    .map(|(left_key, right_key)| {
+                        (
+                            expr_resolver.register_value_expr(left_key),
+                            expr_resolver.register_value_expr(right_key),
+                        )
+                    })
+                    .collect();
                 let on_expr_label = expr_resolver.register_value_expr(on_expr);
 
-                let join_param =
-                    JoinParameter::new(join_type, left_colrefs, right_colrefs, on_expr_label);
+                let join_param = JoinParameter::new(
+                    join_type,
+                    left_colrefs,
+                    right_colrefs,
+                    on_expr_label,
+                    equi_keys,
+                );
 
                 let window_param = self
                     .window_parameter()
                     .expect("JOIN must take window clause");
+                let emit_policy = if outermost {
+                    self.emit_policy()
+                } else {
+                    EmitPolicy::Final
+                };
 
-                Ok(JoinOp::JoinWindow(JoinWindowOp {
-                    left: left_collect_op,
+                let join_op = JoinOp::JoinWindow(JoinWindowOp {
+                    left: Box::new(left_join_op),
                     right: right_collect_op,
                     window_param,
+                    emit_policy,
                     join_param,
-                }))
+                });
+                Ok((join_op, colrefs))
+            }
+        }
+    }
+
+    /// `(left key, right key)` from each `AND`-ed `left_expr = right_expr` in ON condition,
+    /// where `left_expr` only refers to left columns and `right_expr` only refers to right columns (or reversed).
+    fn join_equi_keys(
+        on_expr: &ValueExpr,
+        left_colrefs: &[ColumnReference],
+        right_colrefs: &[ColumnReference],
+    ) -> Vec<(ValueExpr, ValueExpr)> {
+        let only_refers = |expr: &ValueExpr, colrefs: &[ColumnReference]| {
+            let expr_colrefs = expr.column_references();
+            !expr_colrefs.is_empty() && expr_colrefs.iter().all(|colref| colrefs.contains(colref))
+        };
+
+        match on_expr {
+            ValueExpr::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+                LogicalFunction::AndVariant { left, right },
+            )) => {
+                let mut keys = Self::join_equi_keys(left, left_colrefs, right_colrefs);
+                keys.append(&mut Self::join_equi_keys(
+                    right,
+                    left_colrefs,
+                    right_colrefs,
+                ));
+                keys
+            }
+            ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::EqualVariant { left, right },
+            )) => {
+                if only_refers(left, left_colrefs) && only_refers(right, right_colrefs) {
+                    vec![(left.as_ref().clone(), right.as_ref().clone())]
+                } else if only_refers(right, left_colrefs) && only_refers(left, right_colrefs) {
+                    vec![(right.as_ref().clone(), left.as_ref().clone())]
+                } else {
+                    vec![]
+                }
             }
+            _ => vec![],
         }
     }
 
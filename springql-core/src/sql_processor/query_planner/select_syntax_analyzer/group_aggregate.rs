@@ -1,11 +1,109 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::sql_processor::{
-    query_planner::SelectSyntaxAnalyzer, sql_parser::GroupingElementSyntax,
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    expression::ValueExpr,
+    sql_processor::{
+        query_planner::SelectSyntaxAnalyzer,
+        sql_parser::{GroupingElementSyntax, SelectFieldSyntax},
+    },
 };
 
 impl SelectSyntaxAnalyzer {
     pub fn grouping_elements(&self) -> Vec<GroupingElementSyntax> {
         self.select_syntax.grouping_elements.clone()
     }
+
+    pub fn having_condition(&self) -> Option<ValueExpr> {
+        self.select_syntax.having_condition.clone()
+    }
+
+    /// HAVING condition whose alias references are replaced with the aliased expressions in select list.
+    /// Used to check the condition before planning (aliases are resolved by `ExprResolver` on planning).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - an alias is not in select list.
+    pub fn having_condition_with_aliases_inlined(&self) -> Result<Option<ValueExpr>> {
+        self.having_condition()
+            .map(|having_condition| {
+                having_condition.try_map_leaves(&mut |leaf| match leaf {
+                    ValueExpr::AliasRef(alias) => self
+                        .select_syntax
+                        .fields
+                        .iter()
+                        .find_map(|field| match field {
+                            SelectFieldSyntax::AggrExpr {
+                                aggr_expr,
+                                alias: Some(aggr_alias),
+                            } if aggr_alias.as_ref() == alias => {
+                                Some(ValueExpr::AggrExpr(Box::new(aggr_expr.clone())))
+                            }
+                            SelectFieldSyntax::ValueExpr {
+                                value_expr,
+                                alias: Some(value_alias),
+                            } if value_alias.as_ref() == alias => Some(value_expr.clone()),
+                            _ => None,
+                        })
+                        .ok_or_else(|| {
+                            SpringError::Sql(anyhow!("alias `{}` is not in select list", alias))
+                        }),
+                    _ => Ok(leaf),
+                })
+            })
+            .transpose()
+    }
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - WHERE clause or GROUP BY clause has an aggregate expression.
+    ///   - select list or HAVING clause has an aggregate expression but window clause is missing.
+    ///   - HAVING clause is supplied without any aggregate expression.
+    pub fn check_aggregates(&self) -> Result<()> {
+        let select_syntax = &self.select_syntax;
+
+        let where_has_aggr = select_syntax
+            .where_condition
+            .iter()
+            .any(|where_condition| where_condition.has_aggr_expr());
+        let group_by_has_aggr = select_syntax
+            .grouping_elements
+            .iter()
+            .any(|grouping_element| match grouping_element {
+                GroupingElementSyntax::ValueExpr(value_expr) => value_expr.has_aggr_expr(),
+                GroupingElementSyntax::ValueAlias(_) => false,
+            });
+        if where_has_aggr || group_by_has_aggr {
+            return Err(SpringError::Sql(anyhow!(
+                "aggregate functions are not allowed in WHERE or GROUP BY clause"
+            )));
+        }
+
+        let select_has_aggr = select_syntax.fields.iter().any(|field| match field {
+            SelectFieldSyntax::AggrExpr { .. } => true,
+            SelectFieldSyntax::ValueExpr { value_expr, .. } => value_expr.has_aggr_expr(),
+            SelectFieldSyntax::Wildcard { .. } => false,
+        });
+        let having_condition = self.having_condition_with_aliases_inlined()?;
+        let having_has_aggr = having_condition
+            .iter()
+            .any(|having_condition| having_condition.has_aggr_expr());
+        if having_condition.is_some() && !select_has_aggr && !having_has_aggr {
+            return Err(SpringError::Sql(anyhow!(
+                "HAVING clause requires aggregate functions"
+            )));
+        }
+
+        if (select_has_aggr || having_has_aggr) && select_syntax.window_clause.is_none() {
+            return Err(SpringError::Sql(anyhow!(
+                "aggregate functions require a window clause"
+            )));
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,78 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::Result,
+    expression::{AggrExpr, ValueExpr},
+    pipeline::Pipeline,
+    sql_processor::{
+        query_planner::SelectSyntaxAnalyzer,
+        sql_parser::{FromItemSyntax, GroupingElementSyntax, SelectFieldSyntax},
+    },
+};
+
+impl SelectSyntaxAnalyzer {
+    /// Infers types of all the value expressions in the SELECT statement to find type errors before any row arrives.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - A value expression is ill-typed.
+    ///   - An aggregate function takes a value of unsupported type.
+    ///   - An alias is referenced outside of HAVING clause.
+    pub fn check_types(&self, pipeline: &Pipeline) -> Result<()> {
+        self.aggr_exprs()
+            .into_iter()
+            .try_for_each(|aggr_expr| aggr_expr.infer_type(pipeline).map(|_| ()))?;
+        self.value_exprs()
+            .into_iter()
+            .try_for_each(|value_expr| value_expr.infer_type(pipeline).map(|_| ()))?;
+        self.having_condition_with_aliases_inlined()?
+            .map_or(Ok(()), |having_condition| {
+                having_condition.infer_type(pipeline).map(|_| ())
+            })
+    }
+
+    fn aggr_exprs(&self) -> Vec<&AggrExpr> {
+        self.select_syntax
+            .fields
+            .iter()
+            .filter_map(|field| match field {
+                SelectFieldSyntax::AggrExpr { aggr_expr, .. } => Some(aggr_expr),
+                SelectFieldSyntax::ValueExpr { .. } | SelectFieldSyntax::Wildcard { .. } => None,
+            })
+            .collect()
+    }
+
+    fn value_exprs(&self) -> Vec<&ValueExpr> {
+        let select_syntax = &self.select_syntax;
+
+        let mut value_exprs: Vec<&ValueExpr> = select_syntax
+            .fields
+            .iter()
+            .filter_map(|field| match field {
+                SelectFieldSyntax::ValueExpr { value_expr, .. } => Some(value_expr),
+                SelectFieldSyntax::AggrExpr { .. } | SelectFieldSyntax::Wildcard { .. } => None,
+            })
+            .collect();
+
+        value_exprs.extend(select_syntax.where_condition.iter());
+
+        let mut from_item = &select_syntax.from_item;
+        while let FromItemSyntax::JoinVariant { left, on_expr, .. } = from_item {
+            value_exprs.push(on_expr);
+            from_item = left;
+        }
+
+        value_exprs.extend(
+            select_syntax
+                .grouping_elements
+                .iter()
+                .filter_map(|grouping_element| match grouping_element {
+                    GroupingElementSyntax::ValueExpr(value_expr) => Some(value_expr),
+                    GroupingElementSyntax::ValueAlias(_) => None,
+                }),
+        );
+
+        value_exprs
+    }
+}
@@ -1,9 +1,34 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::{pipeline::WindowParameter, sql_processor::query_planner::SelectSyntaxAnalyzer};
+use anyhow::anyhow;
+
+use crate::{
+    api::error::{Result, SpringError},
+    pipeline::{EmitPolicy, WindowParameter},
+    sql_processor::query_planner::SelectSyntaxAnalyzer,
+};
 
 impl SelectSyntaxAnalyzer {
     pub fn window_parameter(&self) -> Option<WindowParameter> {
         self.select_syntax.window_clause.clone()
     }
+
+    /// `EmitPolicy::Final` when no EMIT clause is supplied.
+    pub fn emit_policy(&self) -> EmitPolicy {
+        self.select_syntax.emit_clause.unwrap_or_default()
+    }
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - EMIT clause is supplied without window clause.
+    pub fn check_emit_clause(&self) -> Result<()> {
+        if self.select_syntax.emit_clause.is_some() && self.select_syntax.window_clause.is_none() {
+            Err(SpringError::Sql(anyhow!(
+                "EMIT clause requires a window clause"
+            )))
+        } else {
+            Ok(())
+        }
+    }
 }
@@ -1,7 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::{
-    pipeline::{PumpName, SinkWriterModel, SourceReaderModel, StreamModel},
+    pipeline::{PumpName, SinkWriterModel, SourceReaderModel, StreamModel, StreamName, TableModel},
     sql_processor::sql_parser::syntax::SelectStreamSyntax,
     stream_engine::command::InsertPlan,
 };
@@ -14,6 +14,7 @@ pub enum ParseSuccess {
     CreateStream(StreamModel),
     CreateSinkStream(StreamModel),
     CreateSinkWriter(SinkWriterModel),
+    CreateTable(TableModel),
     CreatePump(Box<CreatePump>),
 }
 
@@ -22,4 +23,6 @@ pub struct CreatePump {
     pub pump_name: PumpName,
     pub select_stream_syntax: SelectStreamSyntax,
     pub insert_plan: InsertPlan,
+    /// `LATE ROWS INTO <stream>`
+    pub late_rows_into: Option<StreamName>,
 }
@@ -3,30 +3,39 @@
 mod generated_parser;
 mod helper;
 
-use std::convert::identity;
+use std::{convert::identity, sync::Arc};
 
 use anyhow::{anyhow, Context};
+use once_cell::sync::Lazy;
 use ordered_float::OrderedFloat;
-use pest::{iterators::Pairs, Parser};
+use pest::{
+    iterators::Pairs,
+    pratt_parser::{Assoc, Op, PrattParser},
+    Parser,
+};
 
 use crate::{
     api::error::{Result, SpringError},
     expression::{
-        AggrExpr, BinaryExpr, BinaryOperator, ComparisonFunction, FunctionCall, LogicalFunction,
-        NumericalFunction, UnaryOperator, ValueExpr,
+        AggrExpr, BinaryExpr, BinaryOperator, CaseExpr, ComparisonFunction, ConstantSet,
+        FunctionCall, InList, LogicalFunction, NumericalFunction, TrimSide, UnaryOperator,
+        ValueExpr,
     },
     pipeline::{
         AggrAlias, AggregateFunctionParameter, ColumnConstraint, ColumnDataType, ColumnDefinition,
-        ColumnName, ColumnReference, CorrelationAlias, JoinType, OptionsBuilder, PumpName,
-        SinkWriterModel, SinkWriterName, SinkWriterType, SourceReaderModel, SourceReaderName,
-        SourceReaderType, SqlType, StreamModel, StreamName, StreamShape, ValueAlias,
-        WindowParameter,
+        ColumnName, ColumnReference, CorrelationAlias, EmitPolicy, JoinType, OptionsBuilder,
+        PumpName, SinkWriterModel, SinkWriterName, SinkWriterType, SourceReaderModel,
+        SourceReaderName, SourceReaderType, SqlType, StreamModel, StreamName, StreamShape,
+        TableModel, ValueAlias, WindowParameter,
     },
     sql_processor::sql_parser::{
         parse_success::{CreatePump, ParseSuccess},
         pest_parser_impl::{
             generated_parser::{GeneratedParser, Rule},
-            helper::{parse_child, parse_child_seq, self_as_str, try_parse_child, FnParseParams},
+            helper::{
+                pair_params, parse_child, parse_child_seq, self_as_str, try_parse_child,
+                FnParseParams,
+            },
         },
         syntax::{
             ColumnConstraintSyntax, DurationFunction, FromItemSyntax, GroupingElementSyntax,
@@ -40,6 +49,26 @@ use crate::{
     },
 };
 
+/// Operators in lower precedence first.
+static PRATT_PARSER: Lazy<PrattParser<Rule>> = Lazy::new(|| {
+    PrattParser::new()
+        .op(Op::infix(Rule::or_operator, Assoc::Left))
+        .op(Op::infix(Rule::and_operator, Assoc::Left))
+        .op(Op::prefix(Rule::not_operator))
+        .op(Op::postfix(Rule::is_null_operator) | Op::postfix(Rule::is_not_null_operator))
+        .op(Op::infix(Rule::comparison_operator, Assoc::Left))
+        .op(Op::postfix(Rule::in_operator)
+            | Op::postfix(Rule::not_in_operator)
+            | Op::postfix(Rule::between_operator)
+            | Op::postfix(Rule::not_between_operator))
+        .op(Op::infix(Rule::like_operator, Assoc::Left)
+            | Op::infix(Rule::not_like_operator, Assoc::Left))
+        .op(Op::infix(Rule::concat_operator, Assoc::Left))
+        .op(Op::infix(Rule::additive_operator, Assoc::Left))
+        .op(Op::infix(Rule::multiplicative_operator, Assoc::Left))
+        .op(Op::prefix(Rule::minus_operator))
+});
+
 #[derive(Debug, Default)]
 pub struct PestParserImpl;
 
@@ -126,18 +155,22 @@ impl PestParserImpl {
         })
     }
 
+    /// Decimal (`291`) or hexadecimal (`0x123`).
     fn parse_integer_constant(mut params: FnParseParams) -> Result<SqlValue> {
         let s = self_as_str(&mut params);
+        let (digits, radix) = match s.get(..2) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("0x") => (&s[2..], 16),
+            _ => (s, 10),
+        };
 
-        s.parse::<i16>()
+        i16::from_str_radix(digits, radix)
             .map(|i| SqlValue::NotNull(NnSqlValue::SmallInt(i)))
             .or_else(|_| {
-                s.parse::<i32>()
+                i32::from_str_radix(digits, radix)
                     .map(|i| SqlValue::NotNull(NnSqlValue::Integer(i)))
             })
             .or_else(|_| {
-                s.parse::<i64>()
-                    .map(|i| SqlValue::NotNull(NnSqlValue::BigInt(i)))
+                i64::from_str_radix(digits, radix).map(|i| SqlValue::NotNull(NnSqlValue::BigInt(i)))
             })
             .map_err(|_e| {
                 SpringError::Sql(anyhow!(
@@ -232,8 +265,9 @@ impl PestParserImpl {
 
     fn parse_unary_operator(mut params: FnParseParams) -> Result<UnaryOperator> {
         let s = self_as_str(&mut params);
-        match s {
+        match s.to_lowercase().as_str() {
             "-" => Ok(UnaryOperator::Minus),
+            "not" => Ok(UnaryOperator::Not),
             _ => Err(SpringError::Sql(anyhow!(
                 "Does not match any child rule of unary_operator.",
             ))),
@@ -241,12 +275,27 @@ impl PestParserImpl {
     }
 
     fn parse_binary_operator(mut params: FnParseParams) -> Result<BinaryOperator> {
-        let s = self_as_str(&mut params);
+        let s = self_as_str(&mut params)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
         match s.to_lowercase().as_str() {
             "=" => Ok(BinaryOperator::Equal),
+            "<>" | "!=" => Ok(BinaryOperator::NotEqual),
+            "<" => Ok(BinaryOperator::LessThan),
+            "<=" => Ok(BinaryOperator::LessThanOrEqual),
+            ">" => Ok(BinaryOperator::GreaterThan),
+            ">=" => Ok(BinaryOperator::GreaterThanOrEqual),
             "+" => Ok(BinaryOperator::Add),
+            "-" => Ok(BinaryOperator::Sub),
             "*" => Ok(BinaryOperator::Mul),
+            "/" => Ok(BinaryOperator::Div),
+            "%" => Ok(BinaryOperator::Mod),
             "and" => Ok(BinaryOperator::And),
+            "or" => Ok(BinaryOperator::Or),
+            "like" => Ok(BinaryOperator::Like),
+            "not like" => Ok(BinaryOperator::NotLike),
+            "||" => Ok(BinaryOperator::Concat),
             _ => Err(SpringError::Sql(anyhow!(
                 "Does not match any child rule of binary_operator.",
             ))),
@@ -290,6 +339,12 @@ impl PestParserImpl {
             Self::parse_create_sink_writer_command,
             identity,
         )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::create_table_command,
+            Self::parse_create_table_command,
+            identity,
+        )?)
         .or(try_parse_child(
             &mut params,
             Rule::create_pump_command,
@@ -432,6 +487,46 @@ impl PestParserImpl {
         Ok(ParseSuccess::CreateSinkStream(sink_stream))
     }
 
+    /*
+     * ----------------------------------------------------------------------------
+     * CREATE TABLE
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_create_table_command(mut params: FnParseParams) -> Result<ParseSuccess> {
+        let table_name = parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )?;
+        let column_definitions = parse_child_seq(
+            &mut params,
+            Rule::column_definition,
+            &Self::parse_column_definition,
+            &identity,
+        )?;
+        let option_syntaxes = try_parse_child(
+            &mut params,
+            Rule::option_specifications,
+            Self::parse_option_specifications,
+            identity,
+        )?;
+
+        let mut options = OptionsBuilder::default();
+        if let Some(option_syntaxes) = option_syntaxes {
+            for o in option_syntaxes {
+                options = options.add(o.option_name, o.option_value);
+            }
+        }
+        let options = options.build();
+
+        let table_shape = StreamShape::new(column_definitions)?;
+        let table = TableModel::new(Arc::new(StreamModel::new(table_name, table_shape)), options);
+
+        Ok(ParseSuccess::CreateTable(table))
+    }
+
     /*
      * ----------------------------------------------------------------------------
      * CREATE SINK WRITER
@@ -513,14 +608,30 @@ impl PestParserImpl {
             Self::parse_select_stream,
             identity,
         )?;
+        let late_rows_into = try_parse_child(
+            &mut params,
+            Rule::late_rows_clause,
+            Self::parse_late_rows_clause,
+            identity,
+        )?;
 
         Ok(ParseSuccess::CreatePump(Box::new(CreatePump {
             pump_name,
             select_stream_syntax,
             insert_plan: InsertPlan::new(into_stream, insert_column_names),
+            late_rows_into,
         })))
     }
 
+    fn parse_late_rows_clause(mut params: FnParseParams) -> Result<StreamName> {
+        parse_child(
+            &mut params,
+            Rule::stream_name,
+            Self::parse_stream_name,
+            identity,
+        )
+    }
+
     /*
      * ----------------------------------------------------------------------------
      * SELECT
@@ -540,45 +651,59 @@ impl PestParserImpl {
             Self::parse_from_item,
             identity,
         )?;
+        let where_condition = try_parse_child(
+            &mut params,
+            Rule::where_clause,
+            Self::parse_where_clause,
+            identity,
+        )?;
         let grouping_elements = try_parse_child(
             &mut params,
             Rule::group_by_clause,
             Self::parse_group_by_clause,
             identity,
         )?;
+        let having_condition = try_parse_child(
+            &mut params,
+            Rule::having_clause,
+            Self::parse_having_clause,
+            identity,
+        )?;
         let window_clause = try_parse_child(
             &mut params,
             Rule::window_clause,
             Self::parse_window_clause,
             identity,
         )?;
+        let emit_clause = try_parse_child(
+            &mut params,
+            Rule::emit_clause,
+            Self::parse_emit_clause,
+            identity,
+        )?;
 
         Ok(SelectStreamSyntax {
             fields,
             from_item,
+            where_condition,
             grouping_elements: grouping_elements.unwrap_or_default(),
+            having_condition,
             window_clause,
+            emit_clause,
         })
     }
 
     fn parse_select_field(mut params: FnParseParams) -> Result<SelectFieldSyntax> {
-        try_parse_child(
+        if let Some(wildcard) = try_parse_child(
             &mut params,
-            Rule::value_expr,
-            Self::parse_value_expr,
+            Rule::select_wildcard,
+            Self::parse_select_wildcard,
             identity,
-        )?
-        .map(|value_expr| {
-            let alias = try_parse_child(
-                &mut params,
-                Rule::value_alias,
-                Self::parse_value_alias,
-                identity,
-            )?;
-            Ok(SelectFieldSyntax::ValueExpr { value_expr, alias })
-        })
-        .transpose()?
-        .or(try_parse_child(
+        )? {
+            return Ok(wildcard);
+        }
+
+        try_parse_child(
             &mut params,
             Rule::aggr_expr,
             Self::parse_aggr_expr,
@@ -596,6 +721,22 @@ impl PestParserImpl {
                 alias: Some(alias),
             })
         })
+        .transpose()?
+        .or(try_parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )?
+        .map(|value_expr| {
+            let alias = try_parse_child(
+                &mut params,
+                Rule::value_alias,
+                Self::parse_value_alias,
+                identity,
+            )?;
+            Ok(SelectFieldSyntax::ValueExpr { value_expr, alias })
+        })
         .transpose()?)
         .ok_or_else(|| {
             SpringError::Sql(anyhow!(
@@ -605,6 +746,16 @@ impl PestParserImpl {
         })
     }
 
+    fn parse_select_wildcard(mut params: FnParseParams) -> Result<SelectFieldSyntax> {
+        let stream_name = try_parse_child(
+            &mut params,
+            Rule::correlation,
+            Self::parse_correlation,
+            identity,
+        )?;
+        Ok(SelectFieldSyntax::Wildcard { stream_name })
+    }
+
     fn parse_from_item(mut params: FnParseParams) -> Result<FromItemSyntax> {
         let sub_from_item = parse_child(
             &mut params,
@@ -613,35 +764,46 @@ impl PestParserImpl {
             identity,
         )?;
 
-        let opt_join = try_parse_child(
+        let join_clauses = parse_child_seq(
             &mut params,
-            Rule::join_type,
-            Self::parse_join_type,
-            identity,
-        )?
-        .map(|join_type| {
-            let right_from_item = parse_child(
-                &mut params,
-                Rule::from_item,
-                Self::parse_from_item,
-                identity,
-            )?;
-            let on_expr = parse_child(
-                &mut params,
-                Rule::condition,
-                Self::parse_condition,
-                identity,
-            )?;
+            Rule::join_clause,
+            &Self::parse_join_clause,
+            &identity,
+        )?;
 
-            Ok(FromItemSyntax::JoinVariant {
-                left: sub_from_item.clone(),
-                right: Box::new(right_from_item),
+        Ok(join_clauses.into_iter().fold(
+            FromItemSyntax::StreamVariant(sub_from_item),
+            |left, (join_type, right, on_expr)| FromItemSyntax::JoinVariant {
+                left: Box::new(left),
+                right,
                 join_type,
                 on_expr,
-            })
-        });
+            },
+        ))
+    }
 
-        opt_join.unwrap_or(Ok(FromItemSyntax::StreamVariant(sub_from_item)))
+    fn parse_join_clause(
+        mut params: FnParseParams,
+    ) -> Result<(JoinType, SubFromItemSyntax, ValueExpr)> {
+        let join_type = parse_child(
+            &mut params,
+            Rule::join_type,
+            Self::parse_join_type,
+            identity,
+        )?;
+        let right = parse_child(
+            &mut params,
+            Rule::sub_from_item,
+            Self::parse_sub_from_item,
+            identity,
+        )?;
+        let on_expr = parse_child(
+            &mut params,
+            Rule::condition,
+            Self::parse_condition,
+            identity,
+        )?;
+        Ok((join_type, right, on_expr))
     }
 
     fn parse_sub_from_item(mut params: FnParseParams) -> Result<SubFromItemSyntax> {
@@ -662,8 +824,16 @@ impl PestParserImpl {
 
     fn parse_join_type(mut params: FnParseParams) -> Result<JoinType> {
         let s = self_as_str(&mut params);
-        match s.to_lowercase().as_str() {
+        let words = s
+            .to_lowercase()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        match words.as_str() {
             "left outer join" => Ok(JoinType::LeftOuter),
+            "right outer join" => Ok(JoinType::RightOuter),
+            "full outer join" => Ok(JoinType::FullOuter),
+            "inner join" | "join" => Ok(JoinType::Inner),
             _ => Err(SpringError::Sql(anyhow!(
                 "unknown join type {}",
                 s.to_lowercase()
@@ -671,6 +841,15 @@ impl PestParserImpl {
         }
     }
 
+    fn parse_where_clause(mut params: FnParseParams) -> Result<ValueExpr> {
+        parse_child(
+            &mut params,
+            Rule::condition,
+            Self::parse_condition,
+            identity,
+        )
+    }
+
     fn parse_group_by_clause(mut params: FnParseParams) -> Result<Vec<GroupingElementSyntax>> {
         parse_child_seq(
             &mut params,
@@ -681,21 +860,26 @@ impl PestParserImpl {
     }
 
     fn parse_grouping_element(mut params: FnParseParams) -> Result<GroupingElementSyntax> {
-        try_parse_child(
+        parse_child(
             &mut params,
             Rule::value_expr,
             Self::parse_value_expr,
-            GroupingElementSyntax::ValueExpr,
-        )?
-        .or(try_parse_child(
+            |value_expr| match value_expr {
+                ValueExpr::AliasRef(alias) => {
+                    GroupingElementSyntax::ValueAlias(ValueAlias::new(alias))
+                }
+                _ => GroupingElementSyntax::ValueExpr(value_expr),
+            },
+        )
+    }
+
+    fn parse_having_clause(mut params: FnParseParams) -> Result<ValueExpr> {
+        parse_child(
             &mut params,
-            Rule::value_alias,
-            Self::parse_value_alias,
-            GroupingElementSyntax::ValueAlias,
-        )?)
-        .ok_or_else(|| {
-            SpringError::Sql(anyhow!("Failed to parse grouping element: {}", params.sql))
-        })
+            Rule::condition,
+            Self::parse_condition,
+            identity,
+        )
     }
 
     fn parse_window_clause(mut params: FnParseParams) -> Result<WindowParameter> {
@@ -711,9 +895,67 @@ impl PestParserImpl {
             Self::parse_sliding_window_clause,
             identity,
         )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::session_window_clause,
+            Self::parse_session_window_clause,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::fixed_rows_window_clause,
+            Self::parse_fixed_rows_window_clause,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::sliding_rows_window_clause,
+            Self::parse_sliding_rows_window_clause,
+            identity,
+        )?)
         .ok_or_else(|| SpringError::Sql(anyhow!("Failed to parse window clause: {}", params.sql)))
     }
 
+    fn parse_emit_clause(mut params: FnParseParams) -> Result<EmitPolicy> {
+        try_parse_child(
+            &mut params,
+            Rule::emit_final,
+            |_| Ok(EmitPolicy::Final),
+            identity,
+        )?
+        .or(try_parse_child(
+            &mut params,
+            Rule::emit_every,
+            Self::parse_emit_every,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::emit_on_each_row,
+            |_| Ok(EmitPolicy::OnEachRow),
+            identity,
+        )?)
+        .ok_or_else(|| SpringError::Sql(anyhow!("Failed to parse emit clause: {}", params.sql)))
+    }
+
+    fn parse_emit_every(mut params: FnParseParams) -> Result<EmitPolicy> {
+        let period = parse_child(
+            &mut params,
+            Rule::duration_constant,
+            Self::parse_duration_constant,
+            identity,
+        )?
+        .to_event_duration()?;
+
+        if period.as_std().is_zero() {
+            Err(SpringError::Sql(anyhow!(
+                "period of EMIT EVERY must be positive"
+            )))
+        } else {
+            Ok(EmitPolicy::Every(period))
+        }
+    }
+
     fn parse_fixed_window_clause(mut params: FnParseParams) -> Result<WindowParameter> {
         let length = parse_child(
             &mut params,
@@ -737,6 +979,26 @@ impl PestParserImpl {
         })
     }
 
+    fn parse_session_window_clause(mut params: FnParseParams) -> Result<WindowParameter> {
+        let gap = parse_child(
+            &mut params,
+            Rule::window_gap,
+            Self::parse_window_gap,
+            identity,
+        )?;
+        let gap = gap.to_event_duration()?;
+
+        let allowed_delay = parse_child(
+            &mut params,
+            Rule::allowed_delay,
+            Self::parse_allowed_delay,
+            identity,
+        )?;
+        let allowed_delay = allowed_delay.to_event_duration()?;
+
+        Ok(WindowParameter::SessionWindow { gap, allowed_delay })
+    }
+
     fn parse_sliding_window_clause(mut params: FnParseParams) -> Result<WindowParameter> {
         let length = parse_child(
             &mut params,
@@ -769,6 +1031,35 @@ impl PestParserImpl {
         })
     }
 
+    fn parse_fixed_rows_window_clause(mut params: FnParseParams) -> Result<WindowParameter> {
+        let length = parse_child(
+            &mut params,
+            Rule::window_rows_length,
+            Self::parse_window_rows,
+            identity,
+        )?;
+
+        Ok(WindowParameter::RowsFixedWindow { length })
+    }
+
+    fn parse_sliding_rows_window_clause(mut params: FnParseParams) -> Result<WindowParameter> {
+        let length = parse_child(
+            &mut params,
+            Rule::window_rows_length,
+            Self::parse_window_rows,
+            identity,
+        )?;
+
+        let period = parse_child(
+            &mut params,
+            Rule::window_rows_period,
+            Self::parse_window_rows,
+            identity,
+        )?;
+
+        Ok(WindowParameter::RowsSlidingWindow { length, period })
+    }
+
     fn parse_window_length(mut params: FnParseParams) -> Result<SqlValue> {
         parse_child(
             &mut params,
@@ -785,6 +1076,36 @@ impl PestParserImpl {
             identity,
         )
     }
+
+    fn parse_window_gap(mut params: FnParseParams) -> Result<SqlValue> {
+        parse_child(
+            &mut params,
+            Rule::duration_constant,
+            Self::parse_duration_constant,
+            identity,
+        )
+    }
+
+    /// Number of rows in count-based windows (must be positive).
+    fn parse_window_rows(mut params: FnParseParams) -> Result<u64> {
+        let n = parse_child(
+            &mut params,
+            Rule::integer_constant,
+            Self::parse_integer_constant,
+            identity,
+        )?
+        .to_i64()?;
+
+        if n > 0 {
+            Ok(n as u64)
+        } else {
+            Err(SpringError::Sql(anyhow!(
+                "number of rows in ROWS WINDOW must be positive but got {}",
+                n
+            )))
+        }
+    }
+
     fn parse_allowed_delay(mut params: FnParseParams) -> Result<SqlValue> {
         parse_child(
             &mut params,
@@ -809,55 +1130,199 @@ impl PestParserImpl {
         )
     }
 
-    fn parse_value_expr(mut params: FnParseParams) -> Result<ValueExpr> {
-        let expr = parse_child(
-            &mut params,
-            Rule::sub_value_expr,
-            Self::parse_sub_value_expr,
-            identity,
-        )?;
+    /// Operator precedence is resolved by `PRATT_PARSER`.
+    fn parse_value_expr(params: FnParseParams) -> Result<ValueExpr> {
+        let sql = params.sql;
+        PRATT_PARSER
+            .map_primary(|primary| Self::parse_sub_value_expr(pair_params(sql, primary)))
+            .map_prefix(|op, expr| {
+                let uni_op = Self::parse_unary_operator(pair_params(sql, op))?;
+                Ok(ValueExpr::UnaryOperator(uni_op, Box::new(expr?)))
+            })
+            .map_postfix(|expr, op| {
+                let target = Box::new(expr?);
+                match op.as_rule() {
+                    Rule::is_null_operator => {
+                        Ok(ValueExpr::FunctionCall(FunctionCall::IsNull { target }))
+                    }
+                    Rule::is_not_null_operator => {
+                        Ok(ValueExpr::FunctionCall(FunctionCall::IsNotNull { target }))
+                    }
+                    Rule::in_operator | Rule::not_in_operator => {
+                        let negated = op.as_rule() == Rule::not_in_operator;
+                        let mut params = pair_params(sql, op);
+                        let items = parse_child_seq(
+                            &mut params,
+                            Rule::value_expr,
+                            &Self::parse_value_expr,
+                            &identity,
+                        )?;
+                        let in_list = ValueExpr::FunctionCall(FunctionCall::InList {
+                            target,
+                            list: Self::in_list(items)?,
+                        });
+                        Ok(Self::negate_if(negated, in_list))
+                    }
+                    Rule::between_operator | Rule::not_between_operator => {
+                        let negated = op.as_rule() == Rule::not_between_operator;
+                        let mut params = pair_params(sql, op);
+                        let low = parse_child(
+                            &mut params,
+                            Rule::between_operand,
+                            Self::parse_value_expr,
+                            identity,
+                        )?;
+                        let high = parse_child(
+                            &mut params,
+                            Rule::between_operand,
+                            Self::parse_value_expr,
+                            identity,
+                        )?;
+                        let between = ValueExpr::FunctionCall(FunctionCall::Between {
+                            target,
+                            low: Box::new(low),
+                            high: Box::new(high),
+                        });
+                        Ok(Self::negate_if(negated, between))
+                    }
+                    _ => Err(SpringError::Sql(anyhow!(
+                        "Does not match any child rule of postfix_operator.",
+                    ))),
+                }
+            })
+            .map_infix(|left, op, right| {
+                let bin_op = Self::parse_binary_operator(pair_params(sql, op))?;
+                Ok(Self::binary_expr(bin_op, left?, right?))
+            })
+            .parse(params.children_pairs.into_iter())
+    }
 
-        if let Some(bin_op) = try_parse_child(
-            &mut params,
-            Rule::binary_operator,
-            Self::parse_binary_operator,
-            identity,
-        )? {
-            let right_expr = parse_child(
-                &mut params,
-                Rule::value_expr,
-                Self::parse_value_expr,
-                identity,
-            )?;
+    /// Items are looked up by hash when all of them are constants.
+    fn in_list(items: Vec<ValueExpr>) -> Result<InList<ValueExpr>> {
+        let constants = items
+            .iter()
+            .map(|item| match item {
+                ValueExpr::Constant(value) => Ok(Some(value.clone())),
+                ValueExpr::UnaryOperator(UnaryOperator::Minus, child) => match child.as_ref() {
+                    ValueExpr::Constant(SqlValue::NotNull(nn_sql_value)) => {
+                        Ok(Some(SqlValue::NotNull(nn_sql_value.clone().negate()?)))
+                    }
+                    _ => Ok(None),
+                },
+                _ => Ok(None),
+            })
+            .collect::<Result<Option<Vec<_>>>>()?;
 
-            match bin_op {
-                BinaryOperator::Equal => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::EqualVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-                BinaryOperator::Add => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::NumericalFunctionVariant(NumericalFunction::AddVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-                BinaryOperator::Mul => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::NumericalFunctionVariant(NumericalFunction::MulVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-                BinaryOperator::And => Ok(ValueExpr::BinaryExpr(
-                    BinaryExpr::LogicalFunctionVariant(LogicalFunction::AndVariant {
-                        left: Box::new(expr),
-                        right: Box::new(right_expr),
-                    }),
-                )),
-            }
+        Ok(match constants {
+            Some(constants) => InList::Constants(ConstantSet::new(constants)),
+            None => InList::Exprs(items),
+        })
+    }
+
+    fn negate_if(negated: bool, expr: ValueExpr) -> ValueExpr {
+        if negated {
+            ValueExpr::UnaryOperator(UnaryOperator::Not, Box::new(expr))
         } else {
-            Ok(expr)
+            expr
+        }
+    }
+
+    fn binary_expr(bin_op: BinaryOperator, left: ValueExpr, right: ValueExpr) -> ValueExpr {
+        match bin_op {
+            BinaryOperator::Equal => ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                ComparisonFunction::EqualVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::NotEqual => ValueExpr::BinaryExpr(
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::NotEqualVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }),
+            ),
+            BinaryOperator::LessThan => ValueExpr::BinaryExpr(
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::LessThanVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }),
+            ),
+            BinaryOperator::LessThanOrEqual => ValueExpr::BinaryExpr(
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::LessThanOrEqualVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }),
+            ),
+            BinaryOperator::GreaterThan => ValueExpr::BinaryExpr(
+                BinaryExpr::ComparisonFunctionVariant(ComparisonFunction::GreaterThanVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                }),
+            ),
+            BinaryOperator::GreaterThanOrEqual => {
+                ValueExpr::BinaryExpr(BinaryExpr::ComparisonFunctionVariant(
+                    ComparisonFunction::GreaterThanOrEqualVariant {
+                        left: Box::new(left),
+                        right: Box::new(right),
+                    },
+                ))
+            }
+            BinaryOperator::Add => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::AddVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Sub => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::SubVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Mul => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::MulVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Div => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::DivVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Mod => ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+                NumericalFunction::ModVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::And => ValueExpr::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+                LogicalFunction::AndVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Or => ValueExpr::BinaryExpr(BinaryExpr::LogicalFunctionVariant(
+                LogicalFunction::OrVariant {
+                    left: Box::new(left),
+                    right: Box::new(right),
+                },
+            )),
+            BinaryOperator::Like => ValueExpr::FunctionCall(FunctionCall::Like {
+                target: Box::new(left),
+                pattern: Box::new(right),
+            }),
+            BinaryOperator::NotLike => ValueExpr::UnaryOperator(
+                UnaryOperator::Not,
+                Box::new(ValueExpr::FunctionCall(FunctionCall::Like {
+                    target: Box::new(left),
+                    pattern: Box::new(right),
+                })),
+            ),
+            BinaryOperator::Concat => ValueExpr::FunctionCall(FunctionCall::Concat {
+                args: vec![left, right],
+            }),
         }
     }
 
@@ -868,40 +1333,227 @@ impl PestParserImpl {
             Self::parse_constant,
             ValueExpr::Constant,
         )?
+        .or(try_parse_child(
+            &mut params,
+            Rule::case_expr,
+            Self::parse_case_expr,
+            ValueExpr::CaseExpr,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::cast_expr,
+            Self::parse_cast_expr,
+            ValueExpr::FunctionCall,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::substring_expr,
+            Self::parse_substring_expr,
+            ValueExpr::FunctionCall,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::trim_expr,
+            Self::parse_trim_expr,
+            ValueExpr::FunctionCall,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::position_expr,
+            Self::parse_position_expr,
+            ValueExpr::FunctionCall,
+        )?)
         .or(try_parse_child(
             &mut params,
             Rule::column_reference,
             Self::parse_column_reference,
             ValueExpr::ColumnReference,
         )?)
-        .or({
-            if let Some(uni_op) = try_parse_child(
-                &mut params,
-                Rule::unary_operator,
-                Self::parse_unary_operator,
-                identity,
-            )? {
-                Some(parse_child(
-                    &mut params,
-                    Rule::value_expr,
-                    Self::parse_value_expr,
-                    |expr| ValueExpr::UnaryOperator(uni_op.clone(), Box::new(expr)),
-                )?)
-            } else {
-                None
-            }
-        })
         .or(try_parse_child(
             &mut params,
             Rule::function_call,
             Self::parse_function_call,
             ValueExpr::FunctionCall,
         )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::aggr_expr,
+            Self::parse_aggr_expr,
+            |aggr_expr| ValueExpr::AggrExpr(Box::new(aggr_expr)),
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )?)
+        .or(try_parse_child(
+            &mut params,
+            Rule::alias_reference,
+            Self::parse_alias_reference,
+            ValueExpr::AliasRef,
+        )?)
         .ok_or_else(|| {
             SpringError::Sql(anyhow!("Does not match any child rule of sub_value_expr.",))
         })
     }
 
+    /*
+     * ----------------------------------------------------------------------------
+     * CASE Expression
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_case_expr(mut params: FnParseParams) -> Result<CaseExpr<ValueExpr>> {
+        let operand = try_parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            Box::new,
+        )?;
+        let when_thens = parse_child_seq(
+            &mut params,
+            Rule::when_clause,
+            &Self::parse_when_clause,
+            &identity,
+        )?;
+        let else_result = try_parse_child(
+            &mut params,
+            Rule::else_clause,
+            Self::parse_else_clause,
+            Box::new,
+        )?;
+        Ok(CaseExpr {
+            operand,
+            when_thens,
+            else_result,
+        })
+    }
+
+    fn parse_when_clause(mut params: FnParseParams) -> Result<(ValueExpr, ValueExpr)> {
+        let when = parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )?;
+        let then = parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )?;
+        Ok((when, then))
+    }
+
+    fn parse_else_clause(mut params: FnParseParams) -> Result<ValueExpr> {
+        parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * CAST Expression
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_cast_expr(mut params: FnParseParams) -> Result<FunctionCall<ValueExpr>> {
+        let target = parse_child(
+            &mut params,
+            Rule::value_expr,
+            Self::parse_value_expr,
+            identity,
+        )?;
+        let sql_type = parse_child(
+            &mut params,
+            Rule::data_type,
+            Self::parse_data_type,
+            identity,
+        )?;
+        Ok(FunctionCall::Cast {
+            target: Box::new(target),
+            sql_type,
+        })
+    }
+
+    /*
+     * ----------------------------------------------------------------------------
+     * String Function Expressions
+     * ----------------------------------------------------------------------------
+     */
+
+    fn parse_substring_expr(mut params: FnParseParams) -> Result<FunctionCall<ValueExpr>> {
+        let mut args = parse_child_seq(
+            &mut params,
+            Rule::value_expr,
+            &Self::parse_value_expr,
+            &identity,
+        )?
+        .into_iter()
+        .map(Box::new);
+        let target = args.next().expect("grammar ensures target");
+        let start = args.next().expect("grammar ensures start");
+        let length = args.next();
+        Ok(FunctionCall::Substring {
+            target,
+            start,
+            length,
+        })
+    }
+
+    fn parse_trim_expr(mut params: FnParseParams) -> Result<FunctionCall<ValueExpr>> {
+        let trim_side = try_parse_child(
+            &mut params,
+            Rule::trim_side,
+            Self::parse_trim_side,
+            identity,
+        )?
+        .unwrap_or(TrimSide::Both);
+        let mut args = parse_child_seq(
+            &mut params,
+            Rule::value_expr,
+            &Self::parse_value_expr,
+            &identity,
+        )?;
+        let target = Box::new(args.pop().expect("grammar ensures target"));
+        let chars = args.pop().map(Box::new);
+        Ok(FunctionCall::Trim {
+            target,
+            chars,
+            trim_side,
+        })
+    }
+
+    fn parse_trim_side(mut params: FnParseParams) -> Result<TrimSide> {
+        let s = self_as_str(&mut params);
+        match s.to_lowercase().as_str() {
+            "leading" => Ok(TrimSide::Leading),
+            "trailing" => Ok(TrimSide::Trailing),
+            "both" => Ok(TrimSide::Both),
+            _ => Err(SpringError::Sql(anyhow!(
+                "Does not match any child rule of trim_side.",
+            ))),
+        }
+    }
+
+    fn parse_position_expr(mut params: FnParseParams) -> Result<FunctionCall<ValueExpr>> {
+        let mut args = parse_child_seq(
+            &mut params,
+            Rule::value_expr,
+            &Self::parse_value_expr,
+            &identity,
+        )?
+        .into_iter()
+        .map(Box::new);
+        let substring = args.next().expect("grammar ensures substring");
+        let target = args.next().expect("grammar ensures target");
+        Ok(FunctionCall::Position { substring, target })
+    }
+
     /*
      * ----------------------------------------------------------------------------
      * Column Reference
@@ -989,7 +1641,200 @@ impl PestParserImpl {
                     )))
                 }
             }
-            "floor" => unimplemented!(),
+            "coalesce" => Ok(FunctionCall::Coalesce { args: parameters }),
+            "upper" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Upper {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "upper() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "lower" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Lower {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "lower() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "length" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Length {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "length() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "concat" => Ok(FunctionCall::Concat { args: parameters }),
+            "replace" => {
+                if parameters.len() == 3 {
+                    Ok(FunctionCall::Replace {
+                        target: Box::new(parameters[0].clone()),
+                        from: Box::new(parameters[1].clone()),
+                        to: Box::new(parameters[2].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "replace() takes exactly three parameters (target, from, to)."
+                    )))
+                }
+            }
+            "nullif" => {
+                if parameters.len() == 2 {
+                    Ok(FunctionCall::NullIf {
+                        left: Box::new(parameters[0].clone()),
+                        right: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "nullif() takes exactly two parameters (left, right)."
+                    )))
+                }
+            }
+            "abs" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Abs {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "abs() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "ceil" | "ceiling" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Ceil {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "ceil() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "floor" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Floor {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "floor() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "round" => match parameters.len() {
+                1 | 2 => Ok(FunctionCall::Round {
+                    target: Box::new(parameters[0].clone()),
+                    digits: parameters.get(1).cloned().map(Box::new),
+                }),
+                _ => Err(SpringError::Sql(anyhow!(
+                    "round() takes one or two parameters (target[, digits])."
+                ))),
+            },
+            "sqrt" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Sqrt {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "sqrt() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "power" => {
+                if parameters.len() == 2 {
+                    Ok(FunctionCall::Power {
+                        base: Box::new(parameters[0].clone()),
+                        exponent: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "power() takes exactly two parameters (base, exponent)."
+                    )))
+                }
+            }
+            "ln" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Ln {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "ln() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "log10" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Log10 {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "log10() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "exp" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Exp {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "exp() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "sin" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Sin {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "sin() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "cos" => {
+                if parameters.len() == 1 {
+                    Ok(FunctionCall::Cos {
+                        target: Box::new(parameters[0].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "cos() takes exactly one parameter (target)."
+                    )))
+                }
+            }
+            "atan2" => {
+                if parameters.len() == 2 {
+                    Ok(FunctionCall::Atan2 {
+                        y: Box::new(parameters[0].clone()),
+                        x: Box::new(parameters[1].clone()),
+                    })
+                } else {
+                    Err(SpringError::Sql(anyhow!(
+                        "atan2() takes exactly two parameters (y, x)."
+                    )))
+                }
+            }
+            "greatest" => Ok(FunctionCall::Greatest { args: parameters }),
+            "least" => Ok(FunctionCall::Least { args: parameters }),
             _ => Err(SpringError::Sql(anyhow!(
                 "unknown function {}",
                 function_name.to_lowercase()
@@ -1008,29 +1853,123 @@ impl PestParserImpl {
      */
 
     fn parse_aggr_expr(mut params: FnParseParams) -> Result<AggrExpr> {
-        let func = parse_child(
+        let name = parse_child(
             &mut params,
             Rule::aggregate_name,
-            Self::parse_aggregate_name,
+            |mut params| Ok(self_as_str(&mut params).to_lowercase()),
             identity,
         )?;
-        let aggregated = parse_child(
+
+        let count_star = try_parse_child(&mut params, Rule::count_star, |_| Ok(()), identity)?;
+        let distinct =
+            try_parse_child(&mut params, Rule::distinct, |_| Ok(()), identity)?.is_some();
+        let aggregated = if count_star.is_some() {
+            if name != "count" {
+                return Err(SpringError::Sql(anyhow!(
+                    "`*` is only allowed in COUNT but got {}",
+                    name
+                )));
+            }
+            // COUNT(*) counts rows as COUNT(1) does
+            ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::SmallInt(1)))
+        } else {
+            parse_child(
+                &mut params,
+                Rule::value_expr,
+                &Self::parse_value_expr,
+                &identity,
+            )?
+        };
+
+        let argument = try_parse_child(
             &mut params,
             Rule::value_expr,
-            &Self::parse_value_expr,
-            &identity,
+            Self::parse_value_expr,
+            identity,
         )?;
-        Ok(AggrExpr { func, aggregated })
+
+        let (func, by) = Self::aggregate_function(&name, distinct, argument)?;
+        Ok(AggrExpr {
+            func,
+            aggregated,
+            by,
+        })
     }
 
-    fn parse_aggregate_name(mut params: FnParseParams) -> Result<AggregateFunctionParameter> {
-        let s = self_as_str(&mut params);
-        match s.to_lowercase().as_str() {
-            "avg" => Ok(AggregateFunctionParameter::Avg),
-            _ => Err(SpringError::Sql(anyhow!(
-                "unknown aggregate function {}",
-                s.to_lowercase()
-            ))),
+    /// `distinct` is only allowed in COUNT.
+    /// `argument` is the second argument of an aggregate function (PERCENTILE_APPROX, ARG_MIN and ARG_MAX take one).
+    ///
+    /// Returns the aggregate function and the `by` argument of ARG_MIN / ARG_MAX.
+    fn aggregate_function(
+        name: &str,
+        distinct: bool,
+        argument: Option<ValueExpr>,
+    ) -> Result<(AggregateFunctionParameter, Option<ValueExpr>)> {
+        if distinct && name != "count" {
+            return Err(SpringError::Sql(anyhow!(
+                "DISTINCT is only allowed in COUNT but got {}",
+                name
+            )));
+        }
+
+        let func = match name {
+            "avg" => AggregateFunctionParameter::Avg,
+            "sum" => AggregateFunctionParameter::Sum,
+            "count" if distinct => AggregateFunctionParameter::CountDistinct,
+            "count" => AggregateFunctionParameter::Count,
+            "approx_count_distinct" => AggregateFunctionParameter::ApproxCountDistinct,
+            "min" => AggregateFunctionParameter::Min,
+            "max" => AggregateFunctionParameter::Max,
+            "first_value" => AggregateFunctionParameter::FirstValue,
+            "last_value" => AggregateFunctionParameter::LastValue,
+            "arg_min" | "arg_max" => {
+                let by = argument.ok_or_else(|| {
+                    SpringError::Sql(anyhow!("{} takes 2 arguments: (x, by)", name))
+                })?;
+                let func = if name == "arg_min" {
+                    AggregateFunctionParameter::ArgMin
+                } else {
+                    AggregateFunctionParameter::ArgMax
+                };
+                return Ok((func, Some(by)));
+            }
+            "stddev_pop" => AggregateFunctionParameter::StddevPop,
+            "stddev_samp" => AggregateFunctionParameter::StddevSamp,
+            "var_pop" => AggregateFunctionParameter::VarPop,
+            "var_samp" => AggregateFunctionParameter::VarSamp,
+            "percentile_approx" => {
+                let percentile = match argument {
+                    Some(ValueExpr::Constant(SqlValue::NotNull(v))) => v.to_f64_loose().ok(),
+                    _ => None,
+                }
+                .filter(|p| (0.0..=1.0).contains(p))
+                .ok_or_else(|| {
+                    SpringError::Sql(anyhow!(
+                        "PERCENTILE_APPROX takes a numeric constant in [0, 1] as its second argument"
+                    ))
+                })?;
+                return Ok((
+                    AggregateFunctionParameter::PercentileApprox {
+                        percentile: OrderedFloat(percentile),
+                    },
+                    None,
+                ));
+            }
+            _ => {
+                return Err(SpringError::Sql(anyhow!(
+                    "unknown aggregate function {}",
+                    name
+                )))
+            }
+        };
+
+        if argument.is_some() {
+            Err(SpringError::Sql(anyhow!(
+                "{} takes only one argument",
+                name
+            )))
+        } else {
+            Ok((func, None))
         }
     }
 
@@ -1104,13 +2043,14 @@ impl PestParserImpl {
 
     fn parse_integer_type(mut params: FnParseParams) -> Result<SqlType> {
         let s = self_as_str(&mut params);
+        let s = s.split_whitespace().collect::<Vec<_>>().join(" ");
         match s.to_ascii_uppercase().as_str() {
+            "SMALLINT" => Ok(SqlType::small_int()),
             "INTEGER" => Ok(SqlType::integer()),
+            "BIGINT" => Ok(SqlType::big_int()),
             "UNSIGNED INTEGER" => Ok(SqlType::unsigned_integer()),
-            x => {
-                eprintln!("Unexpected data type parsed: {}", x);
-                unreachable!();
-            }
+            "UNSIGNED BIGINT" => Ok(SqlType::unsigned_big_int()),
+            x => Err(SpringError::Sql(anyhow!("unsupported data type: {}", x))),
         }
     }
 
@@ -1270,6 +2210,15 @@ impl PestParserImpl {
         )
     }
 
+    fn parse_alias_reference(mut params: FnParseParams) -> Result<String> {
+        parse_child(
+            &mut params,
+            Rule::identifier,
+            Self::parse_identifier,
+            identity,
+        )
+    }
+
     fn parse_aggr_alias(mut params: FnParseParams) -> Result<AggrAlias> {
         parse_child(
             &mut params,
@@ -1406,3 +2355,54 @@ impl PestParserImpl {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_select_fields(select_stream: &str) -> Vec<SelectFieldSyntax> {
+        let sql = format!(
+            "CREATE PUMP pu AS INSERT INTO sink_1 (a) {};",
+            select_stream
+        );
+        match PestParserImpl.parse(sql).unwrap() {
+            ParseSuccess::CreatePump(create_pump) => create_pump.select_stream_syntax.fields,
+            parse_success => panic!("unexpected parse result: {:?}", parse_success),
+        }
+    }
+
+    #[test]
+    fn test_implicit_alias_starting_with_operator_keyword() {
+        for alias in ["origin", "android", "likes", "isnull_flag"] {
+            let fields = parse_select_fields(&format!("SELECT STREAM s.a {} FROM s", alias));
+
+            assert!(
+                matches!(
+                    &fields[..],
+                    [SelectFieldSyntax::ValueExpr {
+                        value_expr: ValueExpr::ColumnReference(_),
+                        alias: Some(value_alias),
+                    }] if value_alias == &ValueAlias::new(alias.to_string())
+                ),
+                "alias `{}` is parsed as: {:?}",
+                alias,
+                fields
+            );
+        }
+    }
+
+    #[test]
+    fn test_operator_keyword_followed_by_parenthesis() {
+        let fields = parse_select_fields(
+            "SELECT STREAM s.a OR(s.b), s.a AND(s.b), s.a LIKE('x%'), s.a IN(1) FROM s",
+        );
+        assert_eq!(fields.len(), 4);
+        assert!(fields.iter().all(|field| matches!(
+            field,
+            SelectFieldSyntax::ValueExpr {
+                value_expr: ValueExpr::BinaryExpr(..) | ValueExpr::FunctionCall(..),
+                alias: None,
+            }
+        )));
+    }
+}
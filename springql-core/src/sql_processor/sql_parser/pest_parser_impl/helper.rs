@@ -3,7 +3,7 @@
 use std::collections::VecDeque;
 
 use anyhow::Context;
-use pest::iterators::{Pair, Pairs};
+use pest::iterators::Pair;
 
 use crate::{
     api::error::{Result, SpringError},
@@ -26,6 +26,18 @@ pub struct FnParseParams<'a> {
     pub self_string: String,
 }
 
+/// Make parameters to parse `pair` by itself.
+///
+/// Used when pairs are consumed by something else than `parse_child()` families (e.g. Pratt parser).
+pub fn pair_params<'a>(sql: &'a str, pair: Pair<'a, Rule>) -> FnParseParams<'a> {
+    let self_string = pair.as_str().to_string();
+    FnParseParams {
+        sql,
+        children_pairs: pair.into_inner().collect(),
+        self_string,
+    }
+}
+
 /// Parse the next child term as `child_term` by `child_parser`.
 ///
 /// Returns Ok(None) when either of the following cases:
@@ -56,14 +68,7 @@ pub fn parse_child<T, ChildRet>(
         .map_err(SpringError::Sql)?;
 
     if child_pair.as_rule() == child_term {
-        let child_str = child_pair.as_str();
-        let grand_children_pairs: Pairs<Rule> = child_pair.into_inner();
-
-        let child_params = FnParseParams {
-            sql: params.sql,
-            children_pairs: grand_children_pairs.collect(),
-            self_string: child_str.to_string(),
-        };
+        let child_params = pair_params(params.sql, child_pair);
         let child_ast = child_parser(child_params)?;
 
         Ok(ret_closure(child_ast))
@@ -97,14 +102,7 @@ pub fn try_parse_child<T, ChildRet>(
 ) -> Result<Option<T>> {
     if let Some(child_pair) = params.children_pairs.pop_front() {
         if child_pair.as_rule() == child_term {
-            let child_str = child_pair.as_str();
-            let grand_children_pairs: Pairs<Rule> = child_pair.into_inner();
-
-            let child_params = FnParseParams {
-                sql: params.sql,
-                children_pairs: grand_children_pairs.collect(),
-                self_string: child_str.to_string(),
-            };
+            let child_params = pair_params(params.sql, child_pair);
             let child_ast = child_parser(child_params)?;
 
             Ok(Some(ret_closure(child_ast)))
@@ -2,7 +2,9 @@
 
 use crate::{
     expression::{AggrExpr, ValueExpr},
-    pipeline::{AggrAlias, CorrelationAlias, JoinType, StreamName, ValueAlias, WindowParameter},
+    pipeline::{
+        AggrAlias, CorrelationAlias, EmitPolicy, JoinType, StreamName, ValueAlias, WindowParameter,
+    },
 };
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -22,10 +24,19 @@ pub struct SelectStreamSyntax {
     pub fields: Vec<SelectFieldSyntax>,
     pub from_item: FromItemSyntax,
 
+    /// None when no WHERE clause is supplied.
+    pub where_condition: Option<ValueExpr>,
+
     /// Empty when no GROUP BY clause is supplied.
     pub grouping_elements: Vec<GroupingElementSyntax>,
 
+    /// None when no HAVING clause is supplied.
+    pub having_condition: Option<ValueExpr>,
+
     pub window_clause: Option<WindowParameter>,
+
+    /// None when no EMIT clause is supplied.
+    pub emit_clause: Option<EmitPolicy>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -38,14 +49,19 @@ pub enum SelectFieldSyntax {
         aggr_expr: AggrExpr,
         alias: Option<AggrAlias>,
     },
+    /// `*` (all the columns of all the streams in FROM clause) or `stream_name.*`.
+    ///
+    /// Expanded into column references by `SelectSyntaxAnalyzer`.
+    Wildcard { stream_name: Option<StreamName> },
 }
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum FromItemSyntax {
     StreamVariant(SubFromItemSyntax),
+    /// Left-deep join tree.
     JoinVariant {
-        left: SubFromItemSyntax,
-        right: Box<FromItemSyntax>,
+        left: Box<FromItemSyntax>,
+        right: SubFromItemSyntax,
 
         join_type: JoinType,
         on_expr: ValueExpr,
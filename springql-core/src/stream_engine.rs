@@ -17,9 +17,9 @@ pub use autonomous_executor::{NnSqlValue, RowTime, SqlCompareResult, SqlValue, S
 
 use crate::{
     api::{error::Result, SpringConfig, SpringError},
-    pipeline::{Pipeline, QueueName},
+    pipeline::{Pipeline, QueueName, StreamName, TableFileOptions},
     stream_engine::{
-        autonomous_executor::{AutonomousExecutor, SchemalessRow},
+        autonomous_executor::{read_table_file, AutonomousExecutor, SchemalessRow, Table},
         command::AlterPipelineCommand,
         in_memory_queue_repository::InMemoryQueueRepository,
         sql_executor::SqlExecutor,
@@ -76,10 +76,62 @@ impl StreamEngine {
 
     pub fn alter_pipeline(&mut self, command: AlterPipelineCommand) -> Result<()> {
         log::debug!("[StreamEngine] alter_pipeline({:?})", command);
+
+        // rows are loaded before altering pipeline not to leave a table without its file
+        let new_table = match &command {
+            AlterPipelineCommand::CreateTable(table_model) => {
+                let rows = match TableFileOptions::from_options(table_model.options())? {
+                    Some(file_options) => read_table_file(&file_options, table_model)?,
+                    None => vec![],
+                };
+                // no pump looks up a table before it is created
+                Some((
+                    table_model.name().clone(),
+                    Table::new(table_model, rows, &[])?,
+                ))
+            }
+            _ => None,
+        };
+        let table_keys = match &command {
+            AlterPipelineCommand::CreatePump(pump) => pump
+                .query_plan()
+                .lower_ops
+                .table_lookups
+                .iter()
+                .map(|table_lookup| (table_lookup.table.clone(), table_lookup.key_column.clone()))
+                .collect(),
+            _ => vec![],
+        };
+
         let pipeline = self.sql_executor.alter_pipeline(command)?;
+        if let Some((table_name, table)) = new_table {
+            self.autonomous_executor.replace_table(table_name, table);
+        }
+        // indexes are built before the new pump task starts to look up tables
+        for (table_name, key_column) in table_keys {
+            self.autonomous_executor
+                .index_table(&table_name, &key_column);
+        }
         self.autonomous_executor.notify_pipeline_update(pipeline)
     }
 
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - table named `table_name` does not exist.
+    ///   - a row does not match the shape of the table.
+    pub fn replace_table(
+        &mut self,
+        table_name: StreamName,
+        rows: Vec<SchemalessRow>,
+    ) -> Result<()> {
+        let table_model = self.current_pipeline().get_table(&table_name)?;
+        let key_columns = self.current_pipeline().table_key_columns(&table_name);
+        let table = Table::new(&table_model, rows, &key_columns)?;
+        self.autonomous_executor.replace_table(table_name, table);
+        Ok(())
+    }
+
     /// # Returns
     ///
     /// - `Ok(Some)` when at least a row is in the queue.
@@ -12,6 +12,7 @@ mod purger_worker;
 mod queue;
 mod repositories;
 mod row;
+mod table;
 mod task;
 mod task_executor;
 mod task_graph;
@@ -25,9 +26,11 @@ pub use row::{
     ColumnValues, JsonObject, NnSqlValue, RowTime, SchemalessRow, SourceRow, SqlCompareResult,
     SqlValue, SqlValueHashKey, StreamColumns, StreamRow,
 };
+pub use table::{read_table_file, Table};
 pub use task::{
-    NetClientSourceReader, NetServerSourceReader, SinkWriterRepository, SourceReader,
-    SourceReaderRepository, SourceTask, Task, TaskContext, Tuple, Window,
+    AggregatedAndGroupingValues, NetClientSourceReader, NetServerSourceReader,
+    SinkWriterRepository, SourceReader, SourceReaderRepository, SourceTask, Task, TaskContext,
+    Tuple, Window,
 };
 
 use std::sync::Arc;
@@ -37,7 +40,7 @@ use crate::{
         error::{Result, SpringError},
         SpringConfig,
     },
-    pipeline::Pipeline,
+    pipeline::{ColumnName, Pipeline, StreamName},
     stream_engine::autonomous_executor::{
         args::{Coordinators, EventQueues, Locks},
         event_queue::{BlockingEventQueue, Event, NonBlockingEventQueue},
@@ -61,6 +64,8 @@ use crate::{
 pub struct AutonomousExecutor {
     b_event_queue: Arc<BlockingEventQueue>,
 
+    repos: Arc<Repositories>,
+
     main_job_lock: Arc<MainJobLock>,
     task_executor: TaskExecutor,
 
@@ -109,7 +114,7 @@ impl AutonomousExecutor {
             locks.main_job_lock.clone(),
             event_queues.clone(),
             coordinators.clone(),
-            PurgerWorkerThreadArg::new(repos, locks.task_executor_lock.clone()),
+            PurgerWorkerThreadArg::new(repos.clone(), locks.task_executor_lock.clone()),
         );
 
         coordinators
@@ -119,6 +124,7 @@ impl AutonomousExecutor {
 
         Self {
             b_event_queue: event_queues.blocking,
+            repos,
             main_job_lock: locks.main_job_lock,
             task_executor,
             _memory_state_machine_worker: memory_state_machine_worker,
@@ -145,6 +151,18 @@ impl AutonomousExecutor {
         Ok(())
     }
 
+    /// Replaces all the rows of a table. Pump tasks look up the new rows from their next input.
+    pub fn replace_table(&self, table_name: StreamName, table: Table) {
+        self.repos.table_repository().replace(table_name, table)
+    }
+
+    /// Builds the hash index of `key_column` of a table (if not yet) to look up the table by the column.
+    pub fn index_table(&self, table_name: &StreamName, key_column: &ColumnName) {
+        self.repos
+            .table_repository()
+            .add_index(table_name, key_column)
+    }
+
     /// Workers in autonomous executor may get SpringError but it must continue their work.
     /// This method provides common way, like logging, to handle an error and then continue their work.
     fn handle_error(e: SpringError) {
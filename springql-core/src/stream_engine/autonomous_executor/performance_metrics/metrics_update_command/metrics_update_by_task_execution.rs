@@ -44,6 +44,11 @@ impl MetricsUpdateByTaskExecution {
         self.task_gain_bytes() as f32 / self.task_execution_time().as_secs_f32()
     }
 
+    /// Rows dropped by the window of this task as too late (whether or not they are routed by `LATE ROWS INTO`).
+    pub fn task_late_rows(&self) -> u64 {
+        self.task.late_rows
+    }
+
     pub fn row_queue_gain_rows(&self, id: &RowQueueId) -> i64 {
         self.queue_put_rows(&id.clone().into()) as i64 - self.row_queue_used_rows(id) as i64
     }
@@ -208,6 +213,8 @@ impl MetricsUpdateByTaskExecution {
 pub struct TaskMetricsUpdateByTask {
     task_id: TaskId,
     execution_time: WallClockDuration,
+    /// Rows dropped by windows as too late (0 for non-window tasks).
+    late_rows: u64,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -13,12 +13,19 @@ use crate::stream_engine::autonomous_executor::performance_metrics::PerformanceM
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub struct PerformanceMetricsSummary {
     pub queue_total_bytes: u64,
+
+    /// Total number of rows dropped by windows as too late.
+    pub late_rows: u64,
 }
 
 impl From<&PerformanceMetrics> for PerformanceMetricsSummary {
     fn from(pm: &PerformanceMetrics) -> Self {
         let queue_total_bytes = Self::queue_total_bytes(pm);
-        Self { queue_total_bytes }
+        let late_rows = Self::late_rows(pm);
+        Self {
+            queue_total_bytes,
+            late_rows,
+        }
     }
 }
 
@@ -42,4 +49,10 @@ impl PerformanceMetricsSummary {
 
         window + row
     }
+
+    fn late_rows(pm: &PerformanceMetrics) -> u64 {
+        pm.get_tasks()
+            .iter()
+            .fold(0, |acc, (_, met)| acc + met.late_rows())
+    }
 }
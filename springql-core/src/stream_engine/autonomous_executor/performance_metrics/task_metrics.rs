@@ -9,6 +9,7 @@ use crate::stream_engine::autonomous_executor::performance_metrics::{
 pub struct TaskMetrics {
     avg_gain_bytes_per_sec: f32,
     n_executions: u64,
+    late_rows: u64,
 }
 
 impl TaskMetrics {
@@ -21,9 +22,15 @@ impl TaskMetrics {
             n,
             command.task_gain_bytes_per_sec(),
         );
+        self.late_rows += command.task_late_rows();
     }
 
     pub fn avg_gain_bytes_per_sec(&self) -> f32 {
         self.avg_gain_bytes_per_sec
     }
+
+    /// Total number of rows dropped by the task's window as too late.
+    pub fn late_rows(&self) -> u64 {
+        self.late_rows
+    }
 }
@@ -35,8 +35,8 @@ impl TaskRepository {
     pub fn purge_windows(&self) {
         for task in self.repo.values() {
             if let Task::Pump(pump_task) = task.as_ref() {
-                if let Some(mut w) = pump_task.get_join_window_mut() {
-                    w.borrow_mut().purge()
+                if let Some(mut windows) = pump_task.get_join_windows_mut() {
+                    windows.iter_mut().for_each(|w| w.purge())
                 } else if let Some(mut w) = pump_task.get_aggr_window_mut() {
                     w.borrow_mut().purge()
                 } else {
@@ -4,6 +4,7 @@ use crate::{
     api::SpringConfig,
     stream_engine::autonomous_executor::{
         queue::{RowQueueRepository, WindowQueueRepository},
+        table::TableRepository,
         task::{SinkWriterRepository, SourceReaderRepository},
     },
 };
@@ -15,6 +16,7 @@ pub struct Repositories {
     window_queue_repository: WindowQueueRepository,
     source_reader_repository: SourceReaderRepository,
     sink_writer_repository: SinkWriterRepository,
+    table_repository: TableRepository,
 }
 
 impl Repositories {
@@ -24,6 +26,7 @@ impl Repositories {
             window_queue_repository: WindowQueueRepository::default(),
             source_reader_repository: SourceReaderRepository::new(config.source_reader),
             sink_writer_repository: SinkWriterRepository::new(config.sink_writer),
+            table_repository: TableRepository::default(),
         }
     }
 
@@ -42,4 +45,8 @@ impl Repositories {
     pub fn sink_writer_repository(&self) -> &SinkWriterRepository {
         &self.sink_writer_repository
     }
+
+    pub fn table_repository(&self) -> &TableRepository {
+        &self.table_repository
+    }
 }
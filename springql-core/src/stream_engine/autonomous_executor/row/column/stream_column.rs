@@ -35,11 +35,12 @@ impl MemSize for StreamColumns {
 
 impl StreamColumns {
     /// Value may be type-casted to stream definition if possible.
+    /// Nullable columns lacking in `column_values` are filled with NULL.
     ///
     /// # Failure
     ///
     /// - `SpringError::Sql` when:
-    ///   - `column_values` lacks any of `stream.columns()`.
+    ///   - `column_values` lacks any of NOT NULL columns in `stream.columns()`.
     ///   - Type mismatch (and failed to convert type) with `stream_shape` and `column_values`.
     pub fn new(stream_model: Arc<StreamModel>, mut column_values: ColumnValues) -> Result<Self> {
         let values = stream_model
@@ -47,7 +48,12 @@ impl StreamColumns {
             .columns()
             .iter()
             .map(|coldef| {
-                let value = column_values.remove(coldef.column_data_type().column_name())?;
+                let cdt = coldef.column_data_type();
+                let value = match column_values.remove(cdt.column_name()) {
+                    Ok(value) => value,
+                    Err(_) if cdt.nullable() => SqlValue::Null,
+                    Err(e) => return Err(e),
+                };
                 Self::validate_or_try_convert_value_type(value, coldef)
             })
             .collect::<Result<Vec<SqlValue>>>()?;
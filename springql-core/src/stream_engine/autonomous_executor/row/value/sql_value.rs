@@ -11,7 +11,7 @@ pub use sql_value_hash_key::SqlValueHashKey;
 use std::{
     fmt::Display,
     hash::Hash,
-    ops::{Add, Mul},
+    ops::{Add, Div, Mul, Rem, Sub},
 };
 
 use anyhow::anyhow;
@@ -146,6 +146,26 @@ impl SqlValue {
         }
     }
 
+    /// Eval as bool if possible, keeping NULL as `None` (for three-valued logic).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - this SqlValue cannot be evaluated as SQL BOOLEAN
+    pub fn to_nullable_bool(&self) -> Result<Option<bool>> {
+        match self {
+            SqlValue::Null => Ok(None),
+            SqlValue::NotNull(_) => self.to_bool().map(Some),
+        }
+    }
+
+    /// SQL BOOLEAN from three-valued logic result. `None` is NULL.
+    pub fn from_nullable_bool(b: Option<bool>) -> Self {
+        b.map_or(SqlValue::Null, |b| {
+            SqlValue::NotNull(NnSqlValue::Boolean(b))
+        })
+    }
+
     /// Eval as i64 if possible.
     ///
     /// # Failures
@@ -243,6 +263,43 @@ impl Mul for SqlValue {
     }
 }
 
+impl Sub for SqlValue {
+    type Output = Result<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (SqlValue::Null, _) | (_, SqlValue::Null) => Ok(SqlValue::Null),
+            (SqlValue::NotNull(lhs_nn), SqlValue::NotNull(rhs_nn)) => {
+                (lhs_nn - rhs_nn).map(SqlValue::NotNull)
+            }
+        }
+    }
+}
+impl Div for SqlValue {
+    type Output = Result<Self>;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (SqlValue::Null, _) | (_, SqlValue::Null) => Ok(SqlValue::Null),
+            (SqlValue::NotNull(lhs_nn), SqlValue::NotNull(rhs_nn)) => {
+                (lhs_nn / rhs_nn).map(SqlValue::NotNull)
+            }
+        }
+    }
+}
+impl Rem for SqlValue {
+    type Output = Result<Self>;
+
+    fn rem(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            (SqlValue::Null, _) | (_, SqlValue::Null) => Ok(SqlValue::Null),
+            (SqlValue::NotNull(lhs_nn), SqlValue::NotNull(rhs_nn)) => {
+                (lhs_nn % rhs_nn).map(SqlValue::NotNull)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 impl SqlValue {
     pub fn unwrap(self) -> NnSqlValue {
@@ -259,6 +316,7 @@ mod tests {
     use std::collections::HashSet;
 
     use crate::stream_engine::autonomous_executor::row::value::sql_value::sql_value_hash_key::SqlValueHashKey;
+    use crate::stream_engine::time::SpringDuration;
 
     use super::*;
 
@@ -347,4 +405,58 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_sql_compare_across_numeric_types() -> Result<()> {
+        let v_integer = SqlValue::NotNull(NnSqlValue::Integer(42));
+        let v_unsigned = SqlValue::NotNull(NnSqlValue::UnsignedBigInt(u64::MAX));
+        let v_float = SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(42.0)));
+        let v_float_half = SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(42.5)));
+
+        assert!(matches!(
+            v_integer.sql_compare(&v_unsigned)?,
+            SqlCompareResult::LessThan
+        ));
+        assert!(matches!(
+            v_integer.sql_compare(&v_float)?,
+            SqlCompareResult::Eq
+        ));
+        assert!(matches!(
+            v_float_half.sql_compare(&v_integer)?,
+            SqlCompareResult::GreaterThan
+        ));
+
+        assert_eq!(
+            SqlValueHashKey::from(&v_integer),
+            SqlValueHashKey::from(&v_float),
+            "equal values generate the same hash value"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sql_compare_duration() -> Result<()> {
+        let v_1sec = SqlValue::NotNull(NnSqlValue::Duration(SpringEventDuration::from_secs(1)));
+        let v_1000ms =
+            SqlValue::NotNull(NnSqlValue::Duration(SpringEventDuration::from_millis(1000)));
+        let v_2sec = SqlValue::NotNull(NnSqlValue::Duration(SpringEventDuration::from_secs(2)));
+
+        assert!(matches!(
+            v_1sec.sql_compare(&v_1000ms)?,
+            SqlCompareResult::Eq
+        ));
+        assert!(matches!(
+            v_1sec.sql_compare(&v_2sec)?,
+            SqlCompareResult::LessThan
+        ));
+        assert!(matches!(
+            v_1sec
+                .sql_compare(&SqlValue::NotNull(NnSqlValue::Integer(1)))
+                .expect_err("DURATION and INTEGER are not comparable"),
+            SpringError::Sql(_),
+        ));
+
+        Ok(())
+    }
 }
@@ -4,7 +4,7 @@ use std::{
     fmt::Display,
     hash::Hash,
     mem::size_of,
-    ops::{Add, Mul},
+    ops::{Add, Div, Mul, Rem, Sub},
 };
 
 use anyhow::anyhow;
@@ -21,9 +21,10 @@ use crate::{
         autonomous_executor::row::value::{
             sql_convertible::ToNnSqlValue, sql_value::sql_compare_result::SqlCompareResult,
         },
-        time::{SpringEventDuration, SpringTimestamp},
+        time::{SpringDuration, SpringEventDuration, SpringTimestamp},
         SpringValue,
     },
+    time::Duration,
 };
 
 /// NOT NULL value.
@@ -145,7 +146,21 @@ impl Hash for NnSqlValue {
                 u.hash(state);
             },
             |f: OrderedFloat<f32>| {
-                f.hash(state);
+                // integral floats must be hashed as integers since `1.0 FLOAT` equals to `1 INTEGER`.
+                if f.fract() == 0.0
+                    && f.into_inner() >= i64::MIN as f32
+                    && f.into_inner() < i64::MAX as f32
+                {
+                    (f.into_inner() as i64).hash(state);
+                } else if f.fract() == 0.0
+                    && f.into_inner() >= 0.0
+                    && f.into_inner() < u64::MAX as f32
+                {
+                    // beyond i64 range but still representable as `UNSIGNED BIGINT`.
+                    (f.into_inner() as u64).hash(state);
+                } else {
+                    f.hash(state);
+                }
             },
             |s: String| {
                 s.hash(state);
@@ -269,6 +284,165 @@ impl NnSqlValue {
         }
     }
 
+    /// Converts value into `typ` following `CAST` rules.
+    ///
+    /// - Numeric types into each other (FLOAT into integer types is rounded to the nearest).
+    /// - TEXT into numeric types, TIMESTAMP and BOOLEAN by parsing.
+    /// - BLOB into TEXT as UTF-8.
+    /// - Any other type into TEXT.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Conversion from this value's type into `typ` is undefined.
+    ///   - Value is out of range of `typ`.
+    ///   - TEXT value cannot be parsed as `typ`.
+    ///   - BLOB value is not valid UTF-8.
+    pub fn try_cast(&self, typ: &SqlType) -> Result<NnSqlValue> {
+        if &self.sql_type() == typ {
+            return Ok(self.clone());
+        }
+
+        match (self, typ) {
+            (NnSqlValue::Text(s), SqlType::NumericComparable(n)) => {
+                let s = s.trim();
+                let parsed = match n {
+                    NumericComparableType::I64Loose(_) => s.parse::<i64>().map(Self::BigInt).ok(),
+                    NumericComparableType::U64Loose(_) => {
+                        s.parse::<u64>().map(Self::UnsignedBigInt).ok()
+                    }
+                    NumericComparableType::F32Loose(_) => {
+                        s.parse::<f32>().map(|f| Self::Float(OrderedFloat(f))).ok()
+                    }
+                };
+                parsed
+                    .ok_or_else(|| cast_err(self, typ, "invalid number"))?
+                    .try_cast(typ)
+            }
+            (NnSqlValue::Text(s), SqlType::TimestampComparable) => s
+                .trim()
+                .parse::<SpringTimestamp>()
+                .map(Self::Timestamp)
+                .map_err(|_| cast_err(self, typ, "invalid timestamp")),
+            (NnSqlValue::Text(s), SqlType::BooleanComparable) => {
+                match s.trim().to_ascii_lowercase().as_str() {
+                    "true" => Ok(Self::Boolean(true)),
+                    "false" => Ok(Self::Boolean(false)),
+                    _ => Err(cast_err(self, typ, "invalid boolean")),
+                }
+            }
+            (NnSqlValue::Blob(v), SqlType::StringComparableLoose(_)) => {
+                String::from_utf8(v.clone())
+                    .map(Self::Text)
+                    .map_err(|_| cast_err(self, typ, "invalid UTF-8 sequence"))
+            }
+            (NnSqlValue::Text(_), _) | (NnSqlValue::Blob(_), _) => {
+                Err(cast_err(self, typ, "undefined conversion"))
+            }
+            (_, SqlType::StringComparableLoose(_)) => Ok(Self::Text(self.to_string())),
+            (_, SqlType::NumericComparable(n)) => self.cast_numeric(n, typ),
+            (_, _) => Err(cast_err(self, typ, "undefined conversion")),
+        }
+    }
+
+    fn cast_numeric(&self, n: &NumericComparableType, typ: &SqlType) -> Result<NnSqlValue> {
+        let out_of_range = || cast_err(self, typ, "out of range");
+
+        match n {
+            NumericComparableType::I64Loose(i) => {
+                let v = match self {
+                    NnSqlValue::SmallInt(_) | NnSqlValue::Integer(_) | NnSqlValue::BigInt(_) => {
+                        self.unpack::<i64>()?
+                    }
+                    NnSqlValue::UnsignedInteger(_) | NnSqlValue::UnsignedBigInt(_) => {
+                        i64::try_from(self.unpack::<u64>()?).map_err(|_| out_of_range())?
+                    }
+                    NnSqlValue::Float(f) => {
+                        let f = f.round();
+                        if f.is_finite() && f >= i64::MIN as f32 && f < i64::MAX as f32 {
+                            f as i64
+                        } else {
+                            return Err(out_of_range());
+                        }
+                    }
+                    _ => return Err(cast_err(self, typ, "undefined conversion")),
+                };
+                match i {
+                    I64LooseType::SmallInt => i16::try_from(v).map(Self::SmallInt),
+                    I64LooseType::Integer => i32::try_from(v).map(Self::Integer),
+                    I64LooseType::BigInt => Ok(Self::BigInt(v)),
+                }
+                .map_err(|_| out_of_range())
+            }
+            NumericComparableType::U64Loose(u) => {
+                let v = match self {
+                    NnSqlValue::SmallInt(_) | NnSqlValue::Integer(_) | NnSqlValue::BigInt(_) => {
+                        u64::try_from(self.unpack::<i64>()?).map_err(|_| out_of_range())?
+                    }
+                    NnSqlValue::UnsignedInteger(_) | NnSqlValue::UnsignedBigInt(_) => {
+                        self.unpack::<u64>()?
+                    }
+                    NnSqlValue::Float(f) => {
+                        let f = f.round();
+                        if f.is_finite() && f >= 0.0 && f < u64::MAX as f32 {
+                            f as u64
+                        } else {
+                            return Err(out_of_range());
+                        }
+                    }
+                    _ => return Err(cast_err(self, typ, "undefined conversion")),
+                };
+                match u {
+                    U64LooseType::UnsignedInteger => u32::try_from(v).map(Self::UnsignedInteger),
+                    U64LooseType::UnsignedBigInt => Ok(Self::UnsignedBigInt(v)),
+                }
+                .map_err(|_| out_of_range())
+            }
+            NumericComparableType::F32Loose(_) => self
+                .to_f64_loose()
+                .map(|f| Self::Float(OrderedFloat(f as f32))),
+        }
+    }
+
+    /// Implicitly widens numeric values of different loose types into a common type to calculate them together.
+    ///
+    /// Integers are widened into FLOAT when either is FLOAT. Otherwise signed and unsigned integers are widened into BIGINT.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - Either value is not numeric.
+    ///   - UNSIGNED value is too large for BIGINT.
+    fn widen_numerics(&self, other: &Self) -> Result<(Self, Self)> {
+        let common_type = match (self.sql_type(), other.sql_type()) {
+            (
+                SqlType::NumericComparable(NumericComparableType::F32Loose(_)),
+                SqlType::NumericComparable(_),
+            )
+            | (
+                SqlType::NumericComparable(_),
+                SqlType::NumericComparable(NumericComparableType::F32Loose(_)),
+            ) => SqlType::float(),
+            (SqlType::NumericComparable(_), SqlType::NumericComparable(_)) => SqlType::big_int(),
+            (_, _) => {
+                return Err(SpringError::Sql(anyhow!(
+                    "both {} and {} must be numeric values",
+                    self,
+                    other
+                )))
+            }
+        };
+        Ok((self.try_cast(&common_type)?, other.try_cast(&common_type)?))
+    }
+
+    /// Compares two NOT NULL values.
+    ///
+    /// Numeric values are comparable to each other even if their loose types are different (e.g. `1 INTEGER` and `1.5 FLOAT`).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `self` and `other` are not in comparable types.
     pub fn sql_compare(&self, other: &Self) -> Result<SqlCompareResult> {
         match (self.sql_type(), other.sql_type()) {
             (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref other_n)) => {
@@ -285,11 +459,25 @@ impl NnSqlValue {
                         let (self_f32, other_f32) = (self.unpack::<f32>()?, other.unpack::<f32>()?);
                         Ok(SqlCompareResult::from(self_f32.partial_cmp(&other_f32)))
                     }
-                    _ => Err(SpringError::Sql(anyhow!(
-                        "Cannot compare {:?} and {:?}",
-                        self_n,
-                        other_n
-                    ))),
+                    (NumericComparableType::I64Loose(_), NumericComparableType::U64Loose(_)) => {
+                        let (self_i128, other_i128) = (
+                            self.unpack::<i64>()? as i128,
+                            other.unpack::<u64>()? as i128,
+                        );
+                        Ok(SqlCompareResult::from(self_i128.cmp(&other_i128)))
+                    }
+                    (NumericComparableType::U64Loose(_), NumericComparableType::I64Loose(_)) => {
+                        let (self_i128, other_i128) = (
+                            self.unpack::<u64>()? as i128,
+                            other.unpack::<i64>()? as i128,
+                        );
+                        Ok(SqlCompareResult::from(self_i128.cmp(&other_i128)))
+                    }
+                    (NumericComparableType::F32Loose(_), _)
+                    | (_, NumericComparableType::F32Loose(_)) => {
+                        let (self_f64, other_f64) = (self.to_f64_loose()?, other.to_f64_loose()?);
+                        Ok(SqlCompareResult::from(self_f64.partial_cmp(&other_f64)))
+                    }
                 }
             }
             (SqlType::StringComparableLoose(self_s), SqlType::StringComparableLoose(other_s)) => {
@@ -312,6 +500,13 @@ impl NnSqlValue {
                 );
                 Ok(SqlCompareResult::from(self_t.cmp(&other_t)))
             }
+            (SqlType::DurationComparable, SqlType::DurationComparable) => {
+                let (self_d, other_d) = (
+                    self.unpack::<SpringEventDuration>()?,
+                    other.unpack::<SpringEventDuration>()?,
+                );
+                Ok(SqlCompareResult::from(self_d.cmp(&other_d)))
+            }
             (_, _) => Err(SpringError::Sql(anyhow!(
                 "`self` and `other` are not in comparable type - self: {:?}, other: {:?}",
                 self,
@@ -320,6 +515,20 @@ impl NnSqlValue {
         }
     }
 
+    /// Numeric value as f64, used to compare values between different numeric loose types and to compute statistics.
+    pub fn to_f64_loose(&self) -> Result<f64> {
+        match self {
+            NnSqlValue::SmallInt(_) | NnSqlValue::Integer(_) | NnSqlValue::BigInt(_) => {
+                self.unpack::<i64>().map(|v| v as f64)
+            }
+            NnSqlValue::UnsignedInteger(_) | NnSqlValue::UnsignedBigInt(_) => {
+                self.unpack::<u64>().map(|v| v as f64)
+            }
+            NnSqlValue::Float(f) => Ok(f.into_inner() as f64),
+            _ => Err(SpringError::Sql(anyhow!("{} is not a numeric value", self))),
+        }
+    }
+
     /// # Failures
     ///
     /// - `SpringError::Sql` when:
@@ -381,13 +590,45 @@ impl Add for NnSqlValue {
                         let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
                         Ok(Self::Float(OrderedFloat(self_f32 + rhs_f32)))
                     }
-                    _ => Err(SpringError::Sql(anyhow!(
-                        "Cannot add {:?} and {:?}",
-                        self_n,
-                        rhs_n
-                    ))),
+                    _ => {
+                        let (self_widened, rhs_widened) = self.widen_numerics(&rhs)?;
+                        self_widened + rhs_widened
+                    }
                 }
             }
+            (SqlType::TimestampComparable, SqlType::DurationComparable) => {
+                let (self_ts, rhs_d) = (
+                    self.unpack::<SpringTimestamp>()?,
+                    rhs.unpack::<SpringEventDuration>()?,
+                );
+                Duration::from_std(*rhs_d.as_std())
+                    .ok()
+                    .and_then(|d| self_ts.checked_add(d))
+                    .map(Self::Timestamp)
+                    .ok_or_else(|| out_of_range_err(&self, "+", &rhs))
+            }
+            (SqlType::DurationComparable, SqlType::TimestampComparable) => {
+                let (self_d, rhs_ts) = (
+                    self.unpack::<SpringEventDuration>()?,
+                    rhs.unpack::<SpringTimestamp>()?,
+                );
+                Duration::from_std(*self_d.as_std())
+                    .ok()
+                    .and_then(|d| rhs_ts.checked_add(d))
+                    .map(Self::Timestamp)
+                    .ok_or_else(|| out_of_range_err(&self, "+", &rhs))
+            }
+            (SqlType::DurationComparable, SqlType::DurationComparable) => {
+                let (self_d, rhs_d) = (
+                    self.unpack::<SpringEventDuration>()?,
+                    rhs.unpack::<SpringEventDuration>()?,
+                );
+                self_d
+                    .as_std()
+                    .checked_add(*rhs_d.as_std())
+                    .map(|d| Self::Duration(SpringEventDuration::from_std(d)))
+                    .ok_or_else(|| out_of_range_err(&self, "+", &rhs))
+            }
             (_, _) => Err(SpringError::Sql(anyhow!(
                 "`self` + `rhs` is undefined - self: {:?}, other: {:?}",
                 self,
@@ -415,11 +656,10 @@ impl Mul for NnSqlValue {
                         let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
                         Ok(Self::Float(OrderedFloat(self_f32 * rhs_f32)))
                     }
-                    _ => Err(SpringError::Sql(anyhow!(
-                        "Cannot multiply {:?} by {:?}",
-                        self_n,
-                        rhs_n
-                    ))),
+                    _ => {
+                        let (self_widened, rhs_widened) = self.widen_numerics(&rhs)?;
+                        self_widened * rhs_widened
+                    }
                 }
             }
             (_, _) => Err(SpringError::Sql(anyhow!(
@@ -431,6 +671,184 @@ impl Mul for NnSqlValue {
     }
 }
 
+impl Sub for NnSqlValue {
+    type Output = Result<Self>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        match (self.sql_type(), rhs.sql_type()) {
+            (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref rhs_n)) => {
+                match (self_n, rhs_n) {
+                    (NumericComparableType::I64Loose(_), NumericComparableType::I64Loose(_)) => {
+                        let (self_i64, rhs_i64) = (self.unpack::<i64>()?, rhs.unpack::<i64>()?);
+                        self_i64
+                            .checked_sub(rhs_i64)
+                            .map(Self::BigInt)
+                            .ok_or_else(|| out_of_range_err(&self, "-", &rhs))
+                    }
+                    (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
+                        let (self_u64, rhs_u64) = (self.unpack::<u64>()?, rhs.unpack::<u64>()?);
+                        self_u64
+                            .checked_sub(rhs_u64)
+                            .map(Self::UnsignedBigInt)
+                            .ok_or_else(|| out_of_range_err(&self, "-", &rhs))
+                    }
+                    (NumericComparableType::F32Loose(_), NumericComparableType::F32Loose(_)) => {
+                        let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
+                        Ok(Self::Float(OrderedFloat(self_f32 - rhs_f32)))
+                    }
+                    _ => {
+                        let (self_widened, rhs_widened) = self.widen_numerics(&rhs)?;
+                        self_widened - rhs_widened
+                    }
+                }
+            }
+            (SqlType::TimestampComparable, SqlType::TimestampComparable) => {
+                let (self_ts, rhs_ts) = (
+                    self.unpack::<SpringTimestamp>()?,
+                    rhs.unpack::<SpringTimestamp>()?,
+                );
+                let std_duration = (self_ts - rhs_ts).to_std().map_err(|_| {
+                    SpringError::Sql(anyhow!("DURATION cannot be negative - {} - {}", self, rhs))
+                })?;
+                Ok(Self::Duration(SpringEventDuration::from_std(std_duration)))
+            }
+            (SqlType::TimestampComparable, SqlType::DurationComparable) => {
+                let (self_ts, rhs_d) = (
+                    self.unpack::<SpringTimestamp>()?,
+                    rhs.unpack::<SpringEventDuration>()?,
+                );
+                Duration::from_std(*rhs_d.as_std())
+                    .ok()
+                    .and_then(|d| self_ts.checked_sub(d))
+                    .map(Self::Timestamp)
+                    .ok_or_else(|| out_of_range_err(&self, "-", &rhs))
+            }
+            (SqlType::DurationComparable, SqlType::DurationComparable) => {
+                let (self_d, rhs_d) = (
+                    self.unpack::<SpringEventDuration>()?,
+                    rhs.unpack::<SpringEventDuration>()?,
+                );
+                self_d
+                    .as_std()
+                    .checked_sub(*rhs_d.as_std())
+                    .map(|d| Self::Duration(SpringEventDuration::from_std(d)))
+                    .ok_or_else(|| out_of_range_err(&self, "-", &rhs))
+            }
+            (_, _) => Err(SpringError::Sql(anyhow!(
+                "`self` - `rhs` is undefined - self: {:?}, other: {:?}",
+                self,
+                rhs
+            ))),
+        }
+    }
+}
+impl Div for NnSqlValue {
+    type Output = Result<Self>;
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        match (self.sql_type(), rhs.sql_type()) {
+            (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref rhs_n)) => {
+                match (self_n, rhs_n) {
+                    (NumericComparableType::I64Loose(_), NumericComparableType::I64Loose(_)) => {
+                        let (self_i64, rhs_i64) = (self.unpack::<i64>()?, rhs.unpack::<i64>()?);
+                        check_non_zero(rhs_i64 == 0, &self, "/")?;
+                        self_i64
+                            .checked_div(rhs_i64)
+                            .map(Self::BigInt)
+                            .ok_or_else(|| out_of_range_err(&self, "/", &rhs))
+                    }
+                    (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
+                        let (self_u64, rhs_u64) = (self.unpack::<u64>()?, rhs.unpack::<u64>()?);
+                        check_non_zero(rhs_u64 == 0, &self, "/")?;
+                        Ok(Self::UnsignedBigInt(self_u64 / rhs_u64))
+                    }
+                    (NumericComparableType::F32Loose(_), NumericComparableType::F32Loose(_)) => {
+                        let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
+                        check_non_zero(rhs_f32 == 0.0, &self, "/")?;
+                        Ok(Self::Float(OrderedFloat(self_f32 / rhs_f32)))
+                    }
+                    _ => {
+                        let (self_widened, rhs_widened) = self.widen_numerics(&rhs)?;
+                        self_widened / rhs_widened
+                    }
+                }
+            }
+            (_, _) => Err(SpringError::Sql(anyhow!(
+                "`self` / `rhs` is undefined - self: {:?}, other: {:?}",
+                self,
+                rhs
+            ))),
+        }
+    }
+}
+impl Rem for NnSqlValue {
+    type Output = Result<Self>;
+
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - `rhs` is zero.
+    fn rem(self, rhs: Self) -> Self::Output {
+        match (self.sql_type(), rhs.sql_type()) {
+            (SqlType::NumericComparable(ref self_n), SqlType::NumericComparable(ref rhs_n)) => {
+                match (self_n, rhs_n) {
+                    (NumericComparableType::I64Loose(_), NumericComparableType::I64Loose(_)) => {
+                        let (self_i64, rhs_i64) = (self.unpack::<i64>()?, rhs.unpack::<i64>()?);
+                        check_non_zero(rhs_i64 == 0, &self, "%")?;
+                        self_i64
+                            .checked_rem(rhs_i64)
+                            .map(Self::BigInt)
+                            .ok_or_else(|| out_of_range_err(&self, "%", &rhs))
+                    }
+                    (NumericComparableType::U64Loose(_), NumericComparableType::U64Loose(_)) => {
+                        let (self_u64, rhs_u64) = (self.unpack::<u64>()?, rhs.unpack::<u64>()?);
+                        check_non_zero(rhs_u64 == 0, &self, "%")?;
+                        Ok(Self::UnsignedBigInt(self_u64 % rhs_u64))
+                    }
+                    (NumericComparableType::F32Loose(_), NumericComparableType::F32Loose(_)) => {
+                        let (self_f32, rhs_f32) = (self.unpack::<f32>()?, rhs.unpack::<f32>()?);
+                        check_non_zero(rhs_f32 == 0.0, &self, "%")?;
+                        Ok(Self::Float(OrderedFloat(self_f32 % rhs_f32)))
+                    }
+                    _ => {
+                        let (self_widened, rhs_widened) = self.widen_numerics(&rhs)?;
+                        self_widened % rhs_widened
+                    }
+                }
+            }
+            (_, _) => Err(SpringError::Sql(anyhow!(
+                "`self` % `rhs` is undefined - self: {:?}, other: {:?}",
+                self,
+                rhs
+            ))),
+        }
+    }
+}
+
+fn check_non_zero(is_zero: bool, lhs: &NnSqlValue, op: &str) -> Result<()> {
+    if is_zero {
+        Err(SpringError::Sql(anyhow!(
+            "division by zero - {} {} 0",
+            lhs,
+            op
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+fn cast_err(value: &NnSqlValue, typ: &SqlType, reason: &str) -> SpringError {
+    SpringError::Sql(anyhow!("cannot cast {} into {} ({})", value, typ, reason))
+}
+
+fn out_of_range_err(lhs: &NnSqlValue, op: &str, rhs: &NnSqlValue) -> SpringError {
+    SpringError::Sql(anyhow!("value out of range - {} {} {}", lhs, op, rhs))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -479,6 +897,168 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_hash_integral_float_beyond_i64() {
+        fn hash_of(v: &NnSqlValue) -> u64 {
+            use std::hash::Hasher;
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            v.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let u = NnSqlValue::UnsignedBigInt(1 << 63);
+        let f = NnSqlValue::Float(OrderedFloat((1u64 << 63) as f32));
+        assert_eq!(u, f);
+        assert_eq!(hash_of(&u), hash_of(&f));
+    }
+
+    #[test]
+    fn test_div_rem_by_zero() {
+        assert!(matches!(
+            (NnSqlValue::Integer(1) / NnSqlValue::SmallInt(0)).unwrap_err(),
+            SpringError::Sql(_)
+        ));
+        assert!(matches!(
+            (NnSqlValue::UnsignedInteger(1) % NnSqlValue::UnsignedBigInt(0)).unwrap_err(),
+            SpringError::Sql(_)
+        ));
+        assert!(matches!(
+            (NnSqlValue::Float(OrderedFloat(1.0)) / NnSqlValue::Float(OrderedFloat(0.0)))
+                .unwrap_err(),
+            SpringError::Sql(_)
+        ));
+        assert!(matches!(
+            (NnSqlValue::BigInt(i64::MIN) / NnSqlValue::BigInt(-1)).unwrap_err(),
+            SpringError::Sql(_)
+        ));
+        assert!(matches!(
+            (NnSqlValue::UnsignedInteger(1) - NnSqlValue::UnsignedInteger(2)).unwrap_err(),
+            SpringError::Sql(_)
+        ));
+    }
+
+    #[test]
+    fn test_timestamp_arithmetic() -> Result<()> {
+        let ts1 = NnSqlValue::Timestamp(SpringTimestamp::fx_ts1());
+        let ts2 = NnSqlValue::Timestamp(SpringTimestamp::fx_ts2());
+        let d = (ts2.clone() - ts1.clone())?;
+        assert!(matches!(d, NnSqlValue::Duration(_)));
+
+        assert_eq!((ts1.clone() + d.clone())?, ts2);
+        assert_eq!((ts2.clone() - d.clone())?, ts1);
+        assert!(matches!((ts1 - ts2).unwrap_err(), SpringError::Sql(_),));
+        Ok(())
+    }
+
+    #[test]
+    fn test_timestamp_arithmetic_overflow() {
+        let ts = NnSqlValue::Timestamp(SpringTimestamp::fx_ts1());
+        let huge = NnSqlValue::Duration(SpringEventDuration::from_secs(1_000_000_000_000));
+        assert!(matches!(
+            (ts.clone() + huge.clone()).unwrap_err(),
+            SpringError::Sql(_)
+        ));
+        assert!(matches!(
+            (huge.clone() + ts.clone()).unwrap_err(),
+            SpringError::Sql(_)
+        ));
+        assert!(matches!((ts - huge).unwrap_err(), SpringError::Sql(_)));
+
+        let max = NnSqlValue::Duration(SpringEventDuration::from_std(std::time::Duration::MAX));
+        assert!(matches!(
+            (max.clone() + max).unwrap_err(),
+            SpringError::Sql(_)
+        ));
+    }
+
+    #[test]
+    fn test_try_cast() -> Result<()> {
+        assert_eq!(
+            NnSqlValue::Text(" -42 ".to_string()).try_cast(&SqlType::small_int())?,
+            NnSqlValue::SmallInt(-42)
+        );
+        assert_eq!(
+            NnSqlValue::Text("1.5".to_string()).try_cast(&SqlType::float())?,
+            NnSqlValue::Float(OrderedFloat(1.5))
+        );
+        assert_eq!(
+            NnSqlValue::Float(OrderedFloat(-1.5)).try_cast(&SqlType::integer())?,
+            NnSqlValue::Integer(-2)
+        );
+        assert_eq!(
+            NnSqlValue::UnsignedBigInt(42).try_cast(&SqlType::float())?,
+            NnSqlValue::Float(OrderedFloat(42.0))
+        );
+        assert_eq!(
+            NnSqlValue::Integer(42).try_cast(&SqlType::text())?,
+            NnSqlValue::Text("42".to_string())
+        );
+        assert_eq!(
+            NnSqlValue::Text(SpringTimestamp::fx_ts1().to_string())
+                .try_cast(&SqlType::timestamp())?,
+            NnSqlValue::Timestamp(SpringTimestamp::fx_ts1())
+        );
+        assert_eq!(
+            NnSqlValue::Timestamp(SpringTimestamp::fx_ts1()).try_cast(&SqlType::text())?,
+            NnSqlValue::Text(SpringTimestamp::fx_ts1().to_string())
+        );
+        assert_eq!(
+            NnSqlValue::Blob("🚔".as_bytes().to_vec()).try_cast(&SqlType::text())?,
+            NnSqlValue::Text("🚔".to_string())
+        );
+
+        assert!(matches!(
+            NnSqlValue::Integer(70000).try_cast(&SqlType::small_int()),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            NnSqlValue::Integer(-1).try_cast(&SqlType::unsigned_integer()),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            NnSqlValue::Float(OrderedFloat(f32::NAN)).try_cast(&SqlType::big_int()),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            NnSqlValue::Text("1.5".to_string()).try_cast(&SqlType::integer()),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            NnSqlValue::Text("yesterday".to_string()).try_cast(&SqlType::timestamp()),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            NnSqlValue::Blob(vec![0xff, 0xfe]).try_cast(&SqlType::text()),
+            Err(SpringError::Sql(_))
+        ));
+        assert!(matches!(
+            NnSqlValue::Boolean(true).try_cast(&SqlType::timestamp()),
+            Err(SpringError::Sql(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_arithmetic_widening() -> Result<()> {
+        assert_eq!(
+            (NnSqlValue::Integer(1) + NnSqlValue::Float(OrderedFloat(0.5)))?,
+            NnSqlValue::Float(OrderedFloat(1.5))
+        );
+        assert_eq!(
+            (NnSqlValue::UnsignedInteger(1) - NnSqlValue::SmallInt(2))?,
+            NnSqlValue::BigInt(-1)
+        );
+        assert_eq!(
+            (NnSqlValue::Float(OrderedFloat(3.0)) / NnSqlValue::BigInt(2))?,
+            NnSqlValue::Float(OrderedFloat(1.5))
+        );
+        assert!(matches!(
+            NnSqlValue::UnsignedBigInt(u64::MAX) * NnSqlValue::Integer(1),
+            Err(SpringError::Sql(_))
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_unpack_blob() {
         assert_eq!(
@@ -0,0 +1,237 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod table_file;
+mod table_repository;
+
+pub use table_file::read_table_file;
+pub use table_repository::TableRepository;
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    api::error::Result,
+    pipeline::{ColumnName, TableModel},
+    stream_engine::{
+        autonomous_executor::{
+            row::{SchemalessRow, StreamRow},
+            task::Tuple,
+        },
+        NnSqlValue, SqlValue,
+    },
+};
+
+/// Row indices having the same key.
+type HashIndex = HashMap<NnSqlValue, Vec<usize>>;
+
+/// Rows of a table, looked up by key.
+#[derive(Debug, Default)]
+pub struct Table {
+    /// Shared with the table having another index.
+    tuples: Arc<Vec<Tuple>>,
+
+    /// Hash indexes of key columns, built in advance so that lookups do not take any lock.
+    indexes: HashMap<ColumnName, HashIndex>,
+}
+
+impl Table {
+    /// Builds hash indexes of `key_columns` too.
+    ///
+    /// # Failure
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - a row lacks any of NOT NULL columns of the table.
+    ///   - type mismatch (and failed to convert type) between a row and the table.
+    pub fn new(
+        table: &TableModel,
+        rows: Vec<SchemalessRow>,
+        key_columns: &[ColumnName],
+    ) -> Result<Self> {
+        let tuples = rows
+            .into_iter()
+            .map(|row| {
+                StreamRow::from_schemaless_row(row, table.stream_model()).map(Tuple::from_row)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut table = Self {
+            tuples: Arc::new(tuples),
+            indexes: HashMap::new(),
+        };
+        for key_column in key_columns {
+            table.build_index(key_column);
+        }
+        Ok(table)
+    }
+
+    pub fn has_index(&self, key_column: &ColumnName) -> bool {
+        self.indexes.contains_key(key_column)
+    }
+
+    /// The same rows with the hash index of `key_column` too.
+    pub fn with_index(&self, key_column: &ColumnName) -> Self {
+        let mut table = Self {
+            tuples: self.tuples.clone(),
+            indexes: self.indexes.clone(),
+        };
+        table.build_index(key_column);
+        table
+    }
+
+    /// Tuples whose `key_column` is equal to `key`. NULL key matches no tuple.
+    ///
+    /// Falls back to a full scan if `key_column` has no index.
+    pub fn lookup(&self, key_column: &ColumnName, key: &SqlValue) -> Vec<&Tuple> {
+        match (key, self.indexes.get(key_column)) {
+            (SqlValue::Null, _) => vec![],
+            (SqlValue::NotNull(key), Some(index)) => index
+                .get(key)
+                .map(|row_indices| row_indices.iter().map(|i| &self.tuples[*i]).collect())
+                .unwrap_or_default(),
+            (SqlValue::NotNull(key), None) => self
+                .tuples
+                .iter()
+                .filter(|tuple| {
+                    matches!(tuple.get_value_by_column_name(key_column), Some(SqlValue::NotNull(v)) if &v == key)
+                })
+                .collect(),
+        }
+    }
+
+    fn build_index(&mut self, key_column: &ColumnName) {
+        let mut index = HashIndex::new();
+        for (i, tuple) in self.tuples.iter().enumerate() {
+            if let Some(SqlValue::NotNull(key)) = tuple.get_value_by_column_name(key_column) {
+                index.entry(key).or_default().push(i);
+            }
+        }
+        self.indexes.insert(key_column.clone(), index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::pipeline::{
+        OptionsBuilder, StreamModel, TableFileFormat, TableFileOptions, TableModel,
+    };
+
+    fn fx_table_model() -> TableModel {
+        TableModel::new(
+            Arc::new(StreamModel::fx_trade()),
+            OptionsBuilder::default().build(),
+        )
+    }
+
+    fn load(format: TableFileFormat, content: &str, key_columns: &[ColumnName]) -> Table {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+
+        let file_options = TableFileOptions {
+            path: file.path().to_path_buf(),
+            format,
+        };
+        let table_model = fx_table_model();
+        let rows = read_table_file(&file_options, &table_model).unwrap();
+        Table::new(&table_model, rows, key_columns).unwrap()
+    }
+
+    fn amounts(table: &Table, ticker: &str) -> Vec<SqlValue> {
+        let mut amounts = table
+            .lookup(
+                &ColumnName::fx_ticker(),
+                &SqlValue::NotNull(NnSqlValue::Text(ticker.to_string())),
+            )
+            .into_iter()
+            .map(|tuple| {
+                tuple
+                    .get_value_by_column_name(&ColumnName::fx_amount())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        amounts.sort_by_key(|v| format!("{:?}", v));
+        amounts
+    }
+
+    #[test]
+    fn test_lookup_csv() {
+        let table = load(
+            TableFileFormat::Csv,
+            "ts, ticker, amount, unknown
+2020-01-01 00:00:00.000000000, ORCL, 10, x
+2020-01-01 00:00:01.000000000, GOOGL, 20, y
+2020-01-01 00:00:02.000000000, ORCL, 30, z
+",
+            &[ColumnName::fx_ticker()],
+        );
+
+        assert_eq!(
+            amounts(&table, "ORCL"),
+            vec![
+                SqlValue::NotNull(NnSqlValue::Integer(10)),
+                SqlValue::NotNull(NnSqlValue::Integer(30))
+            ]
+        );
+        assert_eq!(
+            amounts(&table, "GOOGL"),
+            vec![SqlValue::NotNull(NnSqlValue::Integer(20))]
+        );
+        assert!(amounts(&table, "IBM").is_empty());
+        assert!(table
+            .lookup(&ColumnName::fx_ticker(), &SqlValue::Null)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_lookup_json_lines() {
+        let table = load(
+            TableFileFormat::Json,
+            r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}
+{"ts": "2020-01-01 00:00:01.000000000", "ticker": "GOOGL", "amount": 20}
+"#,
+            &[ColumnName::fx_ticker()],
+        );
+
+        assert_eq!(
+            amounts(&table, "ORCL"),
+            vec![SqlValue::NotNull(NnSqlValue::Integer(10))]
+        );
+    }
+
+    #[test]
+    fn test_lookup_without_index() {
+        let table = load(
+            TableFileFormat::Csv,
+            "ts, ticker, amount
+2020-01-01 00:00:00.000000000, ORCL, 10
+2020-01-01 00:00:01.000000000, GOOGL, 20
+",
+            &[],
+        );
+        assert!(!table.has_index(&ColumnName::fx_ticker()));
+        assert_eq!(
+            amounts(&table, "GOOGL"),
+            vec![SqlValue::NotNull(NnSqlValue::Integer(20))]
+        );
+
+        let indexed = table.with_index(&ColumnName::fx_ticker());
+        assert!(indexed.has_index(&ColumnName::fx_ticker()));
+        assert_eq!(amounts(&indexed, "GOOGL"), amounts(&table, "GOOGL"));
+        assert!(amounts(&indexed, "IBM").is_empty());
+    }
+
+    #[test]
+    fn test_read_missing_not_null_column() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"ticker\nORCL\n").unwrap();
+
+        let file_options = TableFileOptions {
+            path: file.path().to_path_buf(),
+            format: TableFileFormat::Csv,
+        };
+        let table_model = fx_table_model();
+        let rows = read_table_file(&file_options, &table_model).unwrap();
+        assert!(Table::new(&table_model, rows, &[]).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::fs;
+
+use anyhow::{anyhow, Context};
+
+use crate::{
+    api::error::{foreign_info::ForeignInfo, Result, SpringError},
+    pipeline::{ColumnName, TableFileFormat, TableFileOptions, TableModel},
+    stream_engine::{
+        autonomous_executor::row::{ColumnValues, JsonObject, SchemalessRow},
+        NnSqlValue, SqlValue,
+    },
+};
+
+/// Reads rows of `table` from a file.
+///
+/// # Failure
+///
+/// - `SpringError::ForeignIo` when:
+///   - the file cannot be read.
+/// - `SpringError::InvalidFormat` when:
+///   - the file content cannot be parsed as `file_options.format`.
+/// - `SpringError::Sql` when:
+///   - a CSV field cannot be converted into the column type.
+pub fn read_table_file(
+    file_options: &TableFileOptions,
+    table: &TableModel,
+) -> Result<Vec<SchemalessRow>> {
+    let content = fs::read_to_string(&file_options.path)
+        .context("failed to read table file")
+        .map_err(|e| SpringError::ForeignIo {
+            foreign_info: ForeignInfo::File(file_options.path.clone()),
+            source: e,
+        })?;
+
+    match file_options.format {
+        TableFileFormat::Csv => parse_csv(&content, table),
+        TableFileFormat::Json => parse_json(&content),
+    }
+}
+
+/// The header line has column names. Columns not in the table are ignored, and empty fields are NULL.
+fn parse_csv(content: &str, table: &TableModel) -> Result<Vec<SchemalessRow>> {
+    let invalid_format = |e: csv::Error| SpringError::InvalidFormat {
+        s: content.to_string(),
+        source: anyhow!(e).context("failed to parse table file as CSV"),
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let stream_model = table.stream_model();
+    let columns = reader
+        .headers()
+        .map_err(invalid_format)?
+        .iter()
+        .map(|header| {
+            let column_name = ColumnName::new(header.to_string());
+            let sql_type = stream_model
+                .shape()
+                .columns()
+                .iter()
+                .map(|coldef| coldef.column_data_type())
+                .find(|cdt| cdt.column_name() == &column_name)
+                .map(|cdt| cdt.sql_type().clone());
+            (column_name, sql_type)
+        })
+        .collect::<Vec<_>>();
+
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(invalid_format)?;
+
+            let mut column_values = ColumnValues::default();
+            for ((column_name, sql_type), field) in columns.iter().zip(record.iter()) {
+                if let Some(sql_type) = sql_type {
+                    let value = if field.is_empty() {
+                        SqlValue::Null
+                    } else {
+                        SqlValue::NotNull(NnSqlValue::Text(field.to_string()).try_cast(sql_type)?)
+                    };
+                    column_values.insert(column_name.clone(), value)?;
+                }
+            }
+            Ok(column_values.into())
+        })
+        .collect()
+}
+
+/// A JSON array of objects, or an object per line.
+fn parse_json(content: &str) -> Result<Vec<SchemalessRow>> {
+    let json_objects = if content.trim_start().starts_with('[') {
+        serde_json::from_str::<Vec<serde_json::Value>>(content)
+            .context("failed to parse table file as an array of JSON objects")
+            .map_err(|e| SpringError::InvalidFormat {
+                s: content.to_string(),
+                source: e,
+            })?
+            .into_iter()
+            .map(JsonObject::new)
+            .collect()
+    } else {
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(JsonObject::parse)
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    json_objects
+        .into_iter()
+        .map(|json_object| json_object.into_column_values().map(SchemalessRow::from))
+        .collect()
+}
@@ -0,0 +1,43 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use std::{collections::HashMap, sync::Arc};
+
+use parking_lot::RwLock;
+
+use crate::{
+    pipeline::{ColumnName, StreamName},
+    stream_engine::autonomous_executor::table::Table,
+};
+
+#[derive(Debug, Default)]
+pub struct TableRepository {
+    repo: RwLock<HashMap<StreamName, Arc<Table>>>,
+}
+
+impl TableRepository {
+    /// Empty table if rows of `table_name` are not loaded.
+    pub fn get(&self, table_name: &StreamName) -> Arc<Table> {
+        let repo = self.repo.read();
+        repo.get(table_name).cloned().unwrap_or_default()
+    }
+
+    /// Replaces all the rows of a table.
+    ///
+    /// Tasks looking up the table in the middle keep the old rows until their next lookup.
+    pub fn replace(&self, table_name: StreamName, table: Table) {
+        let mut repo = self.repo.write();
+        repo.insert(table_name, Arc::new(table));
+    }
+
+    /// Builds the hash index of `key_column` unless the table has it.
+    ///
+    /// Tasks looking up the table in the middle keep the table without the index until their next lookup.
+    pub fn add_index(&self, table_name: &StreamName, key_column: &ColumnName) {
+        let mut repo = self.repo.write();
+        if let Some(table) = repo.get_mut(table_name) {
+            if !table.has_index(key_column) {
+                *table = Arc::new(table.with_index(key_column));
+            }
+        }
+    }
+}
@@ -17,7 +17,7 @@ pub use source_task::{
 };
 pub use task_context::TaskContext;
 pub use tuple::Tuple;
-pub use window::Window;
+pub use window::{AggregatedAndGroupingValues, Window};
 
 use crate::{
     api::error::Result,
@@ -45,7 +45,7 @@ pub struct TaskRunResult {
 impl Task {
     pub fn new(edge: &Edge, pipeline_graph: &PipelineGraph) -> Self {
         match edge {
-            Edge::Pump { pump_model, .. } => {
+            Edge::Pump { pump_model, .. } | Edge::LateRows(pump_model) => {
                 Self::Pump(Box::new(PumpTask::new(pump_model.as_ref(), pipeline_graph)))
             }
             Edge::Source(s) => Self::Source(SourceTask::new(s)),
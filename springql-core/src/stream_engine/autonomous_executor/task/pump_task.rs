@@ -42,7 +42,8 @@ impl PumpTask {
     pub fn new(pump: &PumpModel, pipeline_graph: &PipelineGraph) -> Self {
         let id = TaskId::from_pump(pump);
         let query_subtask = QuerySubtask::new(pump.query_plan().clone());
-        let insert_subtask = InsertSubtask::new(pump.insert_plan(), pipeline_graph);
+        let insert_subtask =
+            InsertSubtask::new(pump.insert_plan(), pump.late_rows_into(), pipeline_graph);
         Self {
             id,
             query_subtask,
@@ -56,11 +57,11 @@ impl PumpTask {
 
     pub fn run(&self, context: &TaskContext) -> Result<TaskRunResult> {
         let stopwatch = WallClockStopwatch::start();
-        let (processed_rows, in_queue_metrics, out_queues_metrics) =
+        let (processed_rows, late_rows, in_queue_metrics, out_queues_metrics) =
             self.run_query_insert(context)?;
         let execution_time = stopwatch.stop();
 
-        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
+        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time, late_rows);
         let metrics = MetricsUpdateByTaskExecution::new(
             task_metrics,
             in_queue_metrics.map_or_else(Vec::new, |m| vec![m]),
@@ -78,29 +79,41 @@ impl PumpTask {
         context: &TaskContext,
     ) -> Result<(
         ProcessedRows,
+        u64, // late rows
         Option<InQueueMetricsUpdateByTask>,
         Vec<OutQueueMetricsUpdateByTask>,
     )> {
         if let Some(query_subtask_out) = self.query_subtask.run(context)? {
             let processed_rows = query_subtask_out.processed_rows();
-            let insert_subtask_out = self
+            let late_rows = query_subtask_out.late_tuples.len() as u64;
+
+            let mut out_queues_metrics = self
                 .insert_subtask
-                .run(query_subtask_out.values_seq, context);
+                .run(query_subtask_out.values_seq, context)
+                .out_queues_metrics_update;
+            out_queues_metrics.append(
+                &mut self
+                    .insert_subtask
+                    .run_late(query_subtask_out.late_tuples, context)
+                    .out_queues_metrics_update,
+            );
+
             Ok((
                 processed_rows,
+                late_rows,
                 Some(query_subtask_out.in_queue_metrics_update),
-                insert_subtask_out.out_queues_metrics_update,
+                out_queues_metrics,
             ))
         } else {
             thread::sleep(WAIT_ON_NO_INPUT);
-            Ok((ProcessedRows::default(), None, vec![]))
+            Ok((ProcessedRows::default(), 0, None, vec![]))
         }
     }
 
     pub fn get_aggr_window_mut(&self) -> Option<MutexGuard<AggrWindow>> {
         self.query_subtask.get_aggr_window_mut()
     }
-    pub fn get_join_window_mut(&self) -> Option<MutexGuard<JoinWindow>> {
-        self.query_subtask.get_join_window_mut()
+    pub fn get_join_windows_mut(&self) -> Option<MutexGuard<Vec<JoinWindow>>> {
+        self.query_subtask.get_join_windows_mut()
     }
 }
@@ -4,12 +4,15 @@ use std::sync::Arc;
 
 use crate::{
     mem_size::MemSize,
-    pipeline::{ColumnName, PipelineGraph, StreamModel},
+    pipeline::{ColumnName, PipelineGraph, StreamModel, StreamName},
     stream_engine::{
         autonomous_executor::{
             performance_metrics::OutQueueMetricsUpdateByTask,
             row::StreamRow,
-            task::{pump_task::pump_subtask::query_subtask::SqlValues, task_context::TaskContext},
+            task::{
+                pump_task::pump_subtask::query_subtask::SqlValues, task_context::TaskContext,
+                tuple::Tuple,
+            },
             task_graph::QueueId,
         },
         command::InsertPlan,
@@ -22,6 +25,9 @@ pub struct InsertSubtask {
 
     /// INSERT INTO stream (c2, c3, c1) -- this one!
     column_order: Vec<ColumnName>,
+
+    /// LATE ROWS INTO stream -- this one!
+    late_rows_into: Option<Arc<StreamModel>>,
 }
 
 #[derive(Debug, new)]
@@ -32,14 +38,24 @@ pub struct InsertSubtaskOut {
 impl InsertSubtask {
     /// # Panics
     ///
-    /// `plan` has invalid stream name
-    pub fn new(plan: &InsertPlan, pipeline_graph: &PipelineGraph) -> Self {
+    /// `plan` or `late_rows_into` has invalid stream name
+    pub fn new(
+        plan: &InsertPlan,
+        late_rows_into: Option<&StreamName>,
+        pipeline_graph: &PipelineGraph,
+    ) -> Self {
         let into_stream = pipeline_graph
             .get_stream(plan.stream())
             .expect("plan has invalid stream name");
+        let late_rows_into = late_rows_into.map(|stream_name| {
+            pipeline_graph
+                .get_stream(stream_name)
+                .expect("late rows stream has invalid name")
+        });
         Self {
             into_stream,
             column_order: plan.column_order().to_vec(),
+            late_rows_into,
         }
     }
 
@@ -47,42 +63,82 @@ impl InsertSubtask {
         if values_seq.is_empty() {
             InsertSubtaskOut::new(vec![])
         } else {
-            let repos = context.repos();
-            let row_q_repo = repos.row_queue_repository();
-            let window_q_repo = repos.window_queue_repository();
-            let output_queues = context.output_queues();
-
             let rows = values_seq
                 .into_iter()
                 .map(|values| values.into_row(self.into_stream.clone(), self.column_order.clone()))
                 .collect::<Vec<_>>();
 
-            let out_queues_metrics_update = output_queues
-                .into_iter()
-                .map(|q| match q {
-                    QueueId::Row(queue_id) => {
-                        let row_q = row_q_repo.get(&queue_id);
-                        let out = self.out_queue_metrics_update(queue_id.into(), &rows);
-                        for row in rows.clone() {
-                            row_q.put(row);
-                        }
-                        out
-                    }
-                    QueueId::Window(queue_id) => {
-                        let window_queue = window_q_repo.get(&queue_id);
-                        let out = self.out_queue_metrics_update(queue_id.into(), &rows);
-                        for row in rows.clone() {
-                            window_queue.put(row);
-                        }
-                        out
-                    }
-                })
-                .collect();
+            let output_queues = context.output_queues_to_stream(self.into_stream.name());
+            InsertSubtaskOut::new(self.put_rows(rows, output_queues, context))
+        }
+    }
+
+    /// Puts late tuples into the `LATE ROWS INTO` stream.
+    /// Columns of the stream are taken from the same-named fields of tuples (missing ones are NULL).
+    ///
+    /// Late tuples are just dropped if the pump does not have `LATE ROWS INTO`.
+    pub fn run_late(&self, late_tuples: Vec<Tuple>, context: &TaskContext) -> InsertSubtaskOut {
+        match &self.late_rows_into {
+            Some(late_stream) if !late_tuples.is_empty() => {
+                let rows = late_tuples
+                    .into_iter()
+                    .map(|tuple| Self::late_tuple_into_row(late_stream.clone(), tuple))
+                    .collect::<Vec<_>>();
 
-            InsertSubtaskOut::new(out_queues_metrics_update)
+                let output_queues = context.output_queues_to_stream(late_stream.name());
+                InsertSubtaskOut::new(self.put_rows(rows, output_queues, context))
+            }
+            _ => InsertSubtaskOut::new(vec![]),
         }
     }
 
+    fn late_tuple_into_row(late_stream: Arc<StreamModel>, tuple: Tuple) -> StreamRow {
+        let (column_order, values): (Vec<_>, Vec<_>) = late_stream
+            .shape()
+            .column_names()
+            .into_iter()
+            .filter_map(|column_name| {
+                tuple
+                    .get_value_by_column_name(&column_name)
+                    .map(|value| (column_name, value))
+            })
+            .unzip();
+        SqlValues::new(values).into_row(late_stream, column_order)
+    }
+
+    fn put_rows(
+        &self,
+        rows: Vec<StreamRow>,
+        output_queues: Vec<QueueId>,
+        context: &TaskContext,
+    ) -> Vec<OutQueueMetricsUpdateByTask> {
+        let repos = context.repos();
+        let row_q_repo = repos.row_queue_repository();
+        let window_q_repo = repos.window_queue_repository();
+
+        output_queues
+            .into_iter()
+            .map(|q| match q {
+                QueueId::Row(queue_id) => {
+                    let row_q = row_q_repo.get(&queue_id);
+                    let out = self.out_queue_metrics_update(queue_id.into(), &rows);
+                    for row in rows.clone() {
+                        row_q.put(row);
+                    }
+                    out
+                }
+                QueueId::Window(queue_id) => {
+                    let window_queue = window_q_repo.get(&queue_id);
+                    let out = self.out_queue_metrics_update(queue_id.into(), &rows);
+                    for row in rows.clone() {
+                        window_queue.put(row);
+                    }
+                    out
+                }
+            })
+            .collect()
+    }
+
     fn out_queue_metrics_update(
         &self,
         queue_id: QueueId,
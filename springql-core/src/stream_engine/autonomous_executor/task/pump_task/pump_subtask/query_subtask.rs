@@ -4,6 +4,8 @@ mod collect_subtask;
 mod group_aggregate_window_subtask;
 mod join_subtask;
 mod projection_subtask;
+mod selection_subtask;
+mod table_lookup_subtask;
 
 use std::sync::{Arc, Mutex, MutexGuard};
 
@@ -27,6 +29,7 @@ use crate::{
                     collect_subtask::CollectSubtask,
                     group_aggregate_window_subtask::GroupAggregateWindowSubtask,
                     join_subtask::JoinSubtask, projection_subtask::ProjectionSubtask,
+                    selection_subtask::SelectionSubtask, table_lookup_subtask::TableLookupSubtask,
                 },
                 task_context::TaskContext,
                 tuple::Tuple,
@@ -34,11 +37,14 @@ use crate::{
                 ProcessedRows,
             },
         },
-        command::{JoinOp, LowerOps, QueryPlan},
+        command::{JoinOp, QueryPlan},
         SqlValue,
     },
 };
 
+/// (tuples, late tuples, in-queue metrics) from collect and join subtasks.
+type LowerOpsOut = (Vec<Tuple>, Vec<Tuple>, InQueueMetricsUpdateByTask);
+
 /// Process input row 1-by-1.
 #[derive(Debug)]
 pub struct QuerySubtask {
@@ -48,12 +54,15 @@ pub struct QuerySubtask {
 
     group_aggr_window_subtask: Option<GroupAggregateWindowSubtask>,
 
-    // TODO recursive JOIN
+    selection_subtask: Option<SelectionSubtask>,
+
     join: Option<(
         JoinSubtask,
-        CollectSubtask, // right stream
+        Vec<CollectSubtask>, // right stream of each join level
     )>,
-    left_collect_subtask: CollectSubtask, // left stream
+    left_collect_subtask: CollectSubtask, // leftmost stream
+
+    table_lookup_subtasks: Vec<TableLookupSubtask>,
 
     rng: Mutex<SmallRng>,
 }
@@ -105,6 +114,8 @@ impl SqlValues {
 #[derive(Debug, new)]
 pub struct QuerySubtaskOut {
     pub values_seq: Vec<SqlValues>,
+    /// Tuples dropped by windows as too late.
+    pub late_tuples: Vec<Tuple>,
     pub in_queue_metrics_update: InQueueMetricsUpdateByTask,
 }
 impl QuerySubtaskOut {
@@ -130,12 +141,21 @@ impl QuerySubtask {
         let rng =
             Mutex::new(SmallRng::from_rng(rand::thread_rng()).expect("this generally won't fail"));
 
-        let (left_collect_subtask, join) = Self::subtasks_from_lower_ops(plan.lower_ops);
-
-        let group_aggr_window_subtask = plan
-            .upper_ops
-            .group_aggr_window
-            .map(|op| GroupAggregateWindowSubtask::new(op.window_param, op.op_param));
+        let selection_subtask = plan
+            .lower_ops
+            .selection
+            .map(SelectionSubtask::from_selection_op);
+        let (left_collect_subtask, join) = Self::subtasks_from_lower_ops(plan.lower_ops.join);
+        let table_lookup_subtasks = plan
+            .lower_ops
+            .table_lookups
+            .into_iter()
+            .map(TableLookupSubtask::new)
+            .collect();
+
+        let group_aggr_window_subtask = plan.upper_ops.group_aggr_window.map(|op| {
+            GroupAggregateWindowSubtask::new(op.window_param, op.op_param, op.emit_policy)
+        });
 
         let projection_subtask = ProjectionSubtask::new(plan.upper_ops.projection.expr_labels);
 
@@ -143,30 +163,48 @@ impl QuerySubtask {
             expr_resolver: plan.expr_resolver,
             projection_subtask,
             group_aggr_window_subtask,
+            selection_subtask,
             left_collect_subtask,
             join,
+            table_lookup_subtasks,
             rng,
         }
     }
-    /// (left collect subtask, Option<(join subtask, right collect subtask)>)
+    /// (leftmost collect subtask, Option<(join subtask, right collect subtask of each join level)>)
     fn subtasks_from_lower_ops(
-        lower_ops: LowerOps,
-    ) -> (CollectSubtask, Option<(JoinSubtask, CollectSubtask)>) {
-        match lower_ops.join {
-            JoinOp::Collect(collect_op) => {
-                let collect_subtask = CollectSubtask::from_collect_op(collect_op);
-                (collect_subtask, None)
-            }
-            JoinOp::JoinWindow(join_window_op) => {
-                let left_collect_subtask = CollectSubtask::from_collect_op(join_window_op.left);
-                let right_collect_subtask = CollectSubtask::from_collect_op(join_window_op.right);
-                let join_subtask =
-                    JoinSubtask::new(join_window_op.window_param, join_window_op.join_param);
-                (
-                    left_collect_subtask,
-                    Some((join_subtask, right_collect_subtask)),
-                )
+        join_op: JoinOp,
+    ) -> (CollectSubtask, Option<(JoinSubtask, Vec<CollectSubtask>)>) {
+        let mut levels = Vec::new();
+        let mut right_collect_subtasks = Vec::new();
+
+        let mut join_op = join_op;
+        let left_collect_op = loop {
+            match join_op {
+                JoinOp::Collect(collect_op) => break collect_op,
+                JoinOp::JoinWindow(join_window_op) => {
+                    right_collect_subtasks
+                        .push(CollectSubtask::from_collect_op(join_window_op.right));
+                    levels.push((
+                        join_window_op.window_param,
+                        join_window_op.join_param,
+                        join_window_op.emit_policy,
+                    ));
+                    join_op = *join_window_op.left;
+                }
             }
+        };
+        let left_collect_subtask = CollectSubtask::from_collect_op(left_collect_op);
+
+        if levels.is_empty() {
+            (left_collect_subtask, None)
+        } else {
+            // innermost join first
+            levels.reverse();
+            right_collect_subtasks.reverse();
+            (
+                left_collect_subtask,
+                Some((JoinSubtask::new(levels), right_collect_subtasks)),
+            )
         }
     }
 
@@ -178,33 +216,76 @@ impl QuerySubtask {
     ///
     /// TODO
     pub fn run(&self, context: &TaskContext) -> Result<Option<QuerySubtaskOut>> {
-        match self.run_lower_ops(context) {
+        match self.run_lower_ops(context)? {
             None => Ok(None),
-            Some((lower_tuples, in_queue_metrics_update_by_task)) => {
-                let (values_seq, in_queue_metrics_update) =
-                    self.run_upper_ops(lower_tuples, in_queue_metrics_update_by_task)?;
+            Some((lower_tuples, mut late_tuples, in_queue_metrics_update_by_task)) => {
+                let looked_up_tuples = self.run_table_lookups(lower_tuples, context)?;
+                let selected_tuples = self.run_selection(looked_up_tuples)?;
+                let (values_seq, mut upper_late_tuples, in_queue_metrics_update) =
+                    self.run_upper_ops(selected_tuples, in_queue_metrics_update_by_task)?;
+                late_tuples.append(&mut upper_late_tuples);
 
                 Ok(Some(QuerySubtaskOut::new(
                     values_seq,
+                    late_tuples,
                     in_queue_metrics_update,
                 )))
             }
         }
     }
 
+    /// Joins tables to tuples from streams, one table after another.
+    fn run_table_lookups(&self, tuples: Vec<Tuple>, context: &TaskContext) -> Result<Vec<Tuple>> {
+        self.table_lookup_subtasks
+            .iter()
+            .try_fold(tuples, |tuples, table_lookup_subtask| {
+                let mut joined_tuples = Vec::with_capacity(tuples.len());
+                for tuple in tuples {
+                    joined_tuples.append(&mut table_lookup_subtask.run(
+                        &self.expr_resolver,
+                        tuple,
+                        context,
+                    )?);
+                }
+                Ok(joined_tuples)
+            })
+    }
+
+    /// Drops tuples not satisfying WHERE condition before they reach projection and windows.
+    fn run_selection(&self, tuples: Vec<Tuple>) -> Result<Vec<Tuple>> {
+        match &self.selection_subtask {
+            Some(selection_subtask) => {
+                let mut selected_tuples = Vec::with_capacity(tuples.len());
+                for tuple in tuples {
+                    if selection_subtask.run(&self.expr_resolver, &tuple)? {
+                        selected_tuples.push(tuple);
+                    }
+                }
+                Ok(selected_tuples)
+            }
+            None => Ok(tuples),
+        }
+    }
+
     fn run_upper_ops(
         &self,
         tuples: Vec<Tuple>,
         in_queue_metrics_update_by_lower: InQueueMetricsUpdateByTask,
-    ) -> Result<(Vec<SqlValues>, InQueueMetricsUpdateByTask)> {
-        let (values_seq, window_in_flow_upper_total) = tuples.into_iter().fold(
-            Ok((Vec::new(), WindowInFlowByWindowTask::zero())),
+    ) -> Result<(Vec<SqlValues>, Vec<Tuple>, InQueueMetricsUpdateByTask)> {
+        let (values_seq, late_tuples, window_in_flow_upper_total) = tuples.into_iter().fold(
+            Ok((Vec::new(), Vec::new(), WindowInFlowByWindowTask::zero())),
             |res, tuple| {
-                let (mut values_seq_acc, window_in_flow_acc) = res?;
+                let (mut values_seq_acc, mut late_tuples_acc, window_in_flow_acc) = res?;
 
-                let (mut values_seq, window_in_flow) = self.run_upper_ops_inner(tuple)?;
+                let (mut values_seq, late_tuple, window_in_flow) =
+                    self.run_upper_ops_inner(tuple)?;
                 values_seq_acc.append(&mut values_seq);
-                Ok((values_seq_acc, window_in_flow_acc + window_in_flow))
+                late_tuples_acc.extend(late_tuple);
+                Ok((
+                    values_seq_acc,
+                    late_tuples_acc,
+                    window_in_flow_acc + window_in_flow,
+                ))
             },
         )?;
         let in_queue_metrics_update_by_task = InQueueMetricsUpdateByTask::new(
@@ -212,103 +293,111 @@ impl QuerySubtask {
             Some(window_in_flow_upper_total + in_queue_metrics_update_by_lower.window_in_flow),
         );
 
-        Ok((values_seq, in_queue_metrics_update_by_task))
+        Ok((values_seq, late_tuples, in_queue_metrics_update_by_task))
     }
     fn run_upper_ops_inner(
         &self,
         tuple: Tuple,
-    ) -> Result<(Vec<SqlValues>, WindowInFlowByWindowTask)> {
+    ) -> Result<(Vec<SqlValues>, Option<Tuple>, WindowInFlowByWindowTask)> {
         if let Some(group_aggr_window_subtask) = &self.group_aggr_window_subtask {
-            let (aggregated_and_grouping_values_seq, window_in_flow) =
-                group_aggr_window_subtask.run(&self.expr_resolver, tuple);
+            let (aggregated_and_grouping_values_seq, late_tuple, window_in_flow) =
+                group_aggr_window_subtask.run(&self.expr_resolver, tuple)?;
 
             let values_seq = aggregated_and_grouping_values_seq
                 .into_iter()
                 .map(|aggregated_and_grouping_values| {
                     self.projection_subtask
-                        .run_with_aggr(aggregated_and_grouping_values)
+                        .run_with_aggr(&self.expr_resolver, aggregated_and_grouping_values)
                 })
                 .collect::<Result<Vec<_>>>()?;
 
-            Ok((values_seq, window_in_flow))
+            Ok((values_seq, late_tuple, window_in_flow))
         } else {
             let values = self
                 .projection_subtask
                 .run_without_aggr(&self.expr_resolver, &tuple)?;
-            Ok((vec![values], WindowInFlowByWindowTask::zero()))
+            Ok((vec![values], None, WindowInFlowByWindowTask::zero()))
         }
     }
 
     /// # Returns
     ///
-    /// None when input queue does not exist or is empty or JOIN op does not emit output yet.
-    fn run_lower_ops(
-        &self,
-        context: &TaskContext,
-    ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
+    /// - None when input queue does not exist or is empty or JOIN op does not emit output yet.
+    /// - Some((tuples, late tuples, in-queue metrics)) otherwise.
+    fn run_lower_ops(&self, context: &TaskContext) -> Result<Option<LowerOpsOut>> {
         match &self.join {
-            Some((join_subtask, right_collect_subtask)) => self.run_join(
-                context,
-                &self.left_collect_subtask,
-                right_collect_subtask,
-                join_subtask,
-            ),
-            None => self
+            Some((join_subtask, right_collect_subtasks)) => {
+                self.run_join(context, right_collect_subtasks, join_subtask)
+            }
+            None => Ok(self
                 .run_left_collect(context)
                 .map(|(tuple, metrics_collect)| {
                     (
                         vec![tuple],
+                        vec![],
                         InQueueMetricsUpdateByTask::new(
                             metrics_collect,
                             None, // single collect subtask does not use window yet
                         ),
                     )
-                }),
+                })),
         }
     }
 
-    /// JOIN takes tuples from left or right at a time.
+    /// JOIN takes a tuple from one of the joined streams at a time.
     ///
-    /// Left or right is determined randomly and if first candidate does not have tuple to collect, then the other is selected.
+    /// A stream is determined randomly and if first candidate does not have tuple to collect, then the others are selected.
     fn run_join(
         &self,
         context: &TaskContext,
-        left_collect_subtask: &CollectSubtask,
-        right_collect_subtask: &CollectSubtask,
+        right_collect_subtasks: &[CollectSubtask],
         join_subtask: &JoinSubtask,
-    ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
-        self.join_dir_candidates().into_iter().find_map(|dir| {
-            let collect_subtask = match dir {
-                JoinDir::Left => left_collect_subtask,
-                JoinDir::Right => right_collect_subtask,
+    ) -> Result<Option<LowerOpsOut>> {
+        for stream_idx in self.join_stream_candidates(right_collect_subtasks.len()) {
+            // The leftmost stream is the left of level 0, and the others are the right of each level.
+            let (collect_subtask, level, dir) = if stream_idx == 0 {
+                (&self.left_collect_subtask, 0, JoinDir::Left)
+            } else {
+                (
+                    &right_collect_subtasks[stream_idx - 1],
+                    stream_idx - 1,
+                    JoinDir::Right,
+                )
             };
-            self.run_join_core(context, collect_subtask, join_subtask, dir)
-        })
+            if let Some(res) =
+                self.run_join_core(context, collect_subtask, join_subtask, level, dir)?
+            {
+                return Ok(Some(res));
+            }
+        }
+        Ok(None)
     }
-    fn join_dir_candidates(&self) -> [JoinDir; 2] {
-        let first = [JoinDir::Left, JoinDir::Right]
+    /// Indices of joined streams (0 for the leftmost) starting from a random one.
+    fn join_stream_candidates(&self, n_levels: usize) -> Vec<usize> {
+        let n_streams = n_levels + 1;
+        let first = *(0..n_streams)
+            .collect::<Vec<_>>()
             .choose(&mut *self.rng.lock().expect("rng lock poisoned"))
             .expect("not empty");
-        if first == &JoinDir::Left {
-            [JoinDir::Left, JoinDir::Right]
-        } else {
-            [JoinDir::Right, JoinDir::Left]
-        }
+        (0..n_streams).map(|i| (first + i) % n_streams).collect()
     }
     fn run_join_core(
         &self,
         context: &TaskContext,
         collect_subtask: &CollectSubtask,
         join_subtask: &JoinSubtask,
+        level: usize,
         join_dir: JoinDir,
-    ) -> Option<(Vec<Tuple>, InQueueMetricsUpdateByTask)> {
+    ) -> Result<Option<LowerOpsOut>> {
         collect_subtask
             .run(context)
             .map(|(tuple, metrics_collect)| {
-                let (tuples, metrics_join) = join_subtask.run(&self.expr_resolver, tuple, join_dir);
+                let (tuples, late_tuple, metrics_join) =
+                    join_subtask.run(&self.expr_resolver, tuple, level, join_dir)?;
                 let metrics = InQueueMetricsUpdateByTask::new(metrics_collect, Some(metrics_join));
-                (tuples, metrics)
+                Ok((tuples, late_tuple.into_iter().collect(), metrics))
             })
+            .transpose()
     }
 
     fn run_left_collect(
@@ -323,9 +412,9 @@ impl QuerySubtask {
             .as_ref()
             .map(|subtask| subtask.get_window_mut())
     }
-    pub fn get_join_window_mut(&self) -> Option<MutexGuard<JoinWindow>> {
+    pub fn get_join_windows_mut(&self) -> Option<MutexGuard<Vec<JoinWindow>>> {
         self.join
             .as_ref()
-            .map(|(subtask, _)| subtask.get_window_mut())
+            .map(|(subtask, _)| subtask.get_windows_mut())
     }
 }
@@ -3,8 +3,9 @@
 use std::sync::{Mutex, MutexGuard};
 
 use crate::{
-    expr_resolver::ExprResolver,
-    pipeline::{WindowOperationParameter, WindowParameter},
+    api::error::Result,
+    expr_resolver::{ExprResolver, ValueExprLabel},
+    pipeline::{EmitPolicy, WindowOperationParameter, WindowParameter},
     stream_engine::autonomous_executor::{
         performance_metrics::WindowInFlowByWindowTask,
         task::{
@@ -15,28 +16,83 @@ use crate::{
 };
 
 #[derive(Debug)]
-pub struct GroupAggregateWindowSubtask(Mutex<AggrWindow>);
+pub struct GroupAggregateWindowSubtask {
+    window: Mutex<AggrWindow>,
+    having_condition: Option<ValueExprLabel>,
+}
 
 impl GroupAggregateWindowSubtask {
-    pub fn new(window_param: WindowParameter, op_param: WindowOperationParameter) -> Self {
-        let window = AggrWindow::new(window_param, op_param);
-        Self(Mutex::new(window))
+    pub fn new(
+        window_param: WindowParameter,
+        op_param: WindowOperationParameter,
+        emit_policy: EmitPolicy,
+    ) -> Self {
+        let having_condition = match &op_param {
+            WindowOperationParameter::Aggregate(aggregate_parameter) => aggregate_parameter.having,
+            WindowOperationParameter::Join(_) => None,
+        };
+        let window = AggrWindow::new(window_param, op_param, emit_policy);
+        Self {
+            window: Mutex::new(window),
+            having_condition,
+        }
     }
 
+    /// # Returns
+    ///
+    /// - Aggregation results of closed panes (and provisional results of open panes, depending on the emit policy),
+    ///   which satisfy the HAVING condition (NULL is evaluated as FALSE).
+    /// - `tuple` itself if it is too late to be dispatched to the window.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - an aggregate or GROUP BY expression fails to be evaluated (e.g. division by zero).
+    ///   - the HAVING condition cannot be evaluated as BOOLEAN.
     pub fn run(
         &self,
         expr_resolver: &ExprResolver,
         tuple: Tuple,
-    ) -> (Vec<AggregatedAndGroupingValues>, WindowInFlowByWindowTask) {
-        self.0
-            .lock()
-            .expect("another thread accessing to window gets poisoned")
-            .dispatch(expr_resolver, tuple, ())
-            .expect("dispatch failed")
+    ) -> Result<(
+        Vec<AggregatedAndGroupingValues>,
+        Option<Tuple>,
+        WindowInFlowByWindowTask,
+    )> {
+        let (aggregated_and_grouping_values_seq, window_in_flow) = {
+            let mut window = self
+                .window
+                .lock()
+                .expect("another thread accessing to window gets poisoned");
+            if window.is_late(&tuple) {
+                return Ok((Vec::new(), Some(tuple), WindowInFlowByWindowTask::zero()));
+            }
+            window.dispatch(expr_resolver, tuple, ())?
+        };
+
+        let aggregated_and_grouping_values_seq = match self.having_condition {
+            None => aggregated_and_grouping_values_seq,
+            Some(having_condition) => {
+                let mut filtered = Vec::with_capacity(aggregated_and_grouping_values_seq.len());
+                for aggregated_and_grouping_values in aggregated_and_grouping_values_seq {
+                    if expr_resolver
+                        .eval_value_expr_with_aggr(
+                            having_condition,
+                            &aggregated_and_grouping_values,
+                        )?
+                        .to_bool()?
+                    {
+                        filtered.push(aggregated_and_grouping_values);
+                    }
+                }
+                filtered
+            }
+        };
+
+        Ok((aggregated_and_grouping_values_seq, None, window_in_flow))
     }
 
     pub fn get_window_mut(&self) -> MutexGuard<AggrWindow> {
-        self.0
+        self.window
             .lock()
             .expect("another thread accessing to window gets poisoned")
     }
@@ -3,8 +3,9 @@
 use std::sync::{Mutex, MutexGuard};
 
 use crate::{
+    api::error::Result,
     expr_resolver::ExprResolver,
-    pipeline::{JoinParameter, WindowParameter},
+    pipeline::{EmitPolicy, JoinParameter, WindowParameter},
     stream_engine::autonomous_executor::{
         performance_metrics::WindowInFlowByWindowTask,
         task::{
@@ -14,31 +15,342 @@ use crate::{
     },
 };
 
+/// Left-deep join of streams.
+///
+/// ```text
+/// s0 JOIN s1 ON ... JOIN s2 ON ...
+///
+///             level 1
+///            /       \
+///       level 0       s2
+///      /       \
+///    s0         s1
+/// ```
+///
+/// Joined tuples of a level are the left input of the next level.
+/// Every level shares the same watermark, updated by rows from any stream.
 #[derive(Debug)]
-pub struct JoinSubtask(Mutex<JoinWindow>);
+pub struct JoinSubtask(Mutex<Vec<JoinWindow>>);
 
 impl JoinSubtask {
-    pub fn new(window_param: WindowParameter, join_param: JoinParameter) -> Self {
-        let window = JoinWindow::new(window_param, join_param);
-        Self(Mutex::new(window))
+    /// `levels` are ordered from the innermost join.
+    pub fn new(levels: Vec<(WindowParameter, JoinParameter, EmitPolicy)>) -> Self {
+        assert!(!levels.is_empty());
+        let windows = levels
+            .into_iter()
+            .map(|(window_param, join_param, emit_policy)| {
+                JoinWindow::new(window_param, join_param, emit_policy)
+            })
+            .collect();
+        Self(Mutex::new(windows))
     }
 
+    /// Dispatch `tuple` to `dir` side of the join at `level`.
+    ///
+    /// # Returns
+    ///
+    /// - Joined tuples of the outermost level.
+    /// - `tuple` itself if it is too late to be dispatched to the window.
+    ///
+    /// # Failures
+    ///
+    /// - Errors from dispatching tuples to windows or joining tuples in panes.
     pub fn run(
         &self,
         expr_resolver: &ExprResolver,
         tuple: Tuple,
+        level: usize,
         dir: JoinDir,
-    ) -> (Vec<Tuple>, WindowInFlowByWindowTask) {
-        self.0
-            .lock()
-            .expect("another thread accessing to window gets poisoned")
-            .dispatch(expr_resolver, tuple, dir)
-            .expect("dispatch failed")
+    ) -> Result<(Vec<Tuple>, Option<Tuple>, WindowInFlowByWindowTask)> {
+        let mut windows = self.get_windows_mut();
+
+        if windows[level].is_late(&tuple) {
+            return Ok((Vec::new(), Some(tuple), WindowInFlowByWindowTask::zero()));
+        }
+
+        let rowtime = tuple.rowtime().as_timestamp();
+        for window in windows.iter_mut() {
+            window.watermark_mut().update(rowtime);
+        }
+
+        let n_levels = windows.len();
+        let mut tuple = Some(tuple);
+        let mut forwarded = Vec::new();
+        let mut window_in_flow = WindowInFlowByWindowTask::zero();
+
+        for (i, window) in windows.iter_mut().enumerate() {
+            let mut inputs: Vec<(Tuple, JoinDir)> =
+                forwarded.drain(..).map(|t| (t, JoinDir::Left)).collect();
+            if i == level {
+                inputs.push((tuple.take().expect("dispatched only once"), dir));
+            }
+
+            for (input, input_dir) in inputs {
+                window_in_flow = window_in_flow
+                    + window
+                        .panes_mut()
+                        .dispatch(expr_resolver, &input, input_dir)?;
+            }
+
+            let (mut out, window_in_flow_close) = window.close_panes(expr_resolver)?;
+            window_in_flow = window_in_flow + window_in_flow_close;
+
+            if i + 1 == n_levels {
+                out.append(
+                    &mut window
+                        .panes_mut()
+                        .emit_provisional(expr_resolver, rowtime)?,
+                );
+            }
+            forwarded = out;
+        }
+
+        Ok((forwarded, None, window_in_flow))
     }
 
-    pub fn get_window_mut(&self) -> MutexGuard<JoinWindow> {
+    pub fn get_windows_mut(&self) -> MutexGuard<Vec<JoinWindow>> {
         self.0
             .lock()
             .expect("another thread accessing to window gets poisoned")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use springql_test_logger::setup_test_logger;
+
+    use crate::{
+        expression::ValueExpr,
+        pipeline::{ColumnName, ColumnReference, Field, JoinType, StreamName},
+        sql_processor::SelectFieldSyntax,
+        stream_engine::{
+            autonomous_executor::row::RowTime,
+            time::{SpringDuration, SpringEventDuration, SpringTimestamp},
+            NnSqlValue, SqlValue,
+        },
+    };
+
+    use super::*;
+
+    fn ts(s: &str) -> SpringTimestamp {
+        SpringTimestamp::from_str(&format!("2020-01-01 00:00:{}.000000000", s)).unwrap()
+    }
+
+    fn city_humidity_colrefs() -> Vec<ColumnReference> {
+        vec![
+            ColumnReference::factory("city_humidity", "ts"),
+            ColumnReference::factory("city_humidity", "humidity"),
+        ]
+    }
+
+    fn tuple_city_humidity(timestamp: SpringTimestamp, humidity: i32) -> Tuple {
+        let values = vec![
+            SqlValue::NotNull(NnSqlValue::Timestamp(timestamp)),
+            SqlValue::NotNull(NnSqlValue::Integer(humidity)),
+        ];
+        let fields = city_humidity_colrefs()
+            .into_iter()
+            .zip(values)
+            .map(|(colref, value)| Field::new(colref, value))
+            .collect();
+        Tuple::new(RowTime::EventTime(timestamp), fields)
+    }
+
+    fn t_expect(
+        tuple: &Tuple,
+        expected_timestamp: SpringTimestamp,
+        expected_amount: i16,
+        expected_temperature: i32,
+        expected_humidity: Option<i32>,
+    ) {
+        let get = |colref: ColumnReference| tuple.get_value(&colref).unwrap();
+
+        assert_eq!(
+            get(ColumnReference::fx_trade_timestamp())
+                .unwrap()
+                .unpack::<SpringTimestamp>()
+                .unwrap(),
+            expected_timestamp
+        );
+        assert_eq!(
+            get(ColumnReference::fx_trade_amount())
+                .unwrap()
+                .unpack::<i16>()
+                .unwrap(),
+            expected_amount
+        );
+        assert_eq!(
+            get(ColumnReference::fx_city_temperature_temperature())
+                .unwrap()
+                .unpack::<i32>()
+                .unwrap(),
+            expected_temperature
+        );
+        match get(ColumnReference::factory("city_humidity", "humidity")) {
+            SqlValue::Null => assert!(expected_humidity.is_none()),
+            SqlValue::NotNull(h) => assert_eq!(
+                h.unpack::<i32>().unwrap(),
+                expected_humidity.expect("joined tuple has non-NULL humidity")
+            ),
+        }
+    }
+
+    /// SELECT trade.ts, trade.amount, city_temperature.temperature, city_humidity.humidity
+    ///   FROM trade
+    ///   LEFT OUTER JOIN city_temperature
+    ///     ON trade.ts = city_temperature.ts
+    ///   LEFT OUTER JOIN city_humidity
+    ///     ON trade.ts = city_humidity.ts
+    ///   FIXED WINDOW DURATION_SECS(10), DURATION_SECS(5);
+    fn fx_join_subtask() -> (ExprResolver, JoinSubtask) {
+        let trade_timestamp_expr = ValueExpr::factory_colref(
+            StreamName::fx_trade().as_ref(),
+            ColumnName::fx_timestamp().as_ref(),
+        );
+
+        let select_list = vec![
+            trade_timestamp_expr.clone(),
+            ValueExpr::factory_colref(
+                StreamName::fx_trade().as_ref(),
+                ColumnName::fx_amount().as_ref(),
+            ),
+            ValueExpr::factory_colref(
+                StreamName::fx_city_temperature().as_ref(),
+                ColumnName::fx_temperature().as_ref(),
+            ),
+            ValueExpr::factory_colref("city_humidity", "humidity"),
+        ]
+        .into_iter()
+        .map(|value_expr| SelectFieldSyntax::ValueExpr {
+            value_expr,
+            alias: None,
+        })
+        .collect();
+
+        let (mut expr_resolver, _) = ExprResolver::new(select_list);
+
+        let on_expr_0 = expr_resolver.register_value_expr(ValueExpr::factory_eq(
+            trade_timestamp_expr.clone(),
+            ValueExpr::factory_colref(
+                StreamName::fx_city_temperature().as_ref(),
+                ColumnName::fx_timestamp().as_ref(),
+            ),
+        ));
+        let on_expr_1 = expr_resolver.register_value_expr(ValueExpr::factory_eq(
+            trade_timestamp_expr,
+            ValueExpr::factory_colref("city_humidity", "ts"),
+        ));
+
+        let trade_colrefs = vec![
+            ColumnReference::fx_trade_timestamp(),
+            ColumnReference::fx_trade_ticker(),
+            ColumnReference::fx_trade_amount(),
+        ];
+        let city_temperature_colrefs = vec![
+            ColumnReference::fx_city_temperature_timestamp(),
+            ColumnReference::fx_city_temperature_city(),
+            ColumnReference::fx_city_temperature_temperature(),
+        ];
+
+        let window_param = WindowParameter::TimedFixedWindow {
+            length: SpringEventDuration::from_secs(10),
+            allowed_delay: SpringEventDuration::from_secs(5),
+        };
+
+        let subtask = JoinSubtask::new(vec![
+            (
+                window_param.clone(),
+                JoinParameter {
+                    join_type: JoinType::LeftOuter,
+                    left_colrefs: trade_colrefs.clone(),
+                    right_colrefs: city_temperature_colrefs.clone(),
+                    on_expr: on_expr_0,
+                    equi_keys: vec![],
+                },
+                EmitPolicy::Final,
+            ),
+            (
+                window_param,
+                JoinParameter {
+                    join_type: JoinType::LeftOuter,
+                    left_colrefs: trade_colrefs
+                        .into_iter()
+                        .chain(city_temperature_colrefs)
+                        .collect(),
+                    right_colrefs: city_humidity_colrefs(),
+                    on_expr: on_expr_1,
+                    equi_keys: vec![],
+                },
+                EmitPolicy::Final,
+            ),
+        ]);
+
+        (expr_resolver, subtask)
+    }
+
+    #[test]
+    fn test_3_streams_join() {
+        setup_test_logger();
+
+        let (expr_resolver, subtask) = fx_join_subtask();
+
+        let run = |tuple: Tuple, level: usize, dir: JoinDir| {
+            let (out, late, _) = subtask.run(&expr_resolver, tuple, level, dir).unwrap();
+            assert!(late.is_none());
+            out
+        };
+
+        // level 0 [:00, :10): t(:00, 100), c(:00, -3)
+        assert!(run(
+            Tuple::factory_trade(ts("00"), "ORCL", 100),
+            0,
+            JoinDir::Left
+        )
+        .is_empty());
+        assert!(run(
+            Tuple::factory_city_temperature(ts("00"), "Tokyo", -3),
+            0,
+            JoinDir::Right
+        )
+        .is_empty());
+
+        // level 1 [:10, :20): h(:12, 40)
+        assert!(run(tuple_city_humidity(ts("12"), 40), 1, JoinDir::Right).is_empty());
+
+        // level 0 [:10, :20): t(:12, 200), c(:12, 5)
+        assert!(run(
+            Tuple::factory_trade(ts("12"), "GOOGL", 200),
+            0,
+            JoinDir::Left
+        )
+        .is_empty());
+        assert!(run(
+            Tuple::factory_city_temperature(ts("12"), "Tokyo", 5),
+            0,
+            JoinDir::Right
+        )
+        .is_empty());
+
+        // watermark :11 closes level 0 [:00, :10) -> tc(:00, 100, -3)
+        // tc(:00) is forwarded to level 1, where [:00, :10) is inserted before [:10, :20) and closed.
+        let out = run(
+            Tuple::factory_city_temperature(ts("16"), "Osaka", 9),
+            0,
+            JoinDir::Right,
+        );
+        assert_eq!(out.len(), 1);
+        t_expect(&out[0], ts("00"), 100, -3, None);
+
+        // watermark :25 closes level 0 [:10, :20) -> tc(:12, 200, 5)
+        // tc(:12) is forwarded to level 1 and joined with h(:12, 40).
+        let out = run(
+            Tuple::factory_trade(ts("30"), "ORCL", 300),
+            0,
+            JoinDir::Left,
+        );
+        assert_eq!(out.len(), 1);
+        t_expect(&out[0], ts("12"), 200, 5, Some(40));
+    }
+}
@@ -36,25 +36,29 @@ impl ProjectionSubtask {
     }
 
     /// Projection for SELECT with aggregate.
-    /// select_list must only have GROUP BY elements or aggregate expressions.
+    /// select_list must only have GROUP BY elements, aggregate expressions or value expressions of aggregate expressions.
     /// (Column reference without aggregate is not allowed.)
     pub fn run_with_aggr(
         &self,
+        expr_resolver: &ExprResolver,
         aggregated_and_grouping_values: AggregatedAndGroupingValues,
     ) -> Result<SqlValues> {
         let values = self
             .exprs
             .iter()
-            .map(|label| {
-                let value = match label {
-                    ExprLabel::Value(group_by_value_label) => {
-                        aggregated_and_grouping_values.get_group_by_value(group_by_value_label)
-                    }
-                    ExprLabel::Aggr(aggr_label) => {
-                        aggregated_and_grouping_values.get_aggregated_value(aggr_label)
+            .map(|label| match label {
+                ExprLabel::Value(value_label) => {
+                    match aggregated_and_grouping_values.get_group_by_value(value_label) {
+                        Ok(group_by_value) => Ok(group_by_value.clone()),
+                        Err(_) => expr_resolver.eval_value_expr_with_aggr(
+                            *value_label,
+                            &aggregated_and_grouping_values,
+                        ),
                     }
-                }?;
-                Ok(value.clone())
+                }
+                ExprLabel::Aggr(aggr_label) => aggregated_and_grouping_values
+                    .get_aggregated_value(aggr_label)
+                    .cloned(),
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -0,0 +1,64 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+use crate::{
+    api::error::Result,
+    expr_resolver::ExprResolver,
+    pipeline::{Field, JoinType},
+    stream_engine::{
+        autonomous_executor::task::{task_context::TaskContext, tuple::Tuple},
+        command::TableLookupOp,
+        SqlValue,
+    },
+};
+
+#[derive(Debug, new)]
+pub struct TableLookupSubtask {
+    op: TableLookupOp,
+}
+
+impl TableLookupSubtask {
+    /// Joins `tuple` with each table row whose key column is equal to the probe value.
+    ///
+    /// # Returns
+    ///
+    /// Joined tuples (using `tuple`'s rowtime).
+    /// For LEFT OUTER JOIN, `tuple` with NULL table columns when no table row matches.
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - the probe expression cannot be evaluated.
+    pub fn run(
+        &self,
+        expr_resolver: &ExprResolver,
+        tuple: Tuple,
+        context: &TaskContext,
+    ) -> Result<Vec<Tuple>> {
+        let key = expr_resolver.eval_value_expr(self.op.probe, &tuple)?;
+
+        let table = context.repos().table_repository().get(&self.op.table);
+        let matched = table.lookup(&self.op.key_column, &key);
+
+        match (matched.is_empty(), self.op.join_type) {
+            (true, JoinType::LeftOuter) => {
+                let null_table_tuple = self.null_table_tuple(&tuple);
+                Ok(vec![tuple.join(null_table_tuple)])
+            }
+            _ => Ok(matched
+                .into_iter()
+                .map(|table_tuple| tuple.clone().join(table_tuple.clone()))
+                .collect()),
+        }
+    }
+
+    fn null_table_tuple(&self, tuple: &Tuple) -> Tuple {
+        let fields = self
+            .op
+            .table_colrefs
+            .iter()
+            .map(|colref| Field::new(colref.clone(), SqlValue::Null))
+            .collect();
+
+        Tuple::new(tuple.rowtime(), fields)
+    }
+}
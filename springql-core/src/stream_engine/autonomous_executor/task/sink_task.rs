@@ -70,7 +70,7 @@ impl SinkTask {
         let execution_time = stopwatch.stop();
 
         let out_queues_metrics = vec![];
-        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
+        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time, 0);
         let metrics =
             MetricsUpdateByTaskExecution::new(task_metrics, in_queues_metrics, out_queues_metrics);
         Ok(TaskRunResult {
@@ -66,7 +66,7 @@ impl SourceTask {
 
         let execution_time = stopwatch.stop();
 
-        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time);
+        let task_metrics = TaskMetricsUpdateByTask::new(context.task(), execution_time, 0);
         let metrics =
             MetricsUpdateByTaskExecution::new(task_metrics, vec![], out_queue_metrics_seq);
         Ok(TaskRunResult {
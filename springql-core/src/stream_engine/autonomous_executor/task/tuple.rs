@@ -3,7 +3,7 @@
 use crate::{
     api::error::{Result, SpringError},
     mem_size::MemSize,
-    pipeline::{ColumnReference, Field},
+    pipeline::{ColumnName, ColumnReference, Field},
     stream_engine::{
         autonomous_executor::row::{RowTime, StreamRow},
         NnSqlValue, SqlValue,
@@ -81,6 +81,18 @@ impl Tuple {
             .ok_or_else(|| SpringError::Sql(anyhow!("cannot find field `{:?}`", column_reference)))
     }
 
+    /// Value of the column named `column_name`, whichever stream it comes from.
+    ///
+    /// `None` if no field has the column name.
+    pub fn get_value_by_column_name(&self, column_name: &ColumnName) -> Option<SqlValue> {
+        self.fields.iter().find_map(|field| match field.name() {
+            ColumnReference::Column {
+                column_name: name, ..
+            } if name == column_name => Some(field.sql_value().clone()),
+            _ => None,
+        })
+    }
+
     /// # Failures
     ///
     /// `SpringError::Sql` if `column_reference` does not match any field.
@@ -110,6 +122,8 @@ impl Tuple {
 
 #[cfg(test)]
 mod tests {
+    use ordered_float::OrderedFloat;
+
     use crate::{
         expression::{UnaryOperator, ValueExpr},
         stream_engine::time::SpringTimestamp,
@@ -149,6 +163,34 @@ mod tests {
             TestDatum::new(
                 ValueExpr::factory_eq(ValueExpr::factory_null(), ValueExpr::factory_null()),
                 Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_lt(ValueExpr::factory_null(), ValueExpr::factory_integer(1)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::Null,
+            ),
+            TestDatum::new(
+                ValueExpr::factory_lt(ValueExpr::factory_integer(1), ValueExpr::factory_integer(2)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_ge(ValueExpr::factory_integer(2), ValueExpr::factory_integer(2)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(true),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_ne(ValueExpr::factory_integer(1), ValueExpr::factory_integer(1)),
+                Tuple::fx_trade_oracle(),
+                SqlValue::factory_bool(false),
+            ),
+            TestDatum::new(
+                ValueExpr::factory_ge(
+                    ValueExpr::ColumnReference(ColumnReference::factory("trade", "amount")),
+                    ValueExpr::Constant(SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(100.5)))),
+                ),
+                Tuple::factory_trade(SpringTimestamp::fx_ts1(), "ORCL", 100),
                 SqlValue::factory_bool(false),
             ),
             TestDatum::new(
@@ -200,7 +242,11 @@ mod tests {
         for t in test_data {
             let expr_ph2 = t.in_expr.resolve_colref(&t.tuple).unwrap();
             let sql_value = expr_ph2.eval().unwrap();
-            assert_eq!(sql_value, t.expected_sql_value);
+            match t.expected_sql_value {
+                // NULL != NULL
+                SqlValue::Null => assert!(matches!(sql_value, SqlValue::Null)),
+                SqlValue::NotNull(_) => assert_eq!(sql_value, t.expected_sql_value),
+            }
         }
     }
 }
@@ -34,6 +34,14 @@ pub trait Window {
 
     fn purge(&mut self);
 
+    /// Too late tuple does not have any chance to be dispatched nor to close a pane.
+    ///
+    /// Count-based windows do not use watermark and never get late tuples.
+    fn is_late(&self, tuple: &Tuple) -> bool {
+        !self.panes().is_count_based()
+            && tuple.rowtime().as_timestamp() < self.watermark().as_timestamp()
+    }
+
     /// A task dispatches a tuple from waiting queue.
     fn dispatch(
         &mut self,
@@ -43,35 +51,37 @@ pub trait Window {
     ) -> Result<Success<<Self::Pane as Pane>::CloseOut>, SpringError> {
         let rowtime = tuple.rowtime().as_timestamp();
 
-        if rowtime < self.watermark().as_timestamp() {
-            // too late tuple does not have any chance to be dispatched nor to close a pane.
+        if self.is_late(&tuple) {
             Ok((Vec::new(), WindowInFlowByWindowTask::zero()))
         } else {
             self.watermark_mut().update(rowtime);
-            let wm = *self.watermark();
-
-            let window_in_flow_dispatch = self
-                .panes_mut()
-                .panes_to_dispatch(rowtime)?
-                .map(|pane| pane.dispatch(expr_resolver, &tuple, arg.clone()))
-                .fold(WindowInFlowByWindowTask::zero(), |acc, window_in_flow| {
-                    acc + window_in_flow
-                });
-
-            let (out, window_in_flow_close) = self
-                .panes_mut()
-                .remove_panes_to_close(&wm)
-                .into_iter()
-                .fold(
-                    (Vec::new(), WindowInFlowByWindowTask::zero()),
-                    |(mut out_acc, window_in_flow_acc), pane| {
-                        let (mut out_seq, window_in_flow) = pane.close(expr_resolver);
-                        out_acc.append(&mut out_seq);
-                        (out_acc, window_in_flow_acc + window_in_flow)
-                    },
-                );
+
+            let window_in_flow_dispatch = self.panes_mut().dispatch(expr_resolver, &tuple, arg)?;
+
+            let (mut out, window_in_flow_close) = self.close_panes(expr_resolver)?;
+
+            out.append(&mut self.panes_mut().emit_provisional(expr_resolver, rowtime)?);
 
             Ok((out, window_in_flow_dispatch + window_in_flow_close))
         }
     }
+
+    /// Closes panes which the current watermark (or the number of rows for count-based windows) has passed.
+    fn close_panes(
+        &mut self,
+        expr_resolver: &ExprResolver,
+    ) -> Result<Success<<Self::Pane as Pane>::CloseOut>, SpringError> {
+        let wm = *self.watermark();
+        self.panes_mut()
+            .remove_panes_to_close(&wm)
+            .into_iter()
+            .try_fold(
+                (Vec::new(), WindowInFlowByWindowTask::zero()),
+                |(mut out_acc, window_in_flow_acc), pane| {
+                    let (mut out_seq, window_in_flow) = pane.close(expr_resolver)?;
+                    out_acc.append(&mut out_seq);
+                    Ok((out_acc, window_in_flow_acc + window_in_flow))
+                },
+            )
+    }
 }
@@ -7,7 +7,7 @@ use anyhow::anyhow;
 use crate::{
     api::error::{Result, SpringError},
     expr_resolver::{AggrExprLabel, ValueExprLabel},
-    pipeline::{WindowOperationParameter, WindowParameter},
+    pipeline::{EmitPolicy, WindowOperationParameter, WindowParameter},
     stream_engine::{
         autonomous_executor::task::window::{
             panes::{AggrPane, Panes},
@@ -90,11 +90,15 @@ impl Window for AggrWindow {
 }
 
 impl AggrWindow {
-    pub fn new(window_param: WindowParameter, op_param: WindowOperationParameter) -> Self {
+    pub fn new(
+        window_param: WindowParameter,
+        op_param: WindowOperationParameter,
+        emit_policy: EmitPolicy,
+    ) -> Self {
         let watermark = Watermark::new(window_param.allowed_delay());
         Self {
             watermark,
-            panes: Panes::new(window_param, op_param),
+            panes: Panes::new(window_param, op_param, emit_policy),
         }
     }
 }
@@ -111,8 +115,8 @@ mod tests {
         expr_resolver::{ExprLabel, ExprResolver},
         expression::{AggrExpr, ValueExpr},
         pipeline::{
-            AggrAlias, AggregateFunctionParameter, AggregateParameter, ColumnName, GroupByLabels,
-            StreamName,
+            AggrAlias, AggregateExprParameter, AggregateFunctionParameter, AggregateParameter,
+            ColumnName, GroupByLabels, StreamName,
         },
         sql_processor::SelectFieldSyntax,
         stream_engine::{
@@ -181,6 +185,7 @@ mod tests {
                 StreamName::fx_trade().as_ref(),
                 ColumnName::fx_amount().as_ref(),
             ),
+            by: None,
         };
 
         let select_list = vec![
@@ -204,10 +209,14 @@ mod tests {
                         allowed_delay: SpringEventDuration::from_secs(1),
                     },
                     WindowOperationParameter::Aggregate(AggregateParameter {
-                        aggr_func: AggregateFunctionParameter::Avg,
-                        aggr_expr: aggr_label,
+                        aggr_exprs: vec![AggregateExprParameter::new(
+                            AggregateFunctionParameter::Avg,
+                            aggr_label,
+                        )],
                         group_by: GroupByLabels::new(vec![group_by_label]),
+                        having: None,
                     }),
+                    EmitPolicy::Final,
                 );
 
                 // [:55, :05): ("GOOGL", 100)
@@ -429,6 +438,7 @@ mod tests {
                 StreamName::fx_trade().as_ref(),
                 ColumnName::fx_amount().as_ref(),
             ),
+            by: None,
         };
 
         let select_list = vec![
@@ -451,10 +461,14 @@ mod tests {
                         allowed_delay: SpringEventDuration::from_secs(1),
                     },
                     WindowOperationParameter::Aggregate(AggregateParameter {
-                        aggr_func: AggregateFunctionParameter::Avg,
-                        aggr_expr: aggr_label,
+                        aggr_exprs: vec![AggregateExprParameter::new(
+                            AggregateFunctionParameter::Avg,
+                            aggr_label,
+                        )],
                         group_by: GroupByLabels::new(vec![group_by_label]),
+                        having: None,
                     }),
+                    EmitPolicy::Final,
                 );
 
                 // [:00, :10): ("GOOGL", 100)
@@ -1,7 +1,7 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::{
-    pipeline::{JoinParameter, WindowOperationParameter, WindowParameter},
+    pipeline::{EmitPolicy, JoinParameter, WindowOperationParameter, WindowParameter},
     stream_engine::autonomous_executor::task::window::{
         panes::{JoinPane, Panes},
         watermark::Watermark,
@@ -40,11 +40,19 @@ impl Window for JoinWindow {
 }
 
 impl JoinWindow {
-    pub fn new(window_param: WindowParameter, join_param: JoinParameter) -> Self {
+    pub fn new(
+        window_param: WindowParameter,
+        join_param: JoinParameter,
+        emit_policy: EmitPolicy,
+    ) -> Self {
         let watermark = Watermark::new(window_param.allowed_delay());
         Self {
             watermark,
-            panes: Panes::new(window_param, WindowOperationParameter::Join(join_param)),
+            panes: Panes::new(
+                window_param,
+                WindowOperationParameter::Join(join_param),
+                emit_policy,
+            ),
         }
     }
 }
@@ -57,7 +65,7 @@ mod tests {
 
     use crate::{
         expr_resolver::ExprResolver,
-        expression::ValueExpr,
+        expression::{BinaryExpr, NumericalFunction, ValueExpr},
         pipeline::{ColumnName, ColumnReference, JoinParameter, JoinType, StreamName},
         sql_processor::SelectFieldSyntax,
         stream_engine::{
@@ -102,16 +110,18 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_timed_fixed_window_left_out_join() {
-        setup_test_logger();
-
-        // SELECT trade.timestamp, trade.amount, city_temperature.temperature
-        //   FROM trade
-        //   LEFT OUTER JOIN city_temperature
-        //   ON trade.timestamp = city_temperature.timestamp
-        //   FIXED WINDOW duration_secs(10), duration_secs(1);
-
+    /// SELECT trade.timestamp, trade.amount, city_temperature.temperature
+    ///   FROM trade
+    ///   <join_type> city_temperature
+    ///   ON trade.timestamp = city_temperature.timestamp
+    ///   <window_param>;
+    ///
+    /// Right tuples are probed by timestamp if `hash_join` is true.
+    fn fx_join_window(
+        join_type: JoinType,
+        window_param: WindowParameter,
+        hash_join: bool,
+    ) -> (ExprResolver, JoinWindow) {
         let trade_timestamp_expr = ValueExpr::factory_colref(
             StreamName::fx_trade().as_ref(),
             ColumnName::fx_timestamp().as_ref(),
@@ -131,12 +141,12 @@ mod tests {
         );
         let on_expr = ValueExpr::factory_eq(
             trade_timestamp_expr.clone(),
-            city_temperature_timestamp_expr,
+            city_temperature_timestamp_expr.clone(),
         );
 
         let select_list = vec![
             SelectFieldSyntax::ValueExpr {
-                value_expr: trade_timestamp_expr,
+                value_expr: trade_timestamp_expr.clone(),
                 alias: None,
             },
             SelectFieldSyntax::ValueExpr {
@@ -152,14 +162,19 @@ mod tests {
         let (mut expr_resolver, _) = ExprResolver::new(select_list);
 
         let on_expr_label = expr_resolver.register_value_expr(on_expr);
+        let equi_keys = if hash_join {
+            vec![(
+                expr_resolver.register_value_expr(trade_timestamp_expr),
+                expr_resolver.register_value_expr(city_temperature_timestamp_expr),
+            )]
+        } else {
+            vec![]
+        };
 
-        let mut window = JoinWindow::new(
-            WindowParameter::TimedFixedWindow {
-                length: SpringEventDuration::from_secs(10),
-                allowed_delay: SpringEventDuration::from_secs(1),
-            },
+        let window = JoinWindow::new(
+            window_param,
             JoinParameter {
-                join_type: JoinType::LeftOuter,
+                join_type,
                 left_colrefs: vec![
                     ColumnReference::fx_trade_timestamp(),
                     ColumnReference::fx_trade_ticker(),
@@ -171,7 +186,25 @@ mod tests {
                     ColumnReference::fx_city_temperature_temperature(),
                 ],
                 on_expr: on_expr_label,
+                equi_keys,
             },
+            EmitPolicy::Final,
+        );
+
+        (expr_resolver, window)
+    }
+
+    #[test]
+    fn test_timed_fixed_window_left_out_join() {
+        setup_test_logger();
+
+        let (expr_resolver, mut window) = fx_join_window(
+            JoinType::LeftOuter,
+            WindowParameter::TimedFixedWindow {
+                length: SpringEventDuration::from_secs(10),
+                allowed_delay: SpringEventDuration::from_secs(1),
+            },
+            false,
         );
 
         // [:00, :10): t(:00, 100)
@@ -311,4 +344,227 @@ mod tests {
         assert!(window_in_flow.window_gain_bytes_rows < 0);
         assert_eq!(window_in_flow.window_gain_bytes_states, 0);
     }
+
+    #[test]
+    fn test_rows_fixed_window_left_out_join() {
+        setup_test_logger();
+
+        let (expr_resolver, mut window) = fx_join_window(
+            JoinType::LeftOuter,
+            WindowParameter::RowsFixedWindow { length: 3 },
+            false,
+        );
+
+        let t = |ts: &str, amount: i16| {
+            Tuple::factory_trade(SpringTimestamp::from_str(ts).unwrap(), "", amount)
+        };
+        let c = |ts: &str, temperature: i32| {
+            Tuple::factory_city_temperature(SpringTimestamp::from_str(ts).unwrap(), "", temperature)
+        };
+
+        // rows 1-3: t(:10, 100), c(:10, 10), t(:00, 200) <-- rowtime does not matter
+        let (out, _) = window
+            .dispatch(
+                &expr_resolver,
+                t("2020-01-01 00:00:10.000000000", 100),
+                JoinDir::Left,
+            )
+            .unwrap();
+        assert!(out.is_empty());
+        let (out, _) = window
+            .dispatch(
+                &expr_resolver,
+                c("2020-01-01 00:00:10.000000000", 10),
+                JoinDir::Right,
+            )
+            .unwrap();
+        assert!(out.is_empty());
+        let (out, window_in_flow) = window
+            .dispatch(
+                &expr_resolver,
+                t("2020-01-01 00:00:00.000000000", 200),
+                JoinDir::Left,
+            )
+            .unwrap();
+        assert_eq!(out.len(), 2);
+        t_expect(
+            out.first().cloned().unwrap(),
+            SpringTimestamp::from_str("2020-01-01 00:00:10.000000000").unwrap(),
+            100,
+            Some(10),
+        );
+        t_expect(
+            out.get(1).cloned().unwrap(),
+            SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap(),
+            200,
+            None,
+        );
+        assert!(window_in_flow.window_gain_bytes_rows < 0);
+
+        // rows 4-: the right row in the previous pane is not joined
+        let (out, _) = window
+            .dispatch(
+                &expr_resolver,
+                t("2020-01-01 00:00:10.000000000", 300),
+                JoinDir::Left,
+            )
+            .unwrap();
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_join_types() {
+        setup_test_logger();
+
+        let ts = |s: &str| SpringTimestamp::from_str(s).unwrap();
+        let amount_and_temperature = |tuple: &Tuple| {
+            let amount = tuple
+                .get_value(&ColumnReference::fx_trade_amount())
+                .unwrap();
+            let temperature = tuple
+                .get_value(&ColumnReference::fx_city_temperature_temperature())
+                .unwrap();
+            let unpack = |v: SqlValue| match v {
+                SqlValue::Null => None,
+                SqlValue::NotNull(v) => Some(v.unpack::<i32>().unwrap()),
+            };
+            (unpack(amount), unpack(temperature))
+        };
+
+        for (join_type, expected) in [
+            (JoinType::Inner, vec![(Some(100), Some(10))]),
+            (
+                JoinType::LeftOuter,
+                vec![(Some(100), Some(10)), (Some(200), None)],
+            ),
+            (
+                JoinType::RightOuter,
+                vec![(Some(100), Some(10)), (None, Some(20))],
+            ),
+            (
+                JoinType::FullOuter,
+                vec![(Some(100), Some(10)), (Some(200), None), (None, Some(20))],
+            ),
+        ] {
+            for hash_join in [false, true] {
+                let (expr_resolver, mut window) = fx_join_window(
+                    join_type,
+                    WindowParameter::RowsFixedWindow { length: 4 },
+                    hash_join,
+                );
+
+                let mut out = Vec::new();
+                for (tuple, dir) in [
+                    (
+                        Tuple::factory_trade(ts("2020-01-01 00:00:00.000000000"), "", 100),
+                        JoinDir::Left,
+                    ),
+                    (
+                        Tuple::factory_trade(ts("2020-01-01 00:00:01.000000000"), "", 200),
+                        JoinDir::Left,
+                    ),
+                    (
+                        Tuple::factory_city_temperature(
+                            ts("2020-01-01 00:00:00.000000000"),
+                            "",
+                            10,
+                        ),
+                        JoinDir::Right,
+                    ),
+                    (
+                        Tuple::factory_city_temperature(
+                            ts("2020-01-01 00:00:02.000000000"),
+                            "",
+                            20,
+                        ),
+                        JoinDir::Right,
+                    ),
+                ] {
+                    let (mut o, _) = window.dispatch(&expr_resolver, tuple, dir).unwrap();
+                    out.append(&mut o);
+                }
+
+                assert_eq!(
+                    out.iter().map(amount_and_temperature).collect::<Vec<_>>(),
+                    expected,
+                    "{:?} (hash join: {})",
+                    join_type,
+                    hash_join
+                );
+
+                // unmatched right tuple keeps its rowtime
+                if join_type == JoinType::RightOuter {
+                    assert_eq!(
+                        out.last().unwrap().rowtime().as_timestamp(),
+                        ts("2020-01-01 00:00:02.000000000")
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_equi_key_evaluation_error() {
+        setup_test_logger();
+
+        // ON trade.amount / 0 = city_temperature.temperature
+        let left_key = ValueExpr::BinaryExpr(BinaryExpr::NumericalFunctionVariant(
+            NumericalFunction::DivVariant {
+                left: Box::new(ValueExpr::factory_colref(
+                    StreamName::fx_trade().as_ref(),
+                    ColumnName::fx_amount().as_ref(),
+                )),
+                right: Box::new(ValueExpr::factory_integer(0)),
+            },
+        ));
+        let right_key = ValueExpr::factory_colref(
+            StreamName::fx_city_temperature().as_ref(),
+            ColumnName::fx_temperature().as_ref(),
+        );
+
+        let (mut expr_resolver, _) = ExprResolver::new(vec![]);
+        let on_expr = expr_resolver
+            .register_value_expr(ValueExpr::factory_eq(left_key.clone(), right_key.clone()));
+        let equi_keys = vec![(
+            expr_resolver.register_value_expr(left_key),
+            expr_resolver.register_value_expr(right_key),
+        )];
+
+        let mut window = JoinWindow::new(
+            WindowParameter::RowsFixedWindow { length: 2 },
+            JoinParameter {
+                join_type: JoinType::Inner,
+                left_colrefs: vec![
+                    ColumnReference::fx_trade_timestamp(),
+                    ColumnReference::fx_trade_ticker(),
+                    ColumnReference::fx_trade_amount(),
+                ],
+                right_colrefs: vec![
+                    ColumnReference::fx_city_temperature_timestamp(),
+                    ColumnReference::fx_city_temperature_city(),
+                    ColumnReference::fx_city_temperature_temperature(),
+                ],
+                on_expr,
+                equi_keys,
+            },
+            EmitPolicy::Final,
+        );
+
+        let ts = SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap();
+        window
+            .dispatch(
+                &expr_resolver,
+                Tuple::factory_trade(ts, "", 100),
+                JoinDir::Left,
+            )
+            .unwrap();
+        // closes the pane of 2 rows
+        assert!(window
+            .dispatch(
+                &expr_resolver,
+                Tuple::factory_city_temperature(ts, "", 10),
+                JoinDir::Right,
+            )
+            .is_err());
+    }
 }
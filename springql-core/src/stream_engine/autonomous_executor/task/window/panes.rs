@@ -8,13 +8,63 @@ use std::cmp::Ordering;
 
 use crate::{
     api::SpringError,
-    pipeline::{WindowOperationParameter, WindowParameter},
+    expr_resolver::ExprResolver,
+    pipeline::{EmitPolicy, WindowOperationParameter, WindowParameter},
     stream_engine::{
-        autonomous_executor::task::window::watermark::Watermark,
-        time::{SpringDuration, SpringTimestamp},
+        autonomous_executor::{
+            performance_metrics::WindowInFlowByWindowTask, task::window::watermark::Watermark,
+        },
+        time::{SpringDuration, SpringEventDuration, SpringTimestamp},
+        Tuple,
     },
 };
 
+#[derive(Debug)]
+enum PaneKey<K> {
+    Timed,
+    Session(K),
+    /// Index of the first row in count-based windows.
+    Rows(u64),
+}
+
+#[derive(Debug)]
+struct KeyedPane<P>
+where
+    P: Pane,
+{
+    key: PaneKey<P::SessionKey>,
+    pane: P,
+
+    /// Whether the pane got rows since its last emission.
+    updated: bool,
+    /// Whether the pane has emitted provisional results.
+    emitted: bool,
+}
+
+impl<P> KeyedPane<P>
+where
+    P: Pane,
+{
+    fn new(key: PaneKey<P::SessionKey>, pane: P) -> Self {
+        Self {
+            key,
+            pane,
+            updated: false,
+            emitted: false,
+        }
+    }
+
+    fn dispatch(
+        &mut self,
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+        arg: P::DispatchArg,
+    ) -> Result<WindowInFlowByWindowTask, SpringError> {
+        self.updated = true;
+        self.pane.dispatch(expr_resolver, tuple, arg)
+    }
+}
+
 #[derive(Debug)]
 pub struct Panes<P>
 where
@@ -22,39 +72,66 @@ where
 {
     /// FIXME want to use `LinkedList::drain_filter` but it's unstable.
     ///
-    /// Sorted by `Pane::open_at`.
-    panes: Vec<P>,
+    /// Sorted by `Pane::open_at` for time-based windows (not sorted for session windows).
+    panes: Vec<KeyedPane<P>>,
+
+    /// Number of rows dispatched so far (only for count-based windows).
+    n_rows: u64,
+
+    /// Rowtime floored by the period of the last emission (only for `EmitPolicy::Every`).
+    last_emission_tick: Option<SpringTimestamp>,
 
     window_param: WindowParameter,
     op_param: WindowOperationParameter,
+    emit_policy: EmitPolicy,
 }
 
 impl<P> Panes<P>
 where
     P: Pane,
 {
-    pub fn new(window_param: WindowParameter, op_param: WindowOperationParameter) -> Self {
+    pub fn new(
+        window_param: WindowParameter,
+        op_param: WindowOperationParameter,
+        emit_policy: EmitPolicy,
+    ) -> Self {
         Self {
             panes: vec![],
+            n_rows: 0,
+            last_emission_tick: None,
             window_param,
             op_param,
+            emit_policy,
         }
     }
 
-    /// Generate new panes if not exists.
-    /// Then, return all panes to get a tuple with the `rowtime`.
+    /// Generate new panes if not exists (or merge session panes the tuple bridges).
+    /// Then, dispatch the tuple to all panes to get it.
     ///
-    /// Caller must assure rowtime is not smaller than watermark.
-    pub fn panes_to_dispatch(
+    /// Caller must assure rowtime is not smaller than watermark, except for tuples forwarded from inner join windows.
+    pub fn dispatch(
         &mut self,
-        rowtime: SpringTimestamp,
-    ) -> Result<impl Iterator<Item = &mut P>, SpringError> {
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+        arg: P::DispatchArg,
+    ) -> Result<WindowInFlowByWindowTask, SpringError> {
+        let rowtime = tuple.rowtime().as_timestamp();
+
+        if let WindowParameter::SessionWindow { gap, .. } = self.window_param {
+            return self.dispatch_to_session(gap, expr_resolver, tuple, arg);
+        }
+        if let Some((length, period)) = self.window_param.rows_length_and_period() {
+            return self.dispatch_to_rows(length, period, expr_resolver, tuple, arg);
+        }
+
         self.generate_panes_if_not_exist(rowtime)?;
 
-        Ok(self
-            .panes
+        self.panes
             .iter_mut()
-            .filter(move |pane| pane.is_acceptable(&rowtime)))
+            .filter(|keyed| keyed.pane.is_acceptable(&rowtime))
+            .try_fold(WindowInFlowByWindowTask::zero(), |acc, keyed| {
+                Ok(acc + keyed.dispatch(expr_resolver, tuple, arg.clone())?)
+            })
     }
 
     pub fn remove_panes_to_close(&mut self, watermark: &Watermark) -> Vec<P> {
@@ -62,11 +139,19 @@ where
 
         let mut idx = 0;
         while idx < self.panes.len() {
-            let pane = &mut self.panes[idx];
+            let should_close = match self.panes[idx].key {
+                PaneKey::Rows(first_row) => {
+                    let (length, _) = self.rows_length_and_period();
+                    first_row + length <= self.n_rows
+                }
+                PaneKey::Timed | PaneKey::Session(_) => {
+                    self.panes[idx].pane.should_close(watermark)
+                }
+            };
 
-            if pane.should_close(watermark) {
-                let pane = self.panes.remove(idx);
-                panes_to_close.push(pane);
+            if should_close {
+                let keyed = self.panes.remove(idx);
+                panes_to_close.push(keyed.pane);
             } else {
                 idx += 1;
             }
@@ -75,8 +160,160 @@ where
         panes_to_close
     }
 
+    /// Provisional results of open panes to emit after a tuple with `rowtime` is dispatched (and panes are closed).
+    pub fn emit_provisional(
+        &mut self,
+        expr_resolver: &ExprResolver,
+        rowtime: SpringTimestamp,
+    ) -> Result<Vec<P::CloseOut>, SpringError> {
+        enum Emission {
+            Nothing,
+            UpdatedPanes,
+            /// Only panes which have emitted results without the late row.
+            LateUpdatedPanes,
+        }
+
+        let emission = match self.emit_policy {
+            EmitPolicy::Final => Emission::Nothing,
+            EmitPolicy::OnEachRow => Emission::UpdatedPanes,
+            EmitPolicy::Every(period) => {
+                let tick = rowtime.floor(period.to_duration())?;
+                match self.last_emission_tick {
+                    Some(last_tick) if tick < last_tick => Emission::LateUpdatedPanes,
+                    Some(last_tick) if tick == last_tick => Emission::Nothing,
+                    Some(_) => {
+                        self.last_emission_tick = Some(tick);
+                        Emission::UpdatedPanes
+                    }
+                    None => {
+                        // first tick starts here
+                        self.last_emission_tick = Some(tick);
+                        Emission::Nothing
+                    }
+                }
+            }
+        };
+
+        let mut out = vec![];
+        for keyed in &mut self.panes {
+            let should_emit = match emission {
+                Emission::Nothing => false,
+                Emission::UpdatedPanes => keyed.updated,
+                Emission::LateUpdatedPanes => keyed.updated && keyed.emitted,
+            };
+            if should_emit {
+                out.append(&mut keyed.pane.emit(expr_resolver)?);
+                keyed.updated = false;
+                keyed.emitted = true;
+            }
+        }
+        Ok(out)
+    }
+
     pub fn purge(&mut self) {
-        self.panes.clear()
+        self.panes.clear();
+        self.n_rows = 0;
+        self.last_emission_tick = None;
+    }
+
+    /// Whether panes are opened and closed by the number of rows, regardless of rowtime and watermark.
+    pub fn is_count_based(&self) -> bool {
+        self.window_param.rows_length_and_period().is_some()
+    }
+
+    /// A row at `rowtime` belongs to the session pane `[open_at, close_at)` of its session key
+    /// if `open_at - gap < rowtime < close_at` (where `close_at` is `gap` after the last row).
+    ///
+    /// A new pane is generated if no pane has the row.
+    /// Panes are merged if the row bridges them (a late row may fill the gap between sessions).
+    fn dispatch_to_session(
+        &mut self,
+        gap: SpringEventDuration,
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+        arg: P::DispatchArg,
+    ) -> Result<WindowInFlowByWindowTask, SpringError> {
+        let rowtime = tuple.rowtime().as_timestamp();
+        let session_key = P::session_key(&self.op_param, expr_resolver, tuple)?;
+
+        let open_at = rowtime;
+        let close_at = rowtime + gap.to_duration();
+
+        let mut session_panes = vec![];
+        let mut idx = 0;
+        while idx < self.panes.len() {
+            let keyed = &self.panes[idx];
+
+            if matches!(&keyed.key, PaneKey::Session(key) if key == &session_key)
+                && keyed.pane.open_at() < close_at
+                && rowtime < keyed.pane.close_at()
+            {
+                session_panes.push(self.panes.remove(idx));
+            } else {
+                idx += 1;
+            }
+        }
+        session_panes.sort_by_key(|keyed| keyed.pane.open_at());
+
+        let mut window_in_flow = WindowInFlowByWindowTask::zero();
+
+        let mut session_panes = session_panes.into_iter();
+        let mut keyed = session_panes.next().unwrap_or_else(|| {
+            KeyedPane::new(
+                PaneKey::Session(session_key),
+                P::new(open_at, close_at, self.op_param.clone()),
+            )
+        });
+        while let Some(later) = session_panes.next() {
+            match keyed.pane.merge(&later.pane) {
+                Ok(merge_in_flow) => {
+                    window_in_flow = window_in_flow + merge_in_flow;
+                    keyed.emitted |= later.emitted;
+                }
+                Err(e) => {
+                    // panes not merged yet are kept as they are
+                    self.panes.push(keyed);
+                    self.panes.push(later);
+                    self.panes.extend(session_panes);
+                    return Err(e);
+                }
+            }
+        }
+        keyed.pane.extend(open_at, close_at);
+
+        // the pane is kept even if dispatch fails not to lose the rows merged so far
+        let res = keyed.dispatch(expr_resolver, tuple, arg);
+        self.panes.push(keyed);
+        Ok(window_in_flow + res?)
+    }
+
+    /// A pane is opened every `period` rows and gets `length` rows.
+    /// Pane's `open_at` and `close_at` are the rowtime of its first row (not used).
+    fn dispatch_to_rows(
+        &mut self,
+        length: u64,
+        period: u64,
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+        arg: P::DispatchArg,
+    ) -> Result<WindowInFlowByWindowTask, SpringError> {
+        let row_idx = self.n_rows;
+        self.n_rows += 1;
+
+        if row_idx % period == 0 {
+            let rowtime = tuple.rowtime().as_timestamp();
+            self.panes.push(KeyedPane::new(
+                PaneKey::Rows(row_idx),
+                P::new(rowtime, rowtime, self.op_param.clone()),
+            ));
+        }
+
+        self.panes
+            .iter_mut()
+            .filter(|keyed| matches!(keyed.key, PaneKey::Rows(first_row) if row_idx < first_row + length))
+            .try_fold(WindowInFlowByWindowTask::zero(), |acc, keyed| {
+                Ok(acc + keyed.dispatch(expr_resolver, tuple, arg.clone())?)
+            })
     }
 
     fn generate_panes_if_not_exist(&mut self, rowtime: SpringTimestamp) -> Result<(), SpringError> {
@@ -85,8 +322,15 @@ where
         for open_at in self.valid_open_at_s(rowtime)? {
             loop {
                 if pane_idx < self.panes.len() {
-                    match open_at.cmp(&self.panes[pane_idx].open_at()) {
-                        Ordering::Less => unreachable!("watermark must kick this rowtime"),
+                    match open_at.cmp(&self.panes[pane_idx].pane.open_at()) {
+                        Ordering::Less => {
+                            // Tuples forwarded from inner join windows may be behind the watermark shared with them.
+                            self.panes.insert(
+                                pane_idx,
+                                KeyedPane::new(PaneKey::Timed, self.generate_pane(open_at)),
+                            );
+                            break; // next open_at
+                        }
                         Ordering::Equal => {
                             // Pane already exists.
                             break; // next open_at
@@ -98,7 +342,8 @@ where
                     }
                 } else {
                     // no pane has the open_at
-                    self.panes.push(self.generate_pane(open_at));
+                    self.panes
+                        .push(KeyedPane::new(PaneKey::Timed, self.generate_pane(open_at)));
                     break; // next open_at
                 }
             }
@@ -110,34 +355,55 @@ where
         &self,
         rowtime: SpringTimestamp,
     ) -> Result<Vec<SpringTimestamp>, SpringError> {
+        let (length, period) = self.length_and_period();
+        let (length, period) = (length.to_duration(), period.to_duration());
+
         let mut ret = vec![];
 
         let leftmost_open_at = {
-            let l = (rowtime - self.window_param.length().to_duration())
-                .ceil(self.window_param.period().to_duration())?;
+            let l = (rowtime - length).ceil(period)?;
 
             // edge case
-            if l == rowtime - self.window_param.length().to_duration() {
-                l + self.window_param.period().to_duration()
+            if l == rowtime - length {
+                l + period
             } else {
                 l
             }
         };
-        let rightmost_open_at = rowtime.floor(self.window_param.period().to_duration())?;
+        let rightmost_open_at = rowtime.floor(period)?;
 
         let mut open_at = leftmost_open_at;
         while open_at <= rightmost_open_at {
             ret.push(open_at);
-            open_at = open_at + self.window_param.period().to_duration();
+            open_at = open_at + period;
         }
 
         Ok(ret)
     }
 
     fn generate_pane(&self, open_at: SpringTimestamp) -> P {
-        let close_at = open_at + self.window_param.length().to_duration();
+        let (length, _) = self.length_and_period();
+        let close_at = open_at + length.to_duration();
         P::new(open_at, close_at, self.op_param.clone())
     }
+
+    /// # Panics
+    ///
+    /// if the window is not time-based
+    fn length_and_period(&self) -> (SpringEventDuration, SpringEventDuration) {
+        self.window_param
+            .length_and_period()
+            .expect("only time-based windows have length and period in time")
+    }
+
+    /// # Panics
+    ///
+    /// if the window is not count-based
+    fn rows_length_and_period(&self) -> (u64, u64) {
+        self.window_param
+            .rows_length_and_period()
+            .expect("only count-based windows have length and period in rows")
+    }
 }
 
 #[cfg(test)]
@@ -147,7 +413,9 @@ mod tests {
     use crate::{
         expr_resolver::{ExprLabel, ExprResolver},
         expression::{AggrExpr, ValueExpr},
-        pipeline::{AggregateFunctionParameter, AggregateParameter, GroupByLabels},
+        pipeline::{
+            AggregateExprParameter, AggregateFunctionParameter, AggregateParameter, GroupByLabels,
+        },
         sql_processor::SelectFieldSyntax,
         stream_engine::{
             autonomous_executor::task::window::panes::pane::AggrPane,
@@ -162,6 +430,7 @@ mod tests {
         let aggr_expr = AggrExpr {
             func: AggregateFunctionParameter::Avg,
             aggregated: ValueExpr::factory_colref("dontcare", "dontcare"),
+            by: None,
         };
         let group_by_expr = ValueExpr::factory_colref("dontcare", "dontcare");
 
@@ -175,13 +444,16 @@ mod tests {
             GroupByLabels::new(vec![expr_resolver.register_value_expr(group_by_expr)]);
 
         WindowOperationParameter::Aggregate(AggregateParameter {
-            aggr_func: AggregateFunctionParameter::Avg,
-            aggr_expr: if let ExprLabel::Aggr(l) = labels[0] {
-                l
-            } else {
-                unreachable!()
-            },
+            aggr_exprs: vec![AggregateExprParameter::new(
+                AggregateFunctionParameter::Avg,
+                if let ExprLabel::Aggr(l) = labels[0] {
+                    l
+                } else {
+                    unreachable!()
+                },
+            )],
             group_by: group_by_labels,
+            having: None,
         })
     }
 
@@ -198,6 +470,7 @@ mod tests {
                     allowed_delay: SpringEventDuration::from_secs(0),
                 },
                 dont_care_window_operation_parameter(),
+                EmitPolicy::Final,
             )
         }
 
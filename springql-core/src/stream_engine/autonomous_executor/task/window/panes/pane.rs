@@ -6,7 +6,10 @@ mod join_pane;
 pub use aggregate_pane::{AggrPane, AggrPaneInner, GroupByValues};
 pub use join_pane::{JoinDir, JoinPane};
 
+use std::fmt::Debug;
+
 use crate::{
+    api::error::Result,
     expr_resolver::ExprResolver,
     pipeline::WindowOperationParameter,
     stream_engine::{
@@ -22,6 +25,9 @@ pub trait Pane {
     type CloseOut;
     type DispatchArg: Clone;
 
+    /// Rows with the same session key share panes in session windows.
+    type SessionKey: Eq + Debug;
+
     fn new(
         open_at: SpringTimestamp,
         close_at: SpringTimestamp,
@@ -31,6 +37,20 @@ pub trait Pane {
     fn open_at(&self) -> SpringTimestamp;
     fn close_at(&self) -> SpringTimestamp;
 
+    fn session_key(
+        param: &WindowOperationParameter,
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+    ) -> Result<Self::SessionKey>;
+
+    /// Extends the pane to cover `[open_at, close_at)` too.
+    fn extend(&mut self, open_at: SpringTimestamp, close_at: SpringTimestamp);
+
+    /// Merges another pane of the same session into this one (and extends this one to cover it).
+    ///
+    /// Neither pane is modified on error.
+    fn merge(&mut self, other: &Self) -> Result<WindowInFlowByWindowTask>;
+
     fn is_acceptable(&self, rowtime: &SpringTimestamp) -> bool {
         &self.open_at() <= rowtime && rowtime < &self.close_at()
     }
@@ -44,8 +64,13 @@ pub trait Pane {
         expr_resolver: &ExprResolver,
         tuple: &Tuple,
         arg: Self::DispatchArg,
-    ) -> WindowInFlowByWindowTask;
+    ) -> Result<WindowInFlowByWindowTask>;
 
-    fn close(self, expr_resolver: &ExprResolver)
-        -> (Vec<Self::CloseOut>, WindowInFlowByWindowTask);
+    /// Provisional results of the pane (without closing it).
+    fn emit(&self, expr_resolver: &ExprResolver) -> Result<Vec<Self::CloseOut>>;
+
+    fn close(
+        self,
+        expr_resolver: &ExprResolver,
+    ) -> Result<(Vec<Self::CloseOut>, WindowInFlowByWindowTask)>;
 }
@@ -4,11 +4,10 @@ mod aggregate_state;
 
 use std::collections::HashMap;
 
-use ordered_float::OrderedFloat;
-
 use crate::{
     api::error::Result,
     expr_resolver::ExprResolver,
+    mem_size::MemSize,
     pipeline::{
         AggregateFunctionParameter, AggregateParameter, GroupByLabels, WindowOperationParameter,
     },
@@ -19,7 +18,14 @@ use crate::{
                 tuple::Tuple,
                 window::{
                     aggregate::AggregatedAndGroupingValues,
-                    panes::pane::{aggregate_pane::aggregate_state::AvgState, Pane},
+                    panes::pane::{
+                        aggregate_pane::aggregate_state::{
+                            AggregateState, ApproxCountDistinctState, ArgExtremeKind,
+                            ArgExtremeState, AvgState, CountDistinctState, CountState, MaxState,
+                            MinState, PercentileState, SumState, VarianceKind, VarianceState,
+                        },
+                        Pane,
+                    },
                 },
             },
         },
@@ -35,12 +41,14 @@ pub struct AggrPane {
 
     aggregate_parameter: AggregateParameter,
 
-    inner: AggrPaneInner,
+    /// Aggregate states of each group, in the same order as `aggregate_parameter.aggr_exprs`.
+    groups: HashMap<GroupByValues, Vec<AggrPaneInner>>,
 }
 
 impl Pane for AggrPane {
     type CloseOut = AggregatedAndGroupingValues;
     type DispatchArg = ();
+    type SessionKey = GroupByValues;
 
     /// # Panics
     ///
@@ -51,17 +59,11 @@ impl Pane for AggrPane {
         op_param: WindowOperationParameter,
     ) -> Self {
         if let WindowOperationParameter::Aggregate(aggregate_parameter) = op_param {
-            let inner = match aggregate_parameter.aggr_func {
-                AggregateFunctionParameter::Avg => AggrPaneInner::Avg {
-                    states: HashMap::new(),
-                },
-            };
-
             Self {
                 open_at,
                 close_at,
                 aggregate_parameter,
-                inner,
+                groups: HashMap::new(),
             }
         } else {
             panic!("op_param {:?} is not GroupAggregateParameter", op_param)
@@ -76,92 +78,292 @@ impl Pane for AggrPane {
         self.close_at
     }
 
+    /// Each group has its own sessions.
+    ///
+    /// # Panics
+    ///
+    /// if `op_param` is not `GroupAggregateParameter`
+    fn session_key(
+        op_param: &WindowOperationParameter,
+        expr_resolver: &ExprResolver,
+        tuple: &Tuple,
+    ) -> Result<GroupByValues> {
+        if let WindowOperationParameter::Aggregate(aggregate_parameter) = op_param {
+            GroupByValues::from_group_by_labels(
+                aggregate_parameter.group_by.clone(),
+                expr_resolver,
+                tuple,
+            )
+        } else {
+            panic!("op_param {:?} is not GroupAggregateParameter", op_param)
+        }
+    }
+
+    fn extend(&mut self, open_at: SpringTimestamp, close_at: SpringTimestamp) {
+        self.open_at = self.open_at.min(open_at);
+        self.close_at = self.close_at.max(close_at);
+    }
+
+    fn merge(&mut self, other: &Self) -> Result<WindowInFlowByWindowTask> {
+        // states are merged into copies first not to leave half-merged groups on error
+        let merged_groups = other
+            .groups
+            .iter()
+            .map(|(group_by_values, other_states)| {
+                let states = match self.groups.get(group_by_values) {
+                    Some(states) => states
+                        .iter()
+                        .zip(other_states)
+                        .map(|(state, other_state)| {
+                            let mut state = state.clone();
+                            state.merge(other_state.clone())?;
+                            Ok(state)
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                    None => other_states.clone(),
+                };
+                Ok((group_by_values.clone(), states))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let size_before = self.states_bytes() + other.states_bytes();
+        self.extend(other.open_at, other.close_at);
+        self.groups.extend(merged_groups);
+
+        Ok(WindowInFlowByWindowTask::new(
+            self.states_bytes() - size_before,
+            0,
+        ))
+    }
+
     fn dispatch(
         &mut self,
         expr_resolver: &ExprResolver,
         tuple: &Tuple,
         _arg: (),
-    ) -> WindowInFlowByWindowTask {
+    ) -> Result<WindowInFlowByWindowTask> {
         let group_by_values = GroupByValues::from_group_by_labels(
             self.aggregate_parameter.group_by.clone(),
             expr_resolver,
             tuple,
-        )
-        .expect("TODO handle Result");
-
-        let aggregated_value = expr_resolver
-            .eval_aggr_expr_inner(self.aggregate_parameter.aggr_expr, tuple)
-            .expect("TODO Result");
-        let aggregated_value = if let SqlValue::NotNull(v) = aggregated_value {
-            v
-        } else {
-            unimplemented!("aggregation with NULL value is not supported")
-        };
+        )?;
 
-        match &mut self.inner {
-            AggrPaneInner::Avg { states } => {
-                let state = states
-                    .entry(group_by_values)
-                    .or_insert_with(AvgState::default);
+        let aggr_exprs = &self.aggregate_parameter.aggr_exprs;
+        let states = self.groups.entry(group_by_values).or_insert_with(|| {
+            aggr_exprs
+                .iter()
+                .map(|aggr_expr| AggrPaneInner::new(aggr_expr.aggr_func))
+                .collect()
+        });
 
-                state.next(
-                    aggregated_value
-                        .unpack::<f32>()
-                        .expect("only f32 is supported currently"),
-                );
+        let mut states_gain_bytes: i64 = 0;
+        for (aggr_expr, state) in aggr_exprs.iter().zip(states.iter_mut()) {
+            let aggregated_value =
+                expr_resolver.eval_aggr_expr_inner(aggr_expr.aggr_expr, tuple)?;
 
-                WindowInFlowByWindowTask::zero() // state in AVG is constant
-            }
+            let by = match aggr_expr.aggr_func {
+                AggregateFunctionParameter::FirstValue | AggregateFunctionParameter::LastValue => {
+                    Some(SqlValue::NotNull(NnSqlValue::Timestamp(
+                        tuple.rowtime().as_timestamp(),
+                    )))
+                }
+                _ => expr_resolver.eval_aggr_expr_by(aggr_expr.aggr_expr, tuple)?,
+            };
+
+            let size_before = state.mem_size() as i64;
+            state.update(aggregated_value, by)?;
+            states_gain_bytes += state.mem_size() as i64 - size_before;
         }
+
+        Ok(WindowInFlowByWindowTask::new(states_gain_bytes, 0))
+    }
+
+    fn emit(&self, _expr_resolver: &ExprResolver) -> Result<Vec<Self::CloseOut>> {
+        let aggr_exprs = &self.aggregate_parameter.aggr_exprs;
+        let group_by_labels = &self.aggregate_parameter.group_by;
+
+        Ok(self
+            .groups
+            .iter()
+            .map(|(group_by_values, states)| {
+                let aggregates = aggr_exprs
+                    .iter()
+                    .map(|aggr_expr| aggr_expr.aggr_expr)
+                    .zip(states.iter().map(AggrPaneInner::result))
+                    .collect();
+
+                let group_bys = group_by_labels
+                    .as_labels()
+                    .iter()
+                    .cloned()
+                    .zip(group_by_values.clone().into_sql_values())
+                    .collect();
+
+                AggregatedAndGroupingValues::new(aggregates, group_bys)
+            })
+            .collect())
     }
 
     fn close(
         self,
-        _expr_resolver: &ExprResolver,
-    ) -> (Vec<Self::CloseOut>, WindowInFlowByWindowTask) {
-        let aggr_label = self.aggregate_parameter.aggr_expr;
-        let group_by_labels = self.aggregate_parameter.group_by;
-
-        match self.inner {
-            AggrPaneInner::Avg { states } => {
-                let aggregated_and_grouping_values_seq = states
-                    .into_iter()
-                    .map(|(group_by_values, state)| {
-                        let aggr_value =
-                            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(state.finalize())));
-
-                        let group_bys = group_by_labels
-                            .as_labels()
-                            .iter()
-                            .cloned()
-                            .zip(group_by_values.into_sql_values())
-                            .collect();
-
-                        AggregatedAndGroupingValues::new(vec![(aggr_label, aggr_value)], group_bys)
-                    })
-                    .collect();
+        expr_resolver: &ExprResolver,
+    ) -> Result<(Vec<Self::CloseOut>, WindowInFlowByWindowTask)> {
+        let states_bytes = self.states_bytes();
+        Ok((
+            self.emit(expr_resolver)?,
+            WindowInFlowByWindowTask::new(-states_bytes, 0),
+        ))
+    }
+}
 
-                (
-                    aggregated_and_grouping_values_seq,
-                    WindowInFlowByWindowTask::zero(),
-                )
+impl AggrPane {
+    fn states_bytes(&self) -> i64 {
+        self.groups
+            .values()
+            .flatten()
+            .map(|state| state.mem_size() as i64)
+            .sum()
+    }
+}
+
+/// State of an aggregate function in a group.
+#[derive(Clone, Debug)]
+pub enum AggrPaneInner {
+    Avg(AvgState),
+    Sum(SumState),
+    Count(CountState),
+    CountDistinct(CountDistinctState),
+    ApproxCountDistinct(ApproxCountDistinctState),
+    Min(MinState),
+    Max(MaxState),
+    ArgExtreme(ArgExtremeState),
+    Variance(VarianceState),
+    Percentile(PercentileState),
+}
+
+impl AggrPaneInner {
+    fn new(aggr_func: AggregateFunctionParameter) -> Self {
+        match aggr_func {
+            AggregateFunctionParameter::Avg => Self::Avg(AvgState::default()),
+            AggregateFunctionParameter::Sum => Self::Sum(SumState::default()),
+            AggregateFunctionParameter::Count => Self::Count(CountState::default()),
+            AggregateFunctionParameter::CountDistinct => {
+                Self::CountDistinct(CountDistinctState::default())
+            }
+            AggregateFunctionParameter::ApproxCountDistinct => {
+                Self::ApproxCountDistinct(ApproxCountDistinctState::default())
+            }
+            AggregateFunctionParameter::Min => Self::Min(MinState::default()),
+            AggregateFunctionParameter::Max => Self::Max(MaxState::default()),
+            AggregateFunctionParameter::FirstValue | AggregateFunctionParameter::ArgMin => {
+                Self::ArgExtreme(ArgExtremeState::new(ArgExtremeKind::Min))
+            }
+            AggregateFunctionParameter::LastValue => {
+                Self::ArgExtreme(ArgExtremeState::new(ArgExtremeKind::MaxOrLatest))
+            }
+            AggregateFunctionParameter::ArgMax => {
+                Self::ArgExtreme(ArgExtremeState::new(ArgExtremeKind::Max))
+            }
+            AggregateFunctionParameter::StddevPop => {
+                Self::Variance(VarianceState::new(VarianceKind::StddevPop))
+            }
+            AggregateFunctionParameter::StddevSamp => {
+                Self::Variance(VarianceState::new(VarianceKind::StddevSamp))
+            }
+            AggregateFunctionParameter::VarPop => {
+                Self::Variance(VarianceState::new(VarianceKind::VarPop))
+            }
+            AggregateFunctionParameter::VarSamp => {
+                Self::Variance(VarianceState::new(VarianceKind::VarSamp))
+            }
+            AggregateFunctionParameter::PercentileApprox { percentile } => {
+                Self::Percentile(PercentileState::new(percentile.into_inner()))
+            }
+        }
+    }
+
+    /// NULL is skipped by aggregate functions.
+    /// `by` is the ordering value for ARG_MIN, ARG_MAX, FIRST_VALUE and LAST_VALUE (rows with NULL `by` are skipped too).
+    fn update(&mut self, aggregated_value: SqlValue, by: Option<SqlValue>) -> Result<()> {
+        let value = match aggregated_value {
+            SqlValue::Null => return Ok(()),
+            SqlValue::NotNull(v) => v,
+        };
+        match self {
+            Self::ArgExtreme(state) => match by {
+                Some(SqlValue::NotNull(by)) => state.update(value, by),
+                Some(SqlValue::Null) => Ok(()),
+                None => unreachable!("ARG_MIN / ARG_MAX without `by` argument"),
+            },
+            Self::Avg(state) => state.update(value),
+            Self::Sum(state) => state.update(value),
+            Self::Count(state) => state.update(value),
+            Self::CountDistinct(state) => state.update(value),
+            Self::ApproxCountDistinct(state) => state.update(value),
+            Self::Min(state) => state.update(value),
+            Self::Max(state) => state.update(value),
+            Self::Variance(state) => state.update(value),
+            Self::Percentile(state) => state.update(value),
+        }
+    }
+
+    /// # Panics
+    ///
+    /// if `other` is a state of another aggregate function
+    fn merge(&mut self, other: Self) -> Result<()> {
+        match (self, other) {
+            (Self::Avg(state), Self::Avg(other)) => state.merge(other),
+            (Self::Sum(state), Self::Sum(other)) => state.merge(other),
+            (Self::Count(state), Self::Count(other)) => state.merge(other),
+            (Self::CountDistinct(state), Self::CountDistinct(other)) => state.merge(other),
+            (Self::ApproxCountDistinct(state), Self::ApproxCountDistinct(other)) => {
+                state.merge(other)
             }
+            (Self::Min(state), Self::Min(other)) => state.merge(other),
+            (Self::Max(state), Self::Max(other)) => state.merge(other),
+            (Self::ArgExtreme(state), Self::ArgExtreme(other)) => state.merge(other),
+            (Self::Variance(state), Self::Variance(other)) => state.merge(other),
+            (Self::Percentile(state), Self::Percentile(other)) => state.merge(other),
+            (state, other) => unreachable!("cannot merge {:?} into {:?}", other, state),
+        }
+    }
+
+    fn result(&self) -> SqlValue {
+        match self {
+            Self::Avg(state) => state.result(),
+            Self::Sum(state) => state.result(),
+            Self::Count(state) => state.result(),
+            Self::CountDistinct(state) => state.result(),
+            Self::ApproxCountDistinct(state) => state.result(),
+            Self::Min(state) => state.result(),
+            Self::Max(state) => state.result(),
+            Self::ArgExtreme(state) => state.result(),
+            Self::Variance(state) => state.result(),
+            Self::Percentile(state) => state.result(),
         }
     }
 }
 
-#[derive(Debug)]
-pub enum AggrPaneInner {
-    Avg {
-        states: HashMap<GroupByValues, AvgState>,
-    },
+impl MemSize for AggrPaneInner {
+    fn mem_size(&self) -> usize {
+        match self {
+            Self::Avg(state) => state.mem_size(),
+            Self::Sum(state) => state.mem_size(),
+            Self::Count(state) => state.mem_size(),
+            Self::CountDistinct(state) => state.mem_size(),
+            Self::ApproxCountDistinct(state) => state.mem_size(),
+            Self::Min(state) => state.mem_size(),
+            Self::Max(state) => state.mem_size(),
+            Self::ArgExtreme(state) => state.mem_size(),
+            Self::Variance(state) => state.mem_size(),
+            Self::Percentile(state) => state.mem_size(),
+        }
+    }
 }
 
+/// NULL is a group key too (`None`), so that all rows with NULL in a GROUP BY element form a group.
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
-pub struct GroupByValues(
-    /// TODO support NULL in GROUP BY elements
-    Vec<NnSqlValue>,
-);
+pub struct GroupByValues(Vec<Option<NnSqlValue>>);
 
 impl GroupByValues {
     /// Order of elements in GROUP BY clause is preserved.
@@ -176,11 +378,10 @@ impl GroupByValues {
             .map(|group_by_label| {
                 let group_by_value = expr_resolver.eval_value_expr(*group_by_label, tuple)?;
 
-                if let SqlValue::NotNull(v) = group_by_value {
-                    Ok(v)
-                } else {
-                    unimplemented!("group by NULL is not supported ")
-                }
+                Ok(match group_by_value {
+                    SqlValue::NotNull(v) => Some(v),
+                    SqlValue::Null => None,
+                })
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -188,6 +389,82 @@ impl GroupByValues {
     }
 
     pub fn into_sql_values(self) -> Vec<SqlValue> {
-        self.0.into_iter().map(SqlValue::NotNull).collect()
+        self.0
+            .into_iter()
+            .map(|v| v.map_or(SqlValue::Null, SqlValue::NotNull))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn pane_with_sum_and_min(open_at: &str, sum: NnSqlValue, min: NnSqlValue) -> AggrPane {
+        let open_at = SpringTimestamp::from_str(open_at).unwrap();
+        let mut pane = AggrPane::new(
+            open_at,
+            open_at,
+            WindowOperationParameter::Aggregate(AggregateParameter {
+                aggr_exprs: vec![],
+                group_by: GroupByLabels::new(vec![]),
+                having: None,
+            }),
+        );
+
+        let mut sum_state = SumState::default();
+        sum_state.update(sum).unwrap();
+        let mut min_state = MinState::default();
+        min_state.update(min).unwrap();
+        pane.groups.insert(
+            GroupByValues(vec![]),
+            vec![AggrPaneInner::Sum(sum_state), AggrPaneInner::Min(min_state)],
+        );
+        pane
+    }
+
+    fn results(pane: &AggrPane) -> Vec<SqlValue> {
+        pane.groups[&GroupByValues(vec![])]
+            .iter()
+            .map(|state| state.clone().result())
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_error_keeps_panes() {
+        let mut pane = pane_with_sum_and_min(
+            "2020-01-01 00:00:00.000000000",
+            NnSqlValue::Integer(1),
+            NnSqlValue::Integer(1),
+        );
+        // MIN cannot compare INTEGER with TEXT
+        let other = pane_with_sum_and_min(
+            "2020-01-01 00:00:10.000000000",
+            NnSqlValue::Integer(2),
+            NnSqlValue::Text("a".to_string()),
+        );
+
+        assert!(pane.merge(&other).is_err());
+
+        assert_eq!(
+            pane.close_at(),
+            SpringTimestamp::from_str("2020-01-01 00:00:00.000000000").unwrap()
+        );
+        assert_eq!(
+            results(&pane),
+            vec![
+                SqlValue::NotNull(NnSqlValue::BigInt(1)),
+                SqlValue::NotNull(NnSqlValue::Integer(1))
+            ]
+        );
+        assert_eq!(
+            results(&other),
+            vec![
+                SqlValue::NotNull(NnSqlValue::BigInt(2)),
+                SqlValue::NotNull(NnSqlValue::Text("a".to_string()))
+            ]
+        );
     }
 }
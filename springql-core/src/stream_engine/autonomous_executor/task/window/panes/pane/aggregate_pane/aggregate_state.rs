@@ -1,7 +1,42 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    f64::consts::PI,
+    hash::{Hash, Hasher},
+    mem::size_of,
+};
+
+use ordered_float::OrderedFloat;
+
+use crate::{
+    api::error::Result,
+    mem_size::MemSize,
+    pipeline::{NumericComparableType, SqlType},
+    stream_engine::{NnSqlValue, SqlCompareResult, SqlValue},
+};
+
+/// State of an aggregate function for a group.
+///
+/// NULL values are skipped by callers, so `update()` only receives non-NULL values.
+///
+/// `mem_size()` estimates the heap memory used by the state. Fixed-size states report 0.
+pub trait AggregateState: MemSize {
+    fn update(&mut self, value: NnSqlValue) -> Result<()>;
+
+    /// Merges another state of the same aggregate function into this one (used when session windows merge).
+    fn merge(&mut self, other: Self) -> Result<()>
+    where
+        Self: Sized;
+
+    /// NULL when no value has been updated (except for COUNT).
+    ///
+    /// Does not consume the state because provisional results may be emitted before the final one.
+    fn result(&self) -> SqlValue;
+}
+
 // TODO more generic avg
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct AvgState {
     current_avg: f32,
     current_n: u64,
@@ -20,11 +55,603 @@ impl AvgState {
         self.current_n = next_n;
     }
 
-    pub fn finalize(self) -> f32 {
+    pub fn finalize(&self) -> f32 {
         self.current_avg.round()
     }
 }
 
+impl AggregateState for AvgState {
+    fn update(&mut self, value: NnSqlValue) -> Result<()> {
+        self.next(value.to_f64_loose()? as f32);
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) -> Result<()> {
+        let n = self.current_n + other.current_n;
+        if n > 0 {
+            self.current_avg = (self.current_avg * self.current_n as f32
+                + other.current_avg * other.current_n as f32)
+                / n as f32;
+            self.current_n = n;
+        }
+        Ok(())
+    }
+
+    fn result(&self) -> SqlValue {
+        if self.current_n == 0 {
+            SqlValue::Null
+        } else {
+            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(self.finalize())))
+        }
+    }
+}
+
+impl MemSize for AvgState {
+    fn mem_size(&self) -> usize {
+        0
+    }
+}
+
+/// Sum in BIGINT, UNSIGNED BIGINT or FLOAT, depending on the input type.
+#[derive(Clone, Debug, Default)]
+pub struct SumState {
+    current_sum: Option<NnSqlValue>,
+}
+
+impl AggregateState for SumState {
+    fn update(&mut self, value: NnSqlValue) -> Result<()> {
+        let sum = match &self.current_sum {
+            // keeps the current sum if the addition fails
+            Some(current_sum) => (current_sum.clone() + value)?,
+            None => {
+                let sum_type = match value.sql_type() {
+                    SqlType::NumericComparable(NumericComparableType::U64Loose(_)) => {
+                        SqlType::unsigned_big_int()
+                    }
+                    SqlType::NumericComparable(NumericComparableType::F32Loose(_)) => {
+                        SqlType::float()
+                    }
+                    _ => SqlType::big_int(),
+                };
+                value.try_cast(&sum_type)?
+            }
+        };
+        self.current_sum = Some(sum);
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) -> Result<()> {
+        match other.current_sum {
+            Some(other_sum) => self.update(other_sum),
+            None => Ok(()),
+        }
+    }
+
+    fn result(&self) -> SqlValue {
+        self.current_sum
+            .clone()
+            .map_or(SqlValue::Null, SqlValue::NotNull)
+    }
+}
+
+impl MemSize for SumState {
+    fn mem_size(&self) -> usize {
+        0 // numeric values only
+    }
+}
+
+/// Number of non-NULL values in BIGINT.
+#[derive(Clone, Debug, Default)]
+pub struct CountState {
+    current_count: i64,
+}
+
+impl AggregateState for CountState {
+    fn update(&mut self, _value: NnSqlValue) -> Result<()> {
+        self.current_count += 1;
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) -> Result<()> {
+        self.current_count += other.current_count;
+        Ok(())
+    }
+
+    fn result(&self) -> SqlValue {
+        SqlValue::NotNull(NnSqlValue::BigInt(self.current_count))
+    }
+}
+
+impl MemSize for CountState {
+    fn mem_size(&self) -> usize {
+        0
+    }
+}
+
+/// Exact number of distinct non-NULL values in BIGINT.
+///
+/// Memory grows with the number of distinct values. Use `ApproxCountDistinctState` for large windows.
+#[derive(Clone, Debug, Default)]
+pub struct CountDistinctState {
+    values: HashSet<NnSqlValue>,
+}
+
+impl AggregateState for CountDistinctState {
+    fn update(&mut self, value: NnSqlValue) -> Result<()> {
+        self.values.insert(value);
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) -> Result<()> {
+        self.values.extend(other.values);
+        Ok(())
+    }
+
+    fn result(&self) -> SqlValue {
+        SqlValue::NotNull(NnSqlValue::BigInt(self.values.len() as i64))
+    }
+}
+
+impl MemSize for CountDistinctState {
+    fn mem_size(&self) -> usize {
+        // 1 control byte per bucket in hashbrown
+        let buckets_size = self.values.capacity() * (size_of::<NnSqlValue>() + 1);
+        let values_size: usize = self.values.iter().map(heap_size).sum();
+        buckets_size + values_size
+    }
+}
+
+/// Approximate number of distinct non-NULL values in BIGINT, estimated by HyperLogLog.
+///
+/// Uses `2^HLL_PRECISION` 1-byte registers (standard error is about 1.6%), allocated on the first value.
+#[derive(Clone, Debug, Default)]
+pub struct ApproxCountDistinctState {
+    registers: Vec<u8>,
+}
+
+const HLL_PRECISION: u32 = 12;
+
+impl ApproxCountDistinctState {
+    fn n_registers() -> usize {
+        1 << HLL_PRECISION
+    }
+
+    fn estimate(&self) -> f64 {
+        let m = Self::n_registers() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+
+        let sum: f64 = self.registers.iter().map(|&r| 2f64.powi(-(r as i32))).sum();
+        let raw_estimate = alpha * m * m / sum;
+
+        let n_zero_registers = self.registers.iter().filter(|&&r| r == 0).count();
+        if raw_estimate <= 2.5 * m && n_zero_registers > 0 {
+            // linear counting for small cardinalities
+            m * (m / n_zero_registers as f64).ln()
+        } else {
+            raw_estimate
+        }
+    }
+}
+
+impl AggregateState for ApproxCountDistinctState {
+    fn update(&mut self, value: NnSqlValue) -> Result<()> {
+        if self.registers.is_empty() {
+            self.registers = vec![0; Self::n_registers()];
+        }
+
+        // loosely typed equal values (`42 SMALLINT` and `42 INTEGER`) have the same hash
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let index = (hash >> (64 - HLL_PRECISION)) as usize;
+        let rest = hash << HLL_PRECISION;
+        let rank = (rest.leading_zeros() + 1).min(64 - HLL_PRECISION + 1) as u8;
+
+        let register = &mut self.registers[index];
+        *register = (*register).max(rank);
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) -> Result<()> {
+        if self.registers.is_empty() {
+            self.registers = other.registers;
+        } else {
+            for (register, other_register) in self.registers.iter_mut().zip(other.registers) {
+                *register = (*register).max(other_register);
+            }
+        }
+        Ok(())
+    }
+
+    fn result(&self) -> SqlValue {
+        let count = if self.registers.is_empty() {
+            0
+        } else {
+            self.estimate().round() as i64
+        };
+        SqlValue::NotNull(NnSqlValue::BigInt(count))
+    }
+}
+
+impl MemSize for ApproxCountDistinctState {
+    fn mem_size(&self) -> usize {
+        self.registers.capacity()
+    }
+}
+
+/// Minimum value in the input type.
+#[derive(Clone, Debug, Default)]
+pub struct MinState {
+    current_min: Option<NnSqlValue>,
+}
+
+impl AggregateState for MinState {
+    fn update(&mut self, value: NnSqlValue) -> Result<()> {
+        match &self.current_min {
+            Some(current_min)
+                if !matches!(value.sql_compare(current_min)?, SqlCompareResult::LessThan) => {}
+            _ => self.current_min = Some(value),
+        }
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) -> Result<()> {
+        match other.current_min {
+            Some(other_min) => self.update(other_min),
+            None => Ok(()),
+        }
+    }
+
+    fn result(&self) -> SqlValue {
+        self.current_min
+            .clone()
+            .map_or(SqlValue::Null, SqlValue::NotNull)
+    }
+}
+
+impl MemSize for MinState {
+    fn mem_size(&self) -> usize {
+        self.current_min.as_ref().map_or(0, heap_size)
+    }
+}
+
+/// Maximum value in the input type.
+#[derive(Clone, Debug, Default)]
+pub struct MaxState {
+    current_max: Option<NnSqlValue>,
+}
+
+impl AggregateState for MaxState {
+    fn update(&mut self, value: NnSqlValue) -> Result<()> {
+        match &self.current_max {
+            Some(current_max)
+                if !matches!(
+                    value.sql_compare(current_max)?,
+                    SqlCompareResult::GreaterThan
+                ) => {}
+            _ => self.current_max = Some(value),
+        }
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) -> Result<()> {
+        match other.current_max {
+            Some(other_max) => self.update(other_max),
+            None => Ok(()),
+        }
+    }
+
+    fn result(&self) -> SqlValue {
+        self.current_max
+            .clone()
+            .map_or(SqlValue::Null, SqlValue::NotNull)
+    }
+}
+
+impl MemSize for MaxState {
+    fn mem_size(&self) -> usize {
+        self.current_max.as_ref().map_or(0, heap_size)
+    }
+}
+
+/// Heap memory owned by a value (only TEXT and BLOB have one).
+fn heap_size(value: &NnSqlValue) -> usize {
+    match value {
+        NnSqlValue::Text(_) | NnSqlValue::Blob(_) => value.mem_size(),
+        _ => 0,
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ArgExtremeKind {
+    /// Keeps the earliest value on ties.
+    Min,
+    /// Keeps the earliest value on ties.
+    Max,
+    /// Keeps the latest value on ties.
+    MaxOrLatest,
+}
+
+/// Value with the minimum / maximum `by` value, in the input type.
+///
+/// FIRST_VALUE and LAST_VALUE are ARG_MIN and ARG_MAX by `rowtime()`.
+/// Unlike other states, `update()` takes the `by` value, so this does not implement `AggregateState`.
+#[derive(Clone, Debug, new)]
+pub struct ArgExtremeState {
+    kind: ArgExtremeKind,
+    /// (by, value)
+    #[new(default)]
+    current: Option<(NnSqlValue, NnSqlValue)>,
+}
+
+impl ArgExtremeState {
+    pub fn update(&mut self, value: NnSqlValue, by: NnSqlValue) -> Result<()> {
+        let replace = match &self.current {
+            None => true,
+            Some((current_by, _)) => {
+                let cmp = by.sql_compare(current_by)?;
+                match self.kind {
+                    ArgExtremeKind::Min => matches!(cmp, SqlCompareResult::LessThan),
+                    ArgExtremeKind::Max => matches!(cmp, SqlCompareResult::GreaterThan),
+                    ArgExtremeKind::MaxOrLatest => !matches!(cmp, SqlCompareResult::LessThan),
+                }
+            }
+        };
+        if replace {
+            self.current = Some((by, value));
+        }
+        Ok(())
+    }
+
+    /// `other` must hold later rows than `self` so that ties are resolved the same way as `update()`.
+    pub fn merge(&mut self, other: Self) -> Result<()> {
+        match other.current {
+            Some((by, value)) => self.update(value, by),
+            None => Ok(()),
+        }
+    }
+
+    pub fn result(&self) -> SqlValue {
+        self.current.as_ref().map_or(SqlValue::Null, |(_, value)| {
+            SqlValue::NotNull(value.clone())
+        })
+    }
+}
+
+impl MemSize for ArgExtremeState {
+    fn mem_size(&self) -> usize {
+        self.current
+            .as_ref()
+            .map_or(0, |(by, value)| heap_size(by) + heap_size(value))
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum VarianceKind {
+    VarPop,
+    VarSamp,
+    StddevPop,
+    StddevSamp,
+}
+
+/// Variance or standard deviation in FLOAT, computed by Welford's online algorithm.
+///
+/// Population variants are NULL for no value. Sample variants are NULL for less than 2 values.
+#[derive(Clone, Debug, new)]
+pub struct VarianceState {
+    kind: VarianceKind,
+    #[new(default)]
+    n: u64,
+    #[new(default)]
+    mean: f64,
+    /// Sum of squared differences from the current mean.
+    #[new(default)]
+    m2: f64,
+}
+
+impl AggregateState for VarianceState {
+    fn update(&mut self, value: NnSqlValue) -> Result<()> {
+        let x = value.to_f64_loose()?;
+        self.n += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.n as f64;
+        self.m2 += delta * (x - self.mean);
+        Ok(())
+    }
+
+    /// Chan's parallel algorithm.
+    fn merge(&mut self, other: Self) -> Result<()> {
+        let n = self.n + other.n;
+        if n > 0 {
+            let delta = other.mean - self.mean;
+            self.mean += delta * other.n as f64 / n as f64;
+            self.m2 += other.m2 + delta * delta * (self.n as f64 * other.n as f64) / n as f64;
+            self.n = n;
+        }
+        Ok(())
+    }
+
+    fn result(&self) -> SqlValue {
+        let variance = match self.kind {
+            VarianceKind::VarPop | VarianceKind::StddevPop if self.n >= 1 => {
+                Some(self.m2 / self.n as f64)
+            }
+            VarianceKind::VarSamp | VarianceKind::StddevSamp if self.n >= 2 => {
+                Some(self.m2 / (self.n - 1) as f64)
+            }
+            _ => None,
+        };
+        let result = variance.map(|variance| match self.kind {
+            VarianceKind::StddevPop | VarianceKind::StddevSamp => variance.sqrt(),
+            VarianceKind::VarPop | VarianceKind::VarSamp => variance,
+        });
+        result.map_or(SqlValue::Null, |v| {
+            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(v as f32)))
+        })
+    }
+}
+
+impl MemSize for VarianceState {
+    fn mem_size(&self) -> usize {
+        0
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Approximate percentile in FLOAT, computed by a merging t-digest.
+///
+/// Memory is bounded by `TDIGEST_COMPRESSION` centroids (roughly) and `TDIGEST_BUFFER_SIZE` unmerged values,
+/// regardless of the number of values. Accuracy is higher around extreme percentiles (p99, for example).
+#[derive(Clone, Debug)]
+pub struct PercentileState {
+    percentile: f64,
+
+    /// Sorted by mean.
+    centroids: Vec<Centroid>,
+    /// Values not merged into `centroids` yet.
+    buffer: Vec<f64>,
+
+    min: f64,
+    max: f64,
+}
+
+const TDIGEST_COMPRESSION: f64 = 100.0;
+const TDIGEST_BUFFER_SIZE: usize = 500;
+
+impl PercentileState {
+    /// `percentile` must be in [0, 1].
+    pub fn new(percentile: f64) -> Self {
+        Self {
+            percentile,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Scale function k1 of the t-digest paper, which makes centroids smaller around q = 0 and q = 1.
+    fn k(q: f64) -> f64 {
+        TDIGEST_COMPRESSION / (2.0 * PI) * (2.0 * q - 1.0).asin()
+    }
+
+    fn k_inv(k: f64) -> f64 {
+        let k = k.min(TDIGEST_COMPRESSION / 4.0);
+        ((k * 2.0 * PI / TDIGEST_COMPRESSION).sin() + 1.0) / 2.0
+    }
+
+    /// Merges `buffer` into `centroids`.
+    fn compress(&mut self) {
+        let mut all = self.centroids.clone();
+        all.extend(
+            self.buffer
+                .drain(..)
+                .map(|mean| Centroid { mean, weight: 1.0 }),
+        );
+        if all.is_empty() {
+            return;
+        }
+        all.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+
+        let total_weight: f64 = all.iter().map(|c| c.weight).sum();
+
+        let mut merged = Vec::with_capacity(self.centroids.len().max(1));
+        let mut weight_so_far = 0.0;
+        let mut q_limit = Self::k_inv(Self::k(0.0) + 1.0);
+        let mut current = all[0];
+
+        for next in all.into_iter().skip(1) {
+            let q = (weight_so_far + current.weight + next.weight) / total_weight;
+            if q <= q_limit {
+                let weight = current.weight + next.weight;
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                weight_so_far += current.weight;
+                merged.push(current);
+                q_limit = Self::k_inv(Self::k(weight_so_far / total_weight) + 1.0);
+                current = next;
+            }
+        }
+        merged.push(current);
+
+        self.centroids = merged;
+    }
+
+    fn quantile(&self) -> Option<f64> {
+        let total_weight: f64 = self.centroids.iter().map(|c| c.weight).sum();
+        let first = self.centroids.first()?;
+        let last = self.centroids.last()?;
+
+        let target = self.percentile * total_weight;
+
+        // interpolates between centers of adjacent centroids, and between min/max and edge centroids
+        let mut center_so_far = first.weight / 2.0;
+        if target <= center_so_far {
+            return Some(self.min + (first.mean - self.min) * target / center_so_far);
+        }
+        for (left, right) in self.centroids.iter().zip(self.centroids.iter().skip(1)) {
+            let next_center = center_so_far + (left.weight + right.weight) / 2.0;
+            if target <= next_center {
+                let ratio = (target - center_so_far) / (next_center - center_so_far);
+                return Some(left.mean + (right.mean - left.mean) * ratio);
+            }
+            center_so_far = next_center;
+        }
+        let rest = total_weight - center_so_far;
+        Some(last.mean + (self.max - last.mean) * (target - center_so_far) / rest)
+    }
+}
+
+impl AggregateState for PercentileState {
+    fn update(&mut self, value: NnSqlValue) -> Result<()> {
+        let x = value.to_f64_loose()?;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+
+        self.buffer.push(x);
+        if self.buffer.len() >= TDIGEST_BUFFER_SIZE {
+            self.compress();
+        }
+        Ok(())
+    }
+
+    fn merge(&mut self, other: Self) -> Result<()> {
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.centroids.extend(other.centroids);
+        self.buffer.extend(other.buffer);
+        self.compress();
+        Ok(())
+    }
+
+    fn result(&self) -> SqlValue {
+        let quantile = if self.buffer.is_empty() {
+            self.quantile()
+        } else {
+            let mut compressed = self.clone();
+            compressed.compress();
+            compressed.quantile()
+        };
+        quantile.map_or(SqlValue::Null, |v| {
+            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(v as f32)))
+        })
+    }
+}
+
+impl MemSize for PercentileState {
+    fn mem_size(&self) -> usize {
+        self.centroids.capacity() * size_of::<Centroid>()
+            + self.buffer.capacity() * size_of::<f64>()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,5 +663,274 @@ mod tests {
         state.next(400.);
         state.next(100.);
         assert_eq!(state.finalize().round() as i32, 200);
+
+        let mut state = AvgState::default();
+        state.update(NnSqlValue::UnsignedInteger(100)).unwrap();
+        state.update(NnSqlValue::UnsignedBigInt(300)).unwrap();
+        assert_eq!(float_result(state), 200.0);
+    }
+
+    #[test]
+    fn test_sum_state() {
+        let mut state = SumState::default();
+        state.update(NnSqlValue::SmallInt(i16::MAX)).unwrap();
+        state.update(NnSqlValue::SmallInt(i16::MAX)).unwrap();
+        assert_eq!(
+            state.result(),
+            SqlValue::NotNull(NnSqlValue::BigInt(2 * i16::MAX as i64))
+        );
+
+        let mut state = SumState::default();
+        state.update(NnSqlValue::Float(OrderedFloat(1.5))).unwrap();
+        state.update(NnSqlValue::Float(OrderedFloat(2.0))).unwrap();
+        assert_eq!(
+            state.result(),
+            SqlValue::NotNull(NnSqlValue::Float(OrderedFloat(3.5)))
+        );
+
+        assert!(matches!(SumState::default().result(), SqlValue::Null));
+
+        let mut state = SumState::default();
+        state.update(NnSqlValue::Integer(1)).unwrap();
+        assert!(state.update(NnSqlValue::Text("2".to_string())).is_err());
+        assert_eq!(state.result(), SqlValue::NotNull(NnSqlValue::BigInt(1)));
+    }
+
+    #[test]
+    fn test_count_min_max_state() {
+        let mut count = CountState::default();
+        let mut min = MinState::default();
+        let mut max = MaxState::default();
+        for v in [3, 1, 2] {
+            count.update(NnSqlValue::Integer(v)).unwrap();
+            min.update(NnSqlValue::Integer(v)).unwrap();
+            max.update(NnSqlValue::Integer(v)).unwrap();
+        }
+        assert_eq!(count.result(), SqlValue::NotNull(NnSqlValue::BigInt(3)));
+        assert_eq!(min.result(), SqlValue::NotNull(NnSqlValue::Integer(1)));
+        assert_eq!(max.result(), SqlValue::NotNull(NnSqlValue::Integer(3)));
+
+        assert_eq!(
+            CountState::default().result(),
+            SqlValue::NotNull(NnSqlValue::BigInt(0))
+        );
+        assert!(matches!(MinState::default().result(), SqlValue::Null));
+        assert!(matches!(MaxState::default().result(), SqlValue::Null));
+    }
+
+    #[test]
+    fn test_count_distinct_state() {
+        let mut state = CountDistinctState::default();
+        state.update(NnSqlValue::SmallInt(42)).unwrap();
+        state.update(NnSqlValue::Integer(42)).unwrap();
+        state.update(NnSqlValue::Text("42".to_string())).unwrap();
+        assert!(state.mem_size() > 0);
+        assert_eq!(state.result(), SqlValue::NotNull(NnSqlValue::BigInt(2)));
+    }
+
+    #[test]
+    fn test_approx_count_distinct_state() {
+        assert_eq!(ApproxCountDistinctState::default().mem_size(), 0);
+        assert_eq!(
+            ApproxCountDistinctState::default().result(),
+            SqlValue::NotNull(NnSqlValue::BigInt(0))
+        );
+
+        for n in [1, 10, 1_000, 100_000] {
+            let mut state = ApproxCountDistinctState::default();
+            for i in 0..n {
+                // each value twice
+                state.update(NnSqlValue::BigInt(i)).unwrap();
+                state.update(NnSqlValue::Integer(i as i32)).unwrap();
+            }
+            assert_eq!(state.mem_size(), 1 << HLL_PRECISION);
+
+            let estimate = match state.result() {
+                SqlValue::NotNull(NnSqlValue::BigInt(v)) => v,
+                v => panic!("unexpected result {:?}", v),
+            };
+            assert!(
+                ((estimate - n) as f64).abs() <= (n as f64 * 0.05).max(1.0),
+                "n = {}, estimate = {}",
+                n,
+                estimate
+            );
+        }
+    }
+
+    #[test]
+    fn test_arg_extreme_state() {
+        // (value, by)
+        let rows = [(1, 20), (2, 10), (3, 30), (4, 10), (5, 30)];
+        let result = |kind| {
+            let mut state = ArgExtremeState::new(kind);
+            for (value, by) in rows {
+                state
+                    .update(NnSqlValue::Integer(value), NnSqlValue::Integer(by))
+                    .unwrap();
+            }
+            state.result()
+        };
+
+        assert_eq!(
+            result(ArgExtremeKind::Min),
+            SqlValue::NotNull(NnSqlValue::Integer(2))
+        );
+        assert_eq!(
+            result(ArgExtremeKind::Max),
+            SqlValue::NotNull(NnSqlValue::Integer(3))
+        );
+        assert_eq!(
+            result(ArgExtremeKind::MaxOrLatest),
+            SqlValue::NotNull(NnSqlValue::Integer(5))
+        );
+        assert!(matches!(
+            ArgExtremeState::new(ArgExtremeKind::Min).result(),
+            SqlValue::Null
+        ));
+    }
+
+    fn float_result(state: impl AggregateState) -> f32 {
+        match state.result() {
+            SqlValue::NotNull(NnSqlValue::Float(v)) => v.into_inner(),
+            v => panic!("unexpected result {:?}", v),
+        }
+    }
+
+    #[test]
+    fn test_variance_state() {
+        let values = [2, 4, 4, 4, 5, 5, 7, 9];
+        let state = |kind| {
+            let mut state = VarianceState::new(kind);
+            for v in values {
+                state.update(NnSqlValue::Integer(v)).unwrap();
+            }
+            state
+        };
+
+        assert_eq!(float_result(state(VarianceKind::VarPop)), 4.0);
+        assert_eq!(float_result(state(VarianceKind::StddevPop)), 2.0);
+        assert!((float_result(state(VarianceKind::VarSamp)) - 32.0 / 7.0).abs() < 1e-5);
+        assert!(
+            (float_result(state(VarianceKind::StddevSamp)) - (32.0f32 / 7.0).sqrt()).abs() < 1e-5
+        );
+
+        let mut single = VarianceState::new(VarianceKind::VarSamp);
+        single.update(NnSqlValue::Integer(1)).unwrap();
+        assert!(matches!(single.result(), SqlValue::Null));
+        assert!(matches!(
+            VarianceState::new(VarianceKind::VarPop).result(),
+            SqlValue::Null
+        ));
+    }
+
+    #[test]
+    fn test_merge_states() {
+        fn merged<S: AggregateState>(new: impl Fn() -> S, left: &[i32], right: &[i32]) -> S {
+            let state = |values: &[i32]| {
+                let mut state = new();
+                for v in values {
+                    state.update(NnSqlValue::Integer(*v)).unwrap();
+                }
+                state
+            };
+            let mut left = state(left);
+            left.merge(state(right)).unwrap();
+            left
+        }
+
+        let (left, right) = ([2, 4, 4, 4], [5, 5, 7, 9]);
+
+        assert_eq!(float_result(merged(AvgState::default, &left, &right)), 5.0);
+        assert_eq!(
+            merged(SumState::default, &left, &right).result(),
+            SqlValue::NotNull(NnSqlValue::BigInt(40))
+        );
+        assert_eq!(
+            merged(CountState::default, &left, &right).result(),
+            SqlValue::NotNull(NnSqlValue::BigInt(8))
+        );
+        assert_eq!(
+            merged(CountDistinctState::default, &left, &right).result(),
+            SqlValue::NotNull(NnSqlValue::BigInt(5))
+        );
+        assert_eq!(
+            merged(ApproxCountDistinctState::default, &left, &right).result(),
+            SqlValue::NotNull(NnSqlValue::BigInt(5))
+        );
+        assert_eq!(
+            merged(ApproxCountDistinctState::default, &[], &right).result(),
+            SqlValue::NotNull(NnSqlValue::BigInt(3))
+        );
+        assert_eq!(
+            merged(MinState::default, &right, &left).result(),
+            SqlValue::NotNull(NnSqlValue::Integer(2))
+        );
+        assert_eq!(
+            merged(MaxState::default, &right, &[]).result(),
+            SqlValue::NotNull(NnSqlValue::Integer(9))
+        );
+        assert_eq!(
+            float_result(merged(
+                || VarianceState::new(VarianceKind::VarPop),
+                &left,
+                &right
+            )),
+            4.0
+        );
+        assert_eq!(
+            float_result(merged(|| PercentileState::new(1.0), &left, &right)),
+            9.0
+        );
+        assert!(matches!(
+            merged(|| PercentileState::new(0.5), &[], &[]).result(),
+            SqlValue::Null
+        ));
+
+        let mut arg_max = ArgExtremeState::new(ArgExtremeKind::MaxOrLatest);
+        arg_max
+            .update(NnSqlValue::Integer(1), NnSqlValue::Integer(10))
+            .unwrap();
+        let mut other = ArgExtremeState::new(ArgExtremeKind::MaxOrLatest);
+        other
+            .update(NnSqlValue::Integer(2), NnSqlValue::Integer(10))
+            .unwrap();
+        arg_max.merge(other).unwrap();
+        assert_eq!(arg_max.result(), SqlValue::NotNull(NnSqlValue::Integer(2)));
+    }
+
+    #[test]
+    fn test_percentile_state() {
+        const N: u32 = 100_000;
+
+        let state = |percentile| {
+            let mut state = PercentileState::new(percentile);
+            // 0, 1, ..., N - 1 in a shuffled order
+            for i in 0..N {
+                let v = (i as u64 * 7919 % N as u64) as u32;
+                state.update(NnSqlValue::UnsignedInteger(v)).unwrap();
+            }
+            state
+        };
+
+        for percentile in [0.0, 0.01, 0.5, 0.95, 0.99, 1.0] {
+            let expected = percentile as f32 * (N - 1) as f32;
+            let actual = float_result(state(percentile));
+            assert!(
+                (actual - expected).abs() < N as f32 * 0.005,
+                "percentile {}: expected {} but got {}",
+                percentile,
+                expected,
+                actual
+            );
+        }
+
+        // memory is bounded regardless of the number of values
+        assert!(state(0.5).mem_size() < 16 * 1024);
+
+        let mut single = PercentileState::new(0.95);
+        single.update(NnSqlValue::Integer(42)).unwrap();
+        assert_eq!(float_result(single), 42.0);
+        assert!(matches!(PercentileState::new(0.5).result(), SqlValue::Null));
     }
 }
@@ -1,18 +1,19 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
 use crate::{
-    expr_resolver::ExprResolver,
+    api::error::Result,
+    expr_resolver::{ExprResolver, ValueExprLabel},
     mem_size::MemSize,
-    pipeline::{Field, JoinParameter, JoinType, WindowOperationParameter},
+    pipeline::{Field, JoinParameter, WindowOperationParameter},
     stream_engine::{
         autonomous_executor::{
             performance_metrics::WindowInFlowByWindowTask,
             task::{tuple::Tuple, window::panes::pane::Pane},
         },
         time::SpringTimestamp,
-        RowTime, SqlValue,
+        NnSqlValue, RowTime, SqlValue,
     },
 };
 
@@ -36,6 +37,7 @@ pub struct JoinPane {
 impl Pane for JoinPane {
     type CloseOut = Tuple;
     type DispatchArg = JoinDir;
+    type SessionKey = ();
 
     /// # Panics
     ///
@@ -68,68 +70,164 @@ impl Pane for JoinPane {
         self.close_at
     }
 
+    /// All rows share sessions.
+    fn session_key(
+        _param: &WindowOperationParameter,
+        _expr_resolver: &ExprResolver,
+        _tuple: &Tuple,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn extend(&mut self, open_at: SpringTimestamp, close_at: SpringTimestamp) {
+        self.open_at = self.open_at.min(open_at);
+        self.close_at = self.close_at.max(close_at);
+    }
+
+    fn merge(&mut self, other: &Self) -> Result<WindowInFlowByWindowTask> {
+        self.extend(other.open_at, other.close_at);
+        self.left_tuples.extend(other.left_tuples.iter().cloned());
+        self.right_tuples.extend(other.right_tuples.iter().cloned());
+        Ok(WindowInFlowByWindowTask::zero())
+    }
+
     /// Dispatch to left_tuples
     fn dispatch(
         &mut self,
         _expr_resolver: &ExprResolver,
         tuple: &Tuple,
         dir: JoinDir,
-    ) -> WindowInFlowByWindowTask {
+    ) -> Result<WindowInFlowByWindowTask> {
         match dir {
             JoinDir::Left => self.left_tuples.push(tuple.clone()),
             JoinDir::Right => self.right_tuples.push(tuple.clone()),
         }
-        WindowInFlowByWindowTask::new(0, tuple.mem_size() as i64)
+        Ok(WindowInFlowByWindowTask::new(0, tuple.mem_size() as i64))
+    }
+
+    fn emit(&self, expr_resolver: &ExprResolver) -> Result<Vec<Self::CloseOut>> {
+        self.join(expr_resolver)
     }
 
     fn close(
         self,
         expr_resolver: &ExprResolver,
-    ) -> (Vec<Self::CloseOut>, WindowInFlowByWindowTask) {
-        match self.join_parameter.join_type {
-            JoinType::LeftOuter => self.left_outer_join(expr_resolver),
-        }
+    ) -> Result<(Vec<Self::CloseOut>, WindowInFlowByWindowTask)> {
+        Ok((
+            self.emit(expr_resolver)?,
+            self.calc_window_in_flow_on_close(),
+        ))
     }
 }
 
 impl JoinPane {
-    fn left_outer_join(
-        self,
-        expr_resolver: &ExprResolver,
-    ) -> (Vec<Tuple>, WindowInFlowByWindowTask) {
-        let window_in_flow = self.calc_window_in_flow_on_close();
-
+    /// Joined tuples in the order of left tuples, followed by unmatched right tuples (for RIGHT and FULL OUTER JOIN).
+    ///
+    /// # Failures
+    ///
+    /// - `SpringError::Sql` when:
+    ///   - an equi-key or the ON condition cannot be evaluated (the ON condition as BOOLEAN).
+    fn join(&self, expr_resolver: &ExprResolver) -> Result<Vec<Tuple>> {
+        let join_type = self.join_parameter.join_type;
         let null_right = self.null_right_tuple();
 
-        // using Nested Loop Join.
+        let mut right_matched = vec![false; self.right_tuples.len()];
+
+        // using Hash Join if ON condition has equi-keys, Nested Loop Join otherwise.
+        let right_index = self.right_index(expr_resolver)?;
+
         let mut res_tuples = Vec::new();
-        for left_tuple in self.left_tuples {
+        for left_tuple in &self.left_tuples {
             let mut joined_to_the_left = vec![];
 
-            for right_tuple in &self.right_tuples {
+            let right_candidates: Vec<usize> = match &right_index {
+                Some(right_index) => self
+                    .keys(
+                        expr_resolver,
+                        self.join_parameter.equi_keys.iter().map(|(left, _)| *left),
+                        left_tuple,
+                    )?
+                    .and_then(|key| right_index.get(&key).cloned())
+                    .unwrap_or_default(),
+                None => (0..self.right_tuples.len()).collect(),
+            };
+
+            for right_idx in right_candidates {
+                let right_tuple = &self.right_tuples[right_idx];
+
                 // TODO less clone. ExprResolver takes two tuples to resolve ColumnReference?
                 let joined_tuple = left_tuple.clone().join(right_tuple.clone());
 
                 let on_bool = expr_resolver
-                    .eval_value_expr(self.join_parameter.on_expr, &joined_tuple)
-                    .expect("TODO Result")
-                    .to_bool()
-                    .expect("TODO Result");
+                    .eval_value_expr(self.join_parameter.on_expr, &joined_tuple)?
+                    .to_bool()?;
 
                 if on_bool {
                     joined_to_the_left.push(joined_tuple);
+                    right_matched[right_idx] = true;
                 }
             }
 
-            if joined_to_the_left.is_empty() {
-                let joined_tuple = left_tuple.join(null_right.clone());
+            if joined_to_the_left.is_empty() && join_type.keeps_unmatched_left() {
+                let joined_tuple = left_tuple.clone().join(null_right.clone());
                 joined_to_the_left.push(joined_tuple);
             }
 
             res_tuples.extend(joined_to_the_left);
         }
 
-        (res_tuples, window_in_flow)
+        if join_type.keeps_unmatched_right() {
+            for (right_tuple, matched) in self.right_tuples.iter().zip(right_matched) {
+                if !matched {
+                    let null_left = self.null_left_tuple(right_tuple.rowtime());
+                    res_tuples.push(null_left.join(right_tuple.clone()));
+                }
+            }
+        }
+
+        Ok(res_tuples)
+    }
+
+    /// Indices of right tuples by their equi-keys (in the order of right tuples). `None` if ON condition has no equi-key.
+    fn right_index(
+        &self,
+        expr_resolver: &ExprResolver,
+    ) -> Result<Option<HashMap<Vec<NnSqlValue>, Vec<usize>>>> {
+        if self.join_parameter.equi_keys.is_empty() {
+            return Ok(None);
+        }
+
+        let mut index: HashMap<Vec<NnSqlValue>, Vec<usize>> = HashMap::new();
+        for (right_idx, right_tuple) in self.right_tuples.iter().enumerate() {
+            if let Some(key) = self.keys(
+                expr_resolver,
+                self.join_parameter
+                    .equi_keys
+                    .iter()
+                    .map(|(_, right)| *right),
+                right_tuple,
+            )? {
+                index.entry(key).or_default().push(right_idx);
+            }
+        }
+        Ok(Some(index))
+    }
+
+    /// `None` if any of the keys is NULL, which never satisfies `=`.
+    fn keys(
+        &self,
+        expr_resolver: &ExprResolver,
+        key_exprs: impl Iterator<Item = ValueExprLabel>,
+        tuple: &Tuple,
+    ) -> Result<Option<Vec<NnSqlValue>>> {
+        let mut keys = Vec::new();
+        for key_expr in key_exprs {
+            match expr_resolver.eval_value_expr(key_expr, tuple)? {
+                SqlValue::Null => return Ok(None),
+                SqlValue::NotNull(key) => keys.push(key),
+            }
+        }
+        Ok(Some(keys))
     }
 
     fn calc_window_in_flow_on_close(&self) -> WindowInFlowByWindowTask {
@@ -143,6 +241,18 @@ impl JoinPane {
         WindowInFlowByWindowTask::new(0, -((left_size + right_size) as i64))
     }
 
+    /// Joined tuple uses left rowtime. `rowtime` should be the right tuple's one.
+    fn null_left_tuple(&self, rowtime: RowTime) -> Tuple {
+        let fields = self
+            .join_parameter
+            .left_colrefs
+            .iter()
+            .map(|colref| Field::new(colref.clone(), SqlValue::Null))
+            .collect();
+
+        Tuple::new(rowtime, fields)
+    }
+
     fn null_right_tuple(&self) -> Tuple {
         // unused
         let rowtime =
@@ -93,6 +93,19 @@ impl TaskGraph {
             .collect()
     }
 
+    /// Output queues of `task_id` to tasks reading from `stream`.
+    pub fn output_queues_to_stream(&self, task_id: &TaskId, stream: &StreamName) -> Vec<QueueId> {
+        let i = self.find_node(task_id);
+        self.g
+            .edges_directed(i, petgraph::EdgeDirection::Outgoing)
+            .filter_map(|e| {
+                let queue_id_with_upstream = e.weight();
+                (&queue_id_with_upstream.upstream == stream)
+                    .then(|| queue_id_with_upstream.queue_id.clone())
+            })
+            .collect()
+    }
+
     /// # Returns
     ///
     /// `None` if `task_id` does not have incoming edge (queue) from `upstream`.
@@ -209,7 +222,7 @@ impl From<&Pipeline> for TaskGraph {
         // add all task ids
         pipeline_petgraph.edge_weights().for_each(|edge| {
             let task_id = TaskId::from(edge);
-            // duplicate task id on JOIN pump task (but it's ok)
+            // duplicate task id on JOIN pump task and pump task with late rows stream (but it's ok)
             task_graph.add_task(task_id);
         });
 
@@ -250,7 +263,9 @@ impl From<&Pipeline> for TaskGraph {
                         target,
                     );
                 }
-                Edge::Source(_) => {} // no queue is created for source task
+                // no queue is created for source task.
+                // input queues of a pump are created by its `Edge::Pump`.
+                Edge::Source(_) | Edge::LateRows(_) => {}
             };
         }
         task_graph
@@ -1,6 +1,6 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
-use crate::pipeline::{PumpModel, SinkWriterModel, SourceReaderModel, StreamModel};
+use crate::pipeline::{PumpModel, SinkWriterModel, SourceReaderModel, StreamModel, TableModel};
 
 #[allow(clippy::enum_variant_names)]
 #[derive(Clone, PartialEq, Debug)]
@@ -10,5 +10,6 @@ pub enum AlterPipelineCommand {
     CreateStream(StreamModel),
     CreateSinkStream(StreamModel),
     CreateSinkWriter(SinkWriterModel),
+    CreateTable(TableModel),
     CreatePump(Box<PumpModel>),
 }
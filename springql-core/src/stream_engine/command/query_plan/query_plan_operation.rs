@@ -1,8 +1,11 @@
 // This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
 
 use crate::{
-    expr_resolver::ExprLabel,
-    pipeline::{JoinParameter, StreamName, WindowOperationParameter, WindowParameter},
+    expr_resolver::{ExprLabel, ValueExprLabel},
+    pipeline::{
+        ColumnName, ColumnReference, EmitPolicy, JoinParameter, JoinType, StreamName,
+        WindowOperationParameter, WindowParameter,
+    },
 };
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -19,6 +22,12 @@ impl UpperOps {
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct LowerOps {
     pub join: JoinOp,
+
+    /// Applied to tuples emitted from `join` in order.
+    pub table_lookups: Vec<TableLookupOp>,
+
+    /// Applied to tuples emitted from `join` and `table_lookups` (multi stream selection).
+    pub selection: Option<SelectionOp>,
 }
 impl LowerOps {
     pub fn has_window(&self) -> bool {
@@ -31,9 +40,16 @@ pub struct ProjectionOp {
     pub expr_labels: Vec<ExprLabel>,
 }
 
+/// WHERE clause
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SelectionOp {
+    pub where_condition: ValueExprLabel,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct GroupAggregateWindowOp {
     pub window_param: WindowParameter,
+    pub emit_policy: EmitPolicy,
     pub op_param: WindowOperationParameter,
 }
 
@@ -42,18 +58,45 @@ pub struct CollectOp {
     pub stream: StreamName,
 }
 
-/// TODO recursive join
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum JoinOp {
     Collect(CollectOp),
     JoinWindow(JoinWindowOp),
 }
+impl JoinOp {
+    /// Streams to collect from. The leftmost stream comes first.
+    pub fn streams(&self) -> Vec<&StreamName> {
+        match self {
+            JoinOp::Collect(collect) => vec![&collect.stream],
+            JoinOp::JoinWindow(JoinWindowOp { left, right, .. }) => {
+                let mut streams = left.streams();
+                streams.push(&right.stream);
+                streams
+            }
+        }
+    }
+}
 
+/// A join of 3 or more streams is a left-deep tree of join windows: `(s1 JOIN s2) JOIN s3`.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct JoinWindowOp {
-    pub left: CollectOp,
+    pub left: Box<JoinOp>,
     pub right: CollectOp,
 
     pub window_param: WindowParameter,
+    pub emit_policy: EmitPolicy,
     pub join_param: JoinParameter,
 }
+
+/// `JOIN table ON table.key_column = probe` without window.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TableLookupOp {
+    pub table: StreamName,
+    /// `Inner` or `LeftOuter`
+    pub join_type: JoinType,
+    pub key_column: ColumnName,
+    /// Evaluated with an input tuple to get the key to look up.
+    pub probe: ValueExprLabel,
+    /// To fill NULLs for unmatched tuples in LEFT OUTER JOIN.
+    pub table_colrefs: Vec<ColumnReference>,
+}
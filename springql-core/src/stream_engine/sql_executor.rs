@@ -6,6 +6,7 @@ use crate::{
     api::error::Result,
     pipeline::{
         Pipeline, PipelineVersion, PumpModel, SinkWriterModel, SourceReaderModel, StreamModel,
+        TableModel,
     },
     stream_engine::command::AlterPipelineCommand,
 };
@@ -52,6 +53,7 @@ impl SqlExecutor {
             AlterPipelineCommand::CreateSinkWriter(sink_writer) => {
                 Self::create_sink_writer(pipeline, sink_writer)
             }
+            AlterPipelineCommand::CreateTable(table) => Self::create_table(pipeline, table),
             AlterPipelineCommand::CreatePump(pump) => Self::create_pump(pipeline, *pump),
         }
     }
@@ -88,6 +90,11 @@ impl SqlExecutor {
         Ok(pipeline)
     }
 
+    fn create_table(mut pipeline: Pipeline, table: TableModel) -> Result<Pipeline> {
+        pipeline.add_table(Arc::new(table))?;
+        Ok(pipeline)
+    }
+
     fn create_pump(mut pipeline: Pipeline, pump: PumpModel) -> Result<Pipeline> {
         pipeline.add_pump(pump)?;
         Ok(pipeline)
@@ -61,6 +61,16 @@ impl SpringTimestamp {
         }
     }
 
+    /// `None` on overflow.
+    pub fn checked_add(self, rhs: Duration) -> Option<Self> {
+        self.0.checked_add(rhs).map(Self)
+    }
+
+    /// `None` on overflow.
+    pub fn checked_sub(self, rhs: Duration) -> Option<Self> {
+        self.0.checked_sub(rhs).map(Self)
+    }
+
     fn try_parse_original(s: &str) -> Result<Self> {
         let ndt = NaiveDateTime::parse_from_str(s)
             .with_context(|| format!("failed to parse timestamp: {}", s))
@@ -130,6 +130,16 @@ impl NaiveDateTime {
     pub fn format(&self) -> String {
         format_primitive(&self.0).unwrap() // TODO: avoid panic
     }
+
+    /// `None` on overflow.
+    pub fn checked_add(self, rhs: Duration) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// `None` on overflow.
+    pub fn checked_sub(self, rhs: Duration) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
 }
 
 impl Add<Duration> for NaiveDateTime {
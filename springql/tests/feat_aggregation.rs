@@ -242,3 +242,1254 @@ fn test_feat_aggregation_with_2_group_bys() -> Result<()> {
 
     Ok(())
 }
+
+fn push_trade_rows_with_null(pipeline: &SpringPipeline, source_queue_name: &str) {
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}"#,
+        r#"{"ts": "2020-01-01 00:00:05.000000000", "ticker": "GOOGL"}"#,
+        r#"{"ts": "2020-01-01 00:00:09.999999999", "ticker": "GOOGL", "amount": 30}"#,
+        r#"{"ts": "2020-01-01 00:00:10.000000000", "ticker": "IBM"}"#,
+        r#"{"ts": "2020-01-01 00:00:20.000000000", "ticker": "IBM", "amount": 70}"#,
+    ];
+    for row in source_rows {
+        pipeline
+            .push(source_queue_name, SpringSourceRow::from_json(row).unwrap())
+            .unwrap();
+    }
+}
+
+#[test]
+fn test_feat_aggregate_functions() -> Result<()> {
+    setup_test_logger();
+
+    let aggregates = [
+        ("sum", "SUM(source_trade.amount)"),
+        ("count_all", "COUNT(*)"),
+        ("count", "COUNT(source_trade.amount)"),
+        ("count_distinct", "COUNT(DISTINCT source_trade.ticker)"),
+        (
+            "approx_count_distinct",
+            "APPROX_COUNT_DISTINCT(source_trade.ticker)",
+        ),
+        ("min", "MIN(source_trade.amount)"),
+        ("max", "MAX(source_trade.amount)"),
+    ];
+
+    let mut ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_aggregate_functions'
+          );
+        "
+        .to_string(),
+    ];
+    for (name, aggregate) in aggregates {
+        ddls.push(format!(
+            "
+            CREATE SINK STREAM sink_{name} (
+              v BIGINT
+            );
+            "
+        ));
+        ddls.push(format!(
+            "
+            CREATE PUMP pu_{name} AS
+            INSERT INTO sink_{name} (v)
+            SELECT STREAM
+                {aggregate} AS v
+            FROM source_trade
+            FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+            "
+        ));
+        ddls.push(format!(
+            "
+            CREATE SINK WRITER q_sink_{name} FOR sink_{name}
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME 'q_sink_aggregate_functions_{name}'
+              );
+            "
+        ));
+    }
+
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+    push_trade_rows_with_null(&pipeline, "q_source_aggregate_functions");
+
+    // [00:00, 00:10) has 10, NULL and 30 (ORCL, GOOGL and GOOGL); [00:10, 00:20) has only NULL (IBM).
+    let expected = [
+        ("sum", Some(40), None),
+        ("count_all", Some(3), Some(1)),
+        ("count", Some(2), Some(0)),
+        ("count_distinct", Some(2), Some(1)),
+        ("approx_count_distinct", Some(2), Some(1)),
+        ("min", Some(10), None),
+        ("max", Some(30), None),
+    ];
+    for (name, expected_00, expected_10) in expected {
+        let queue = format!("q_sink_aggregate_functions_{}", name);
+        for expected_v in [expected_00, expected_10] {
+            let row = pipeline.pop(&queue)?;
+            match expected_v {
+                Some(v) => assert_eq!(row.get_not_null_by_index::<i64>(0)?, v, "{}", name),
+                None => assert!(
+                    matches!(
+                        row.get_not_null_by_index::<i64>(0),
+                        Err(SpringError::Null { i_col: 0 })
+                    ),
+                    "{}",
+                    name
+                ),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_aggregate_function_type_error() {
+    setup_test_logger();
+
+    let pipeline = apply_ddls(
+        &[
+            "
+            CREATE SOURCE STREAM source_trade (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              ticker TEXT NOT NULL,
+              amount INTEGER
+            );
+            "
+            .to_string(),
+            "
+            CREATE SINK STREAM sink_sum (
+              v BIGINT
+            );
+            "
+            .to_string(),
+        ],
+        SpringConfig::default(),
+    );
+
+    assert!(pipeline
+        .command(
+            "
+            CREATE PUMP pu_sum AS
+            INSERT INTO sink_sum (v)
+            SELECT STREAM
+                SUM(source_trade.ticker) AS v
+            FROM source_trade
+            FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+            ",
+        )
+        .is_err());
+}
+
+/// A row failing to be aggregated is skipped (and logged) without stopping the pipeline.
+#[test]
+fn test_feat_aggregate_evaluation_error() -> Result<()> {
+    setup_test_logger();
+
+    let ddls = [
+        "
+        CREATE SOURCE STREAM source_ratio (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          a INTEGER NOT NULL,
+          b INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_sum_ratio (
+          v BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_sum_ratio AS
+        INSERT INTO sink_sum_ratio (v)
+        SELECT STREAM
+            SUM(source_ratio.a / source_ratio.b) AS v
+        FROM source_ratio
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_sum_ratio FOR sink_sum_ratio
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_sum_ratio'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_ratio FOR source_ratio
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_ratio'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "a": 10, "b": 2}"#,
+        // division by zero
+        r#"{"ts": "2020-01-01 00:00:01.000000000", "a": 1, "b": 0}"#,
+        r#"{"ts": "2020-01-01 00:00:05.000000000", "a": 8, "b": 4}"#,
+        // closes [00:00, 00:10)
+        r#"{"ts": "2020-01-01 00:00:10.000000000", "a": 0, "b": 1}"#,
+    ];
+    for row in source_rows {
+        pipeline.push("q_source_ratio", SpringSourceRow::from_json(row)?)?;
+    }
+
+    let row = pipeline.pop("q_sink_sum_ratio")?;
+    assert_eq!(row.get_not_null_by_index::<i64>(0)?, 7);
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_multiple_aggregates_in_expressions() -> Result<()> {
+    setup_test_logger();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_trade_stats (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          avg_amount FLOAT,
+          amount_range BIGINT,
+          n BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_trade_stats AS
+        INSERT INTO sink_trade_stats (ts, ticker, avg_amount, amount_range, n)
+        SELECT STREAM
+            FLOOR_TIME(source_trade.ts, DURATION_SECS(10)) AS min_ts,
+            source_trade.ticker AS ticker,
+            AVG(source_trade.amount) AS avg_amount,
+            MAX(source_trade.amount) - MIN(source_trade.amount) AS amount_range,
+            COUNT(*) AS n
+        FROM source_trade
+        GROUP BY min_ts, ticker
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_trade_stats FOR sink_trade_stats
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_multiple_aggregates'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_multiple_aggregates'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}"#,
+        r#"{"ts": "2020-01-01 00:00:01.000000000", "ticker": "ORCL", "amount": 40}"#,
+        r#"{"ts": "2020-01-01 00:00:05.000000000", "ticker": "GOOGL"}"#,
+        r#"{"ts": "2020-01-01 00:00:09.999999999", "ticker": "GOOGL", "amount": 30}"#,
+        r#"{"ts": "2020-01-01 00:00:10.000000000", "ticker": "IBM"}"#,
+        r#"{"ts": "2020-01-01 00:00:20.000000000", "ticker": "IBM", "amount": 70}"#,
+    ];
+    for row in source_rows {
+        pipeline.push(
+            "q_source_multiple_aggregates",
+            SpringSourceRow::from_json(row).unwrap(),
+        )?;
+    }
+
+    let mut rows = (0..3)
+        .map(|_| pipeline.pop("q_sink_multiple_aggregates"))
+        .collect::<Result<Vec<_>>>()?;
+    rows.sort_by_key(|row| {
+        (
+            row.get_not_null_by_index::<String>(0).unwrap(),
+            row.get_not_null_by_index::<String>(1).unwrap(),
+        )
+    });
+
+    assert_eq!(rows[0].get_not_null_by_index::<String>(1)?, "GOOGL");
+    assert_eq!(rows[0].get_not_null_by_index::<f32>(2)?, 30.0);
+    assert_eq!(rows[0].get_not_null_by_index::<i64>(3)?, 0);
+    assert_eq!(rows[0].get_not_null_by_index::<i64>(4)?, 2);
+
+    assert_eq!(rows[1].get_not_null_by_index::<String>(1)?, "ORCL");
+    assert_eq!(rows[1].get_not_null_by_index::<f32>(2)?, 25.0);
+    assert_eq!(rows[1].get_not_null_by_index::<i64>(3)?, 30);
+    assert_eq!(rows[1].get_not_null_by_index::<i64>(4)?, 2);
+
+    assert_eq!(rows[2].get_not_null_by_index::<String>(1)?, "IBM");
+    assert!(matches!(
+        rows[2].get_not_null_by_index::<f32>(2),
+        Err(SpringError::Null { i_col: 2 })
+    ));
+    assert!(matches!(
+        rows[2].get_not_null_by_index::<i64>(3),
+        Err(SpringError::Null { i_col: 3 })
+    ));
+    assert_eq!(rows[2].get_not_null_by_index::<i64>(4)?, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_misplaced_aggregates() {
+    setup_test_logger();
+
+    let pipeline = apply_ddls(
+        &[
+            "
+            CREATE SOURCE STREAM source_trade (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              ticker TEXT NOT NULL,
+              amount INTEGER
+            );
+            "
+            .to_string(),
+            "
+            CREATE SINK STREAM sink_sum (
+              v BIGINT
+            );
+            "
+            .to_string(),
+        ],
+        SpringConfig::default(),
+    );
+
+    // aggregate in WHERE
+    assert!(pipeline
+        .command(
+            "
+            CREATE PUMP pu_sum AS
+            INSERT INTO sink_sum (v)
+            SELECT STREAM
+                SUM(source_trade.amount) AS v
+            FROM source_trade
+            WHERE MAX(source_trade.amount) > 10
+            FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+            ",
+        )
+        .is_err());
+
+    // aggregate without window
+    assert!(pipeline
+        .command(
+            "
+            CREATE PUMP pu_sum AS
+            INSERT INTO sink_sum (v)
+            SELECT STREAM
+                SUM(source_trade.amount) + 1 AS v
+            FROM source_trade;
+            ",
+        )
+        .is_err());
+}
+
+#[test]
+fn test_feat_statistical_aggregates() -> Result<()> {
+    setup_test_logger();
+
+    let ddls = [
+        "
+        CREATE SOURCE STREAM source_sensor (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          value FLOAT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_stats (
+          stddev_pop FLOAT,
+          stddev_samp FLOAT,
+          var_pop FLOAT,
+          var_samp FLOAT,
+          median FLOAT
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_stats AS
+        INSERT INTO sink_stats (stddev_pop, stddev_samp, var_pop, var_samp, median)
+        SELECT STREAM
+            STDDEV_POP(source_sensor.value),
+            STDDEV_SAMP(source_sensor.value),
+            VAR_POP(source_sensor.value),
+            VAR_SAMP(source_sensor.value),
+            PERCENTILE_APPROX(source_sensor.value, 0.5)
+        FROM source_sensor
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_stats FOR sink_stats
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_stats'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_sensor FOR source_sensor
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_sensor'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    for (i, value) in [2, 4, 4, 4, 5, 5, 7, 9].into_iter().enumerate() {
+        let row = format!(
+            r#"{{"ts": "2020-01-01 00:00:0{}.000000000", "value": {}}}"#,
+            i, value
+        );
+        pipeline.push("q_source_sensor", SpringSourceRow::from_json(&row)?)?;
+    }
+    // closes [00:00, 00:10)
+    pipeline.push(
+        "q_source_sensor",
+        SpringSourceRow::from_json(r#"{"ts": "2020-01-01 00:00:10.000000000", "value": 0}"#)?,
+    )?;
+
+    let row = pipeline.pop("q_sink_stats")?;
+    let col = |i| row.get_not_null_by_index::<f32>(i).unwrap();
+    assert_eq!(col(0), 2.0);
+    assert!((col(1) - (32.0f32 / 7.0).sqrt()).abs() < 1e-5);
+    assert_eq!(col(2), 4.0);
+    assert!((col(3) - 32.0 / 7.0).abs() < 1e-5);
+    assert_eq!(col(4), 4.5);
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_percentile_approx_argument_error() {
+    setup_test_logger();
+
+    let pipeline = apply_ddls(
+        &[
+            "
+            CREATE SOURCE STREAM source_sensor (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              value FLOAT NOT NULL
+            );
+            "
+            .to_string(),
+            "
+            CREATE SINK STREAM sink_stats (
+              v FLOAT
+            );
+            "
+            .to_string(),
+        ],
+        SpringConfig::default(),
+    );
+
+    for aggregate in [
+        "PERCENTILE_APPROX(source_sensor.value, 1.5)",
+        "PERCENTILE_APPROX(source_sensor.value, source_sensor.value)",
+        "PERCENTILE_APPROX(source_sensor.value)",
+        "STDDEV_POP(source_sensor.value, 0.5)",
+    ] {
+        assert!(
+            pipeline
+                .command(format!(
+                    "
+                    CREATE PUMP pu_stats AS
+                    INSERT INTO sink_stats (v)
+                    SELECT STREAM
+                        {aggregate} AS v
+                    FROM source_sensor
+                    FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+                    "
+                ))
+                .is_err(),
+            "{}",
+            aggregate
+        );
+    }
+}
+
+#[test]
+fn test_feat_first_last_and_arg_aggregates() -> Result<()> {
+    setup_test_logger();
+
+    let ddls = [
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_sampled (
+          first_amount INTEGER,
+          last_amount INTEGER,
+          min_ticker TEXT,
+          max_ticker TEXT
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_sampled AS
+        INSERT INTO sink_sampled (first_amount, last_amount, min_ticker, max_ticker)
+        SELECT STREAM
+            FIRST_VALUE(source_trade.amount),
+            LAST_VALUE(source_trade.amount),
+            ARG_MIN(source_trade.ticker, source_trade.amount),
+            ARG_MAX(source_trade.ticker, source_trade.amount)
+        FROM source_trade
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_sampled FOR sink_sampled
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_sampled'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_sampled'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+    push_trade_rows_with_null(&pipeline, "q_source_sampled");
+
+    // [00:00, 00:10): (ORCL, 10), (GOOGL, NULL), (GOOGL, 30)
+    let row = pipeline.pop("q_sink_sampled")?;
+    assert_eq!(row.get_not_null_by_index::<i32>(0)?, 10);
+    assert_eq!(row.get_not_null_by_index::<i32>(1)?, 30);
+    assert_eq!(row.get_not_null_by_index::<String>(2)?, "ORCL");
+    assert_eq!(row.get_not_null_by_index::<String>(3)?, "GOOGL");
+
+    // [00:10, 00:20): (IBM, NULL)
+    let row = pipeline.pop("q_sink_sampled")?;
+    for i_col in 0..4 {
+        assert!(matches!(
+            row.get_not_null_by_index::<i32>(i_col),
+            Err(SpringError::Null { .. })
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_having() -> Result<()> {
+    setup_test_logger();
+
+    let ddls = [
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_count (
+          ticker TEXT NOT NULL,
+          n BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_sum (
+          ticker TEXT NOT NULL,
+          total BIGINT
+        );
+        "
+        .to_string(),
+        // HAVING with an aggregate alias and an aggregate not in select list
+        "
+        CREATE PUMP pu_count AS
+        INSERT INTO sink_count (ticker, n)
+        SELECT STREAM
+            source_trade.ticker AS ticker,
+            COUNT(*) AS n
+        FROM source_trade
+        GROUP BY ticker
+        HAVING n >= 2 AND MAX(source_trade.amount) > 20
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        // HAVING with a GROUP BY element
+        "
+        CREATE PUMP pu_sum AS
+        INSERT INTO sink_sum (ticker, total)
+        SELECT STREAM
+            source_trade.ticker AS ticker,
+            SUM(source_trade.amount) AS total
+        FROM source_trade
+        GROUP BY ticker
+        HAVING source_trade.ticker <> 'GOOGL'
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_count FOR sink_count
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_having_count'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_sum FOR sink_sum
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_having_sum'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_having'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+    push_trade_rows_with_null(&pipeline, "q_source_having");
+
+    // [00:00, 00:10): ORCL (1 row), GOOGL (2 rows); [00:10, 00:20): IBM (1 row)
+    let row = pipeline.pop("q_sink_having_count")?;
+    assert_eq!(row.get_not_null_by_index::<String>(0)?, "GOOGL");
+    assert_eq!(row.get_not_null_by_index::<i64>(1)?, 2);
+    assert!(pipeline.pop_non_blocking("q_sink_having_count")?.is_none());
+
+    let row = pipeline.pop("q_sink_having_sum")?;
+    assert_eq!(row.get_not_null_by_index::<String>(0)?, "ORCL");
+    assert_eq!(row.get_not_null_by_index::<i64>(1)?, 10);
+    let row = pipeline.pop("q_sink_having_sum")?;
+    assert_eq!(row.get_not_null_by_index::<String>(0)?, "IBM");
+    assert!(matches!(
+        row.get_not_null_by_index::<i64>(1),
+        Err(SpringError::Null { i_col: 1 })
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_having_errors() {
+    setup_test_logger();
+
+    let pipeline = apply_ddls(
+        &[
+            "
+            CREATE SOURCE STREAM source_trade (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              ticker TEXT NOT NULL,
+              amount INTEGER
+            );
+            "
+            .to_string(),
+            "
+            CREATE SINK STREAM sink_sum (
+              ticker TEXT NOT NULL,
+              total BIGINT
+            );
+            "
+            .to_string(),
+        ],
+        SpringConfig::default(),
+    );
+
+    let pumps = [
+        // HAVING without aggregate
+        "
+        CREATE PUMP pu_sum AS
+        INSERT INTO sink_sum (ticker, total)
+        SELECT STREAM
+            source_trade.ticker AS ticker,
+            source_trade.amount AS total
+        FROM source_trade
+        HAVING source_trade.amount > 10
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        ",
+        // alias not in select list
+        "
+        CREATE PUMP pu_sum AS
+        INSERT INTO sink_sum (ticker, total)
+        SELECT STREAM
+            source_trade.ticker AS ticker,
+            SUM(source_trade.amount) AS total
+        FROM source_trade
+        GROUP BY ticker
+        HAVING average > 10
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        ",
+        // alias outside of HAVING clause
+        "
+        CREATE PUMP pu_sum AS
+        INSERT INTO sink_sum (ticker, total)
+        SELECT STREAM
+            source_trade.ticker AS ticker,
+            SUM(source_trade.amount) AS total
+        FROM source_trade
+        WHERE total > 10
+        GROUP BY ticker
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        ",
+    ];
+    for pump in pumps {
+        assert!(pipeline.command(pump).is_err(), "{}", pump);
+    }
+}
+
+#[test]
+fn test_feat_session_window() -> Result<()> {
+    setup_test_logger();
+
+    let ddls = [
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_session (
+          ticker TEXT NOT NULL,
+          n BIGINT NOT NULL,
+          total BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_session AS
+        INSERT INTO sink_session (ticker, n, total)
+        SELECT STREAM
+            source_trade.ticker AS ticker,
+            COUNT(*) AS n,
+            SUM(source_trade.amount) AS total
+        FROM source_trade
+        GROUP BY ticker
+        SESSION WINDOW DURATION_SECS(5), DURATION_SECS(10);
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_session FOR sink_session
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_session'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_session'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let source_rows = vec![
+        // ORCL: sessions [00:00, 00:07) and [00:09, 00:14)
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 1}"#,
+        r#"{"ts": "2020-01-01 00:00:02.000000000", "ticker": "ORCL", "amount": 2}"#,
+        r#"{"ts": "2020-01-01 00:00:09.000000000", "ticker": "ORCL", "amount": 4}"#,
+        // GOOGL: session [00:03, 00:08)
+        r#"{"ts": "2020-01-01 00:00:03.000000000", "ticker": "GOOGL", "amount": 10}"#,
+        // late row bridges the 2 ORCL sessions into [00:00, 00:14)
+        r#"{"ts": "2020-01-01 00:00:06.000000000", "ticker": "ORCL", "amount": 8}"#,
+        // watermark 00:20 closes ORCL and GOOGL sessions
+        r#"{"ts": "2020-01-01 00:00:30.000000000", "ticker": "IBM", "amount": 100}"#,
+        r#"{"ts": "2020-01-01 00:00:40.000000000", "ticker": "IBM", "amount": 200}"#,
+        // watermark 00:40 closes the first IBM session
+        r#"{"ts": "2020-01-01 00:00:50.000000000", "ticker": "IBM", "amount": 300}"#,
+    ];
+    for row in source_rows {
+        pipeline.push("q_source_session", SpringSourceRow::from_json(row)?)?;
+    }
+
+    let mut sessions = (0..3)
+        .map(|_| {
+            let row = pipeline.pop("q_sink_session")?;
+            Ok((
+                row.get_not_null_by_index::<String>(0)?,
+                row.get_not_null_by_index::<i64>(1)?,
+                row.get_not_null_by_index::<i64>(2)?,
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    sessions.sort();
+
+    assert_eq!(
+        sessions,
+        vec![
+            ("GOOGL".to_string(), 1, 10),
+            ("IBM".to_string(), 1, 100),
+            ("ORCL".to_string(), 4, 15),
+        ]
+    );
+    assert!(pipeline.pop_non_blocking("q_sink_session")?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_session_window_group_by_null() -> Result<()> {
+    setup_test_logger();
+
+    let ddls = [
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_session (
+          ticker TEXT,
+          n BIGINT NOT NULL,
+          total BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_session AS
+        INSERT INTO sink_session (ticker, n, total)
+        SELECT STREAM
+            source_trade.ticker AS ticker,
+            COUNT(*) AS n,
+            SUM(source_trade.amount) AS total
+        FROM source_trade
+        GROUP BY ticker
+        SESSION WINDOW DURATION_SECS(5), DURATION_SECS(10);
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_session FOR sink_session
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_session_null'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_session_null'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 1}"#,
+        // rows without ticker share a session of NULL group
+        r#"{"ts": "2020-01-01 00:00:01.000000000", "amount": 2}"#,
+        r#"{"ts": "2020-01-01 00:00:03.000000000", "amount": 4}"#,
+        // watermark 00:20 closes ORCL and NULL sessions
+        r#"{"ts": "2020-01-01 00:00:30.000000000", "ticker": "ORCL", "amount": 100}"#,
+    ];
+    for row in source_rows {
+        pipeline.push("q_source_session_null", SpringSourceRow::from_json(row)?)?;
+    }
+
+    let mut sessions = (0..2)
+        .map(|_| {
+            let row = pipeline.pop("q_sink_session_null")?;
+            Ok((
+                row.get_not_null_by_index::<String>(0).ok(),
+                row.get_not_null_by_index::<i64>(1)?,
+                row.get_not_null_by_index::<i64>(2)?,
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    sessions.sort();
+
+    assert_eq!(
+        sessions,
+        vec![(None, 2, 6), (Some("ORCL".to_string()), 1, 1)]
+    );
+    assert!(pipeline.pop_non_blocking("q_sink_session_null")?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_rows_window() -> Result<()> {
+    setup_test_logger();
+
+    let ddls = [
+        "
+        CREATE SOURCE STREAM source_sensor (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          value INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_fixed (
+          n BIGINT NOT NULL,
+          total BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_sliding (
+          n BIGINT NOT NULL,
+          total BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_fixed AS
+        INSERT INTO sink_fixed (n, total)
+        SELECT STREAM
+            COUNT(*) AS n,
+            SUM(source_sensor.value) AS total
+        FROM source_sensor
+        FIXED ROWS WINDOW 2;
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_sliding AS
+        INSERT INTO sink_sliding (n, total)
+        SELECT STREAM
+            COUNT(*) AS n,
+            SUM(source_sensor.value) AS total
+        FROM source_sensor
+        SLIDING ROWS WINDOW 3, 2;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_fixed FOR sink_fixed
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_rows_fixed'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_sliding FOR sink_sliding
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_rows_sliding'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_sensor FOR source_sensor
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_rows'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    // rowtime does not matter (the 4th row is not dropped as a late row)
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "value": 1}"#,
+        r#"{"ts": "2020-01-01 00:00:10.000000000", "value": 2}"#,
+        r#"{"ts": "2020-01-01 00:01:00.000000000", "value": 3}"#,
+        r#"{"ts": "2020-01-01 00:00:05.000000000", "value": 4}"#,
+        r#"{"ts": "2020-01-01 00:02:00.000000000", "value": 5}"#,
+        r#"{"ts": "2020-01-01 00:03:00.000000000", "value": 6}"#,
+    ];
+    for row in source_rows {
+        pipeline.push("q_source_rows", SpringSourceRow::from_json(row)?)?;
+    }
+
+    let pop = |queue: &str| -> Result<(i64, i64)> {
+        let row = pipeline.pop(queue)?;
+        Ok((
+            row.get_not_null_by_index::<i64>(0)?,
+            row.get_not_null_by_index::<i64>(1)?,
+        ))
+    };
+
+    // [1, 2], [3, 4], [5, 6]
+    assert_eq!(pop("q_sink_rows_fixed")?, (2, 3));
+    assert_eq!(pop("q_sink_rows_fixed")?, (2, 7));
+    assert_eq!(pop("q_sink_rows_fixed")?, (2, 11));
+
+    // [1, 2, 3], [3, 4, 5], ([5, 6] is not closed yet)
+    assert_eq!(pop("q_sink_rows_sliding")?, (3, 6));
+    assert_eq!(pop("q_sink_rows_sliding")?, (3, 12));
+    assert!(pipeline.pop_non_blocking("q_sink_rows_sliding")?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_emit_policy() -> Result<()> {
+    setup_test_logger();
+
+    let emit_clauses = [
+        ("on_each_row", "EMIT ON EACH ROW"),
+        ("every", "EMIT EVERY DURATION_SECS(5)"),
+        ("final", "EMIT FINAL"),
+    ];
+
+    let mut ddls = vec![
+        "
+        CREATE SOURCE STREAM source_sensor (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          value INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_sensor FOR source_sensor
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_emit'
+          );
+        "
+        .to_string(),
+    ];
+    for (name, emit_clause) in emit_clauses {
+        ddls.push(format!(
+            "
+            CREATE SINK STREAM sink_{name} (
+              n BIGINT NOT NULL,
+              total BIGINT NOT NULL
+            );
+            "
+        ));
+        ddls.push(format!(
+            "
+            CREATE PUMP pu_{name} AS
+            INSERT INTO sink_{name} (n, total)
+            SELECT STREAM
+                COUNT(*) AS n,
+                SUM(source_sensor.value) AS total
+            FROM source_sensor
+            FIXED WINDOW DURATION_SECS(10), DURATION_SECS(5)
+            {emit_clause};
+            "
+        ));
+        ddls.push(format!(
+            "
+            CREATE SINK WRITER q_sink_{name} FOR sink_{name}
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME 'q_sink_emit_{name}'
+              );
+            "
+        ));
+    }
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "value": 1}"#,
+        r#"{"ts": "2020-01-01 00:00:03.000000000", "value": 2}"#,
+        r#"{"ts": "2020-01-01 00:00:12.000000000", "value": 4}"#,
+        // late row accepted into [00:00, 00:10)
+        r#"{"ts": "2020-01-01 00:00:08.000000000", "value": 8}"#,
+        // watermark 00:11 closes [00:00, 00:10)
+        r#"{"ts": "2020-01-01 00:00:16.000000000", "value": 16}"#,
+    ];
+    for row in source_rows {
+        pipeline.push("q_source_emit", SpringSourceRow::from_json(row)?)?;
+    }
+
+    let pop_all = |name: &str, n: usize| -> Result<Vec<(i64, i64)>> {
+        let queue = format!("q_sink_emit_{}", name);
+        let rows = (0..n)
+            .map(|_| {
+                let row = pipeline.pop(&queue)?;
+                Ok((
+                    row.get_not_null_by_index::<i64>(0)?,
+                    row.get_not_null_by_index::<i64>(1)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        assert!(pipeline.pop_non_blocking(&queue)?.is_none());
+        Ok(rows)
+    };
+
+    // provisional results of [00:00, 00:10) and [00:10, 00:20) on each row, and the final result of [00:00, 00:10)
+    assert_eq!(
+        pop_all("on_each_row", 6)?,
+        vec![(1, 1), (2, 3), (1, 4), (3, 11), (3, 11), (2, 20)]
+    );
+    // provisional results at 00:10 and 00:15, and a late update by the row at 00:08
+    assert_eq!(
+        pop_all("every", 5)?,
+        vec![(2, 3), (1, 4), (3, 11), (3, 11), (2, 20)]
+    );
+    assert_eq!(pop_all("final", 1)?, vec![(3, 11)]);
+
+    // EMIT clause without window
+    assert!(pipeline
+        .command(
+            "
+            CREATE PUMP pu_no_window AS
+            INSERT INTO sink_final (n, total)
+            SELECT STREAM
+                source_sensor.value AS n,
+                source_sensor.value AS total
+            FROM source_sensor
+            EMIT ON EACH ROW;
+            "
+        )
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_late_rows_into() -> Result<()> {
+    setup_test_logger();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_sensor (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          value INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_sum (
+          total BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_late (
+          value INTEGER NOT NULL,
+          memo TEXT
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_sum AS
+        INSERT INTO sink_sum (total)
+        SELECT STREAM
+            SUM(source_sensor.value) AS total
+        FROM source_sensor
+        FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0)
+        LATE ROWS INTO sink_late;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_sum FOR sink_sum
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_late_sum'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_late FOR sink_late
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_late_late'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_sensor FOR source_sensor
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_late'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "value": 1}"#,
+        // watermark 00:12 closes [00:00, 00:10)
+        r#"{"ts": "2020-01-01 00:00:12.000000000", "value": 2}"#,
+        // too late
+        r#"{"ts": "2020-01-01 00:00:05.000000000", "value": 4}"#,
+        // watermark 00:25 closes [00:10, 00:20)
+        r#"{"ts": "2020-01-01 00:00:25.000000000", "value": 8}"#,
+    ];
+    for row in source_rows {
+        pipeline.push("q_source_late", SpringSourceRow::from_json(row)?)?;
+    }
+
+    let sum_rows = (0..2)
+        .map(|_| {
+            let row = pipeline.pop("q_sink_late_sum")?;
+            row.get_not_null_by_index::<i64>(0)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(sum_rows, vec![1, 2]);
+
+    let late_row = pipeline.pop("q_sink_late_late")?;
+    assert_eq!(late_row.get_not_null_by_index::<i32>(0)?, 4);
+    // memo is missing in source_sensor
+    assert!(late_row.get_not_null_by_index::<String>(1).is_err());
+    assert!(pipeline.pop_non_blocking("q_sink_late_late")?.is_none());
+
+    // LATE ROWS INTO without window
+    assert!(pipeline
+        .command(
+            "
+            CREATE PUMP pu_no_window AS
+            INSERT INTO sink_sum (total)
+            SELECT STREAM
+                source_sensor.value AS total
+            FROM source_sensor
+            LATE ROWS INTO sink_late;
+            "
+        )
+        .is_err());
+
+    // nullable upstream column into NOT NULL late rows column
+    pipeline.command(
+        "
+        CREATE SOURCE STREAM source_nullable (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          value INTEGER
+        );
+        ",
+    )?;
+    assert!(pipeline
+        .command(
+            "
+            CREATE PUMP pu_nullable AS
+            INSERT INTO sink_sum (total)
+            SELECT STREAM
+                SUM(source_nullable.value) AS total
+            FROM source_nullable
+            FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0)
+            LATE ROWS INTO sink_late;
+            "
+        )
+        .is_err());
+
+    Ok(())
+}
@@ -0,0 +1,108 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use springql::{SpringConfig, SpringError, SpringPipeline};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+const DDL_SOURCE_TRADE: &str = "
+    CREATE SOURCE STREAM source_trade (
+      ts TIMESTAMP NOT NULL ROWTIME,
+      ticker TEXT NOT NULL,
+      amount INTEGER NOT NULL
+    );
+";
+
+const DDL_SINK_TRADE: &str = "
+    CREATE SINK STREAM sink_trade (
+      ts TIMESTAMP NOT NULL ROWTIME,
+      size TEXT,
+      company TEXT
+    );
+";
+
+fn pipeline(pump: &str, source_queue_name: &str, sink_queue_name: &str) -> SpringPipeline {
+    apply_ddls_with_in_memory_queues(
+        &[DDL_SOURCE_TRADE, DDL_SINK_TRADE],
+        pump,
+        ("source_trade", source_queue_name),
+        ("sink_trade", sink_queue_name),
+    )
+}
+
+fn push_trades(pipeline: &SpringPipeline, source_queue_name: &str) {
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}"#,
+        r#"{"ts": "2020-01-01 00:00:01.000000000", "ticker": "GOOGL", "amount": 1000}"#,
+        r#"{"ts": "2020-01-01 00:00:02.000000000", "ticker": "IBM", "amount": 100}"#,
+    ];
+    push_json_rows(pipeline, source_queue_name, &source_rows);
+}
+
+#[test]
+fn test_feat_case() {
+    setup_test_logger();
+
+    let pipeline = pipeline(
+        "
+        CREATE PUMP pu_case AS
+          INSERT INTO sink_trade (ts, size, company)
+          SELECT STREAM
+            source_trade.ts,
+            CASE
+              WHEN source_trade.amount >= 1000 THEN 'large'
+              WHEN source_trade.amount >= 100 THEN 'medium'
+              ELSE 'small'
+            END,
+            CASE source_trade.ticker
+              WHEN 'ORCL' THEN 'Oracle'
+              WHEN 'GOOGL' THEN 'Google'
+            END
+          FROM source_trade;
+        ",
+        "q_source_case",
+        "q_sink_case",
+    );
+    push_trades(&pipeline, "q_source_case");
+
+    let row1 = pipeline.pop("q_sink_case").unwrap();
+    let row2 = pipeline.pop("q_sink_case").unwrap();
+    let row3 = pipeline.pop("q_sink_case").unwrap();
+
+    assert_eq!(row1.get_not_null_by_index::<String>(1).unwrap(), "small");
+    assert_eq!(row1.get_not_null_by_index::<String>(2).unwrap(), "Oracle");
+
+    assert_eq!(row2.get_not_null_by_index::<String>(1).unwrap(), "large");
+    assert_eq!(row2.get_not_null_by_index::<String>(2).unwrap(), "Google");
+
+    assert_eq!(row3.get_not_null_by_index::<String>(1).unwrap(), "medium");
+    assert!(matches!(
+        row3.get_not_null_by_index::<String>(2),
+        Err(SpringError::Null { i_col: 2 })
+    ));
+}
+
+#[test]
+fn test_feat_case_branch_type_mismatch() {
+    setup_test_logger();
+
+    let pipeline = SpringPipeline::new(&SpringConfig::default()).unwrap();
+    pipeline.command(DDL_SOURCE_TRADE).unwrap();
+    pipeline.command(DDL_SINK_TRADE).unwrap();
+
+    let res = pipeline.command(
+        "
+        CREATE PUMP pu_case_mismatch AS
+          INSERT INTO sink_trade (ts, size, company)
+          SELECT STREAM
+            source_trade.ts,
+            CASE WHEN source_trade.amount >= 1000 THEN 'large' ELSE 0 END,
+            source_trade.ticker
+          FROM source_trade;
+        ",
+    );
+    assert!(res.is_err());
+}
@@ -0,0 +1,76 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use springql::{SpringPipeline, SpringSourceRow};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+fn pipeline(pump: &str, source_queue_name: &str, sink_queue_name: &str) -> SpringPipeline {
+    apply_ddls_with_in_memory_queues(
+        &[
+            "
+        CREATE SOURCE STREAM source_raw (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          event_ts TEXT NOT NULL,
+          amount TEXT NOT NULL,
+          ratio FLOAT NOT NULL
+        );
+        ",
+            "
+        CREATE SINK STREAM sink_typed (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          event_ts TIMESTAMP NOT NULL,
+          amount BIGINT NOT NULL,
+          weighted FLOAT NOT NULL,
+          amount_text TEXT NOT NULL
+        );
+        ",
+        ],
+        pump,
+        ("source_raw", source_queue_name),
+        ("sink_typed", sink_queue_name),
+    )
+}
+
+#[test]
+fn test_feat_cast() {
+    setup_test_logger();
+
+    let pipeline = pipeline(
+        "
+        CREATE PUMP pu_cast AS
+          INSERT INTO sink_typed (ts, event_ts, amount, weighted, amount_text)
+          SELECT STREAM
+            source_raw.ts,
+            CAST(source_raw.event_ts AS TIMESTAMP),
+            CAST(source_raw.amount AS BIGINT) * 2,
+            CAST(source_raw.amount AS INTEGER) * source_raw.ratio,
+            CAST(CAST(source_raw.ratio AS INTEGER) AS TEXT)
+          FROM source_raw;
+        ",
+        "q_source_cast",
+        "q_sink_cast",
+    );
+
+    pipeline
+        .push(
+            "q_source_cast",
+            SpringSourceRow::from_json(
+                r#"{"ts": "2020-01-01 00:00:00.000000000", "event_ts": "2019-12-31 23:59:59.000000000", "amount": " 21 ", "ratio": 2.5}"#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+    let row = pipeline.pop("q_sink_cast").unwrap();
+    assert_eq!(
+        row.get_not_null_by_index::<String>(1).unwrap(),
+        "2019-12-31 23:59:59.000000000"
+    );
+    assert_eq!(row.get_not_null_by_index::<i64>(2).unwrap(), 42);
+    assert_eq!(row.get_not_null_by_index::<f32>(3).unwrap(), 52.5);
+    assert_eq!(row.get_not_null_by_index::<String>(4).unwrap(), "3");
+}
@@ -0,0 +1,160 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use springql::{SpringConfig, SpringError, SpringPipeline, SpringSinkRow};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+fn pipeline(pump: &str, source_queue_name: &str, sink_queue_name: &str) -> SpringPipeline {
+    apply_ddls_with_in_memory_queues(
+        &[
+            "
+        CREATE SOURCE STREAM source_can (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          can_id INTEGER NOT NULL,
+          speed INTEGER
+        );
+        ",
+            "
+        CREATE SINK STREAM sink_can (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          can_id INTEGER NOT NULL,
+          not_in_ids BOOLEAN,
+          in_ids_or_null BOOLEAN,
+          out_of_range BOOLEAN,
+          in_speed_or_one BOOLEAN
+        );
+        ",
+        ],
+        pump,
+        ("source_can", source_queue_name),
+        ("sink_can", sink_queue_name),
+    )
+}
+
+fn push_can_rows(pipeline: &SpringPipeline, source_queue_name: &str) {
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "can_id": 291, "speed": 15}"#,
+        r#"{"ts": "2020-01-01 00:00:01.000000000", "can_id": 1110, "speed": 25}"#,
+        r#"{"ts": "2020-01-01 00:00:02.000000000", "can_id": 1929}"#,
+        r#"{"ts": "2020-01-01 00:00:03.000000000", "can_id": 1, "speed": 5}"#,
+    ];
+    push_json_rows(pipeline, source_queue_name, &source_rows);
+}
+
+fn nullable_bool(row: &SpringSinkRow, i_col: usize) -> Option<bool> {
+    match row.get_not_null_by_index::<bool>(i_col) {
+        Ok(b) => Some(b),
+        Err(SpringError::Null { .. }) => None,
+        Err(e) => panic!("unexpected error: {:?}", e),
+    }
+}
+
+#[test]
+fn test_feat_in_between_selection() {
+    setup_test_logger();
+
+    let pipeline = pipeline(
+        "
+        CREATE PUMP pu_in_between AS
+          INSERT INTO sink_can (ts, can_id)
+          SELECT STREAM source_can.ts, source_can.can_id
+          FROM source_can
+          WHERE source_can.can_id IN (0x123, 0x456, -1) AND source_can.speed BETWEEN 10 AND 10 + 10;
+        ",
+        "q_source_in_between_selection",
+        "q_sink_in_between_selection",
+    );
+    push_can_rows(&pipeline, "q_source_in_between_selection");
+
+    let row = pipeline.pop("q_sink_in_between_selection").unwrap();
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 0x123);
+    assert!(pipeline
+        .pop_non_blocking("q_sink_in_between_selection")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_feat_in_between_three_valued_logic() {
+    setup_test_logger();
+
+    let pipeline = pipeline(
+        "
+        CREATE PUMP pu_in_between AS
+          INSERT INTO sink_can (ts, can_id, not_in_ids, in_ids_or_null, out_of_range, in_speed_or_one)
+          SELECT STREAM
+            source_can.ts,
+            source_can.can_id,
+            source_can.can_id NOT IN (0x123, 0x456),
+            source_can.can_id IN (0x123, NULL),
+            source_can.speed NOT BETWEEN 10 AND 20,
+            source_can.can_id IN (source_can.speed, 1)
+          FROM source_can;
+        ",
+        "q_source_in_between_3vl",
+        "q_sink_in_between_3vl",
+    );
+    push_can_rows(&pipeline, "q_source_in_between_3vl");
+
+    let expected = vec![
+        (Some(false), Some(true), Some(false), Some(false)),
+        (Some(false), None, Some(true), Some(false)),
+        (Some(true), None, None, None),
+        (Some(true), None, Some(true), Some(true)),
+    ];
+    for expected_row in expected {
+        let row = pipeline.pop("q_sink_in_between_3vl").unwrap();
+        assert_eq!(
+            (
+                nullable_bool(&row, 2),
+                nullable_bool(&row, 3),
+                nullable_bool(&row, 4),
+                nullable_bool(&row, 5),
+            ),
+            expected_row
+        );
+    }
+}
+
+#[test]
+fn test_feat_in_list_type_mismatch() {
+    setup_test_logger();
+
+    let pipeline = SpringPipeline::new(&SpringConfig::default()).unwrap();
+    pipeline
+        .command(
+            "
+            CREATE SOURCE STREAM source_can (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              can_id INTEGER NOT NULL
+            );
+            ",
+        )
+        .unwrap();
+    pipeline
+        .command(
+            "
+            CREATE SINK STREAM sink_can (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              can_id INTEGER NOT NULL
+            );
+            ",
+        )
+        .unwrap();
+
+    assert!(pipeline
+        .command(
+            "
+            CREATE PUMP pu_in_list_mismatch AS
+              INSERT INTO sink_can (ts, can_id)
+              SELECT STREAM source_can.ts, source_can.can_id
+              FROM source_can
+              WHERE source_can.can_id IN (1, 'abc');
+            ",
+        )
+        .is_err());
+}
@@ -2,6 +2,8 @@
 
 mod test_support;
 
+use std::{thread, time::Duration};
+
 use pretty_assertions::assert_eq;
 use serde_json::json;
 use springql_core::api::*;
@@ -183,3 +185,461 @@ fn test_feat_left_outer_join() {
     assert_eq!(r2["amount"].as_i64().unwrap(), 50);
     assert!(r2["temperature"].is_null());
 }
+
+/// Joins source_trade (left) and source_city_temperature (right) into `sink_joined` through in-memory queues.
+///
+/// Rows are read from `{queue_prefix}_trade` and `{queue_prefix}_city_temperature`, and written to `{queue_prefix}_joined`.
+fn apply_in_memory_join(
+    queue_prefix: &str,
+    sink_columns: &str,
+    insert_columns: &str,
+    select: &str,
+    join: &str,
+) -> SpringPipeline {
+    let pipeline = apply_ddls_with_in_memory_queues(
+        &[
+            "
+            CREATE SOURCE STREAM source_trade (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              ticker TEXT NOT NULL,
+              amount INTEGER NOT NULL
+            );
+            ",
+            "
+            CREATE SOURCE STREAM source_city_temperature (
+              ts TIMESTAMP NOT NULL ROWTIME,
+              city TEXT NOT NULL,
+              temperature INTEGER NOT NULL
+            );
+            ",
+            &format!("CREATE SINK STREAM sink_joined ({});", sink_columns),
+        ],
+        &format!(
+            "
+            CREATE PUMP pu_join AS
+              INSERT INTO sink_joined ({insert_columns})
+              SELECT STREAM {select}
+              FROM source_trade
+              {join} source_city_temperature
+                ON source_trade.ts = source_city_temperature.ts
+              FIXED WINDOW DURATION_SECS(10), DURATION_SECS(5);
+            ",
+            insert_columns = insert_columns,
+            select = select,
+            join = join
+        ),
+        ("source_trade", &format!("{}_trade", queue_prefix)),
+        ("sink_joined", &format!("{}_joined", queue_prefix)),
+    );
+    pipeline
+        .command(format!(
+            "
+            CREATE SOURCE READER q_source_city_temperature FOR source_city_temperature
+              TYPE IN_MEMORY_QUEUE OPTIONS (
+                NAME '{}_city_temperature'
+              );
+            ",
+            queue_prefix
+        ))
+        .unwrap();
+    pipeline
+}
+
+/// Pushes rows for the window [00:00:00, 00:00:10) and then closes it by a left row at 00:00:20.
+///
+/// Left and right rows are read by different tasks and may reach the join in any order.
+/// The allowed delay keeps the rows in the window from being late, and the closing row is pushed after they are processed.
+fn push_join_rows_and_close(pipeline: &SpringPipeline, queue_prefix: &str) {
+    push_json_rows(
+        pipeline,
+        &format!("{}_trade", queue_prefix),
+        &[
+            r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}"#,
+            r#"{"ts": "2020-01-01 00:00:01.000000000", "ticker": "GOOGL", "amount": 20}"#,
+        ],
+    );
+    push_json_rows(
+        pipeline,
+        &format!("{}_city_temperature", queue_prefix),
+        &[
+            r#"{"ts": "2020-01-01 00:00:00.000000000", "city": "Tokyo", "temperature": -3}"#,
+            r#"{"ts": "2020-01-01 00:00:05.000000000", "city": "Osaka", "temperature": 7}"#,
+        ],
+    );
+    thread::sleep(Duration::from_secs(1));
+    push_json_rows(
+        pipeline,
+        &format!("{}_trade", queue_prefix),
+        &[r#"{"ts": "2020-01-01 00:00:20.000000000", "ticker": "ORCL", "amount": 99}"#],
+    );
+}
+
+fn is_null<T: SpringValue>(res: Result<T>) -> bool {
+    matches!(res, Err(SpringError::Null { .. }))
+}
+
+#[test]
+fn test_feat_right_outer_join() -> Result<()> {
+    setup_test_logger();
+
+    let pipeline = apply_in_memory_join(
+        "q_right_outer",
+        "
+        ts TIMESTAMP NOT NULL ROWTIME,
+        ticker TEXT,
+        amount INTEGER,
+        temperature INTEGER NOT NULL
+        ",
+        "ts, ticker, amount, temperature",
+        "
+        source_city_temperature.ts,
+        source_trade.ticker,
+        source_trade.amount,
+        source_city_temperature.temperature
+        ",
+        "RIGHT OUTER JOIN",
+    );
+    push_join_rows_and_close(&pipeline, "q_right_outer");
+
+    // matched rows in the order of left rows, followed by unmatched right rows
+    let r0 = pipeline.pop("q_right_outer_joined")?;
+    assert_eq!(
+        r0.get_not_null_by_index::<String>(0)?,
+        "2020-01-01 00:00:00.000000000"
+    );
+    assert_eq!(r0.get_not_null_by_index::<String>(1)?, "ORCL");
+    assert_eq!(r0.get_not_null_by_index::<i32>(2)?, 10);
+    assert_eq!(r0.get_not_null_by_index::<i32>(3)?, -3);
+
+    let r1 = pipeline.pop("q_right_outer_joined")?;
+    assert_eq!(
+        r1.get_not_null_by_index::<String>(0)?,
+        "2020-01-01 00:00:05.000000000"
+    );
+    assert!(is_null(r1.get_not_null_by_index::<String>(1)));
+    assert!(is_null(r1.get_not_null_by_index::<i32>(2)));
+    assert_eq!(r1.get_not_null_by_index::<i32>(3)?, 7);
+
+    // unmatched left row at 00:00:01 is not emitted
+    assert!(pipeline.pop_non_blocking("q_right_outer_joined")?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_full_outer_join() -> Result<()> {
+    setup_test_logger();
+
+    let pipeline = apply_in_memory_join(
+        "q_full_outer",
+        "
+        trade_ts TIMESTAMP,
+        amount INTEGER,
+        temperature_ts TIMESTAMP,
+        temperature INTEGER
+        ",
+        "trade_ts, amount, temperature_ts, temperature",
+        "
+        source_trade.ts,
+        source_trade.amount,
+        source_city_temperature.ts,
+        source_city_temperature.temperature
+        ",
+        "FULL OUTER JOIN",
+    );
+    push_join_rows_and_close(&pipeline, "q_full_outer");
+
+    let rows = (0..3)
+        .map(|_| pipeline.pop("q_full_outer_joined"))
+        .collect::<Result<Vec<_>>>()?;
+
+    // matched
+    assert_eq!(
+        rows[0].get_not_null_by_index::<String>(0)?,
+        "2020-01-01 00:00:00.000000000"
+    );
+    assert_eq!(rows[0].get_not_null_by_index::<i32>(1)?, 10);
+    assert_eq!(
+        rows[0].get_not_null_by_index::<String>(2)?,
+        "2020-01-01 00:00:00.000000000"
+    );
+    assert_eq!(rows[0].get_not_null_by_index::<i32>(3)?, -3);
+
+    // unmatched left
+    assert_eq!(
+        rows[1].get_not_null_by_index::<String>(0)?,
+        "2020-01-01 00:00:01.000000000"
+    );
+    assert_eq!(rows[1].get_not_null_by_index::<i32>(1)?, 20);
+    assert!(is_null(rows[1].get_not_null_by_index::<String>(2)));
+    assert!(is_null(rows[1].get_not_null_by_index::<i32>(3)));
+
+    // unmatched right
+    assert!(is_null(rows[2].get_not_null_by_index::<String>(0)));
+    assert!(is_null(rows[2].get_not_null_by_index::<i32>(1)));
+    assert_eq!(
+        rows[2].get_not_null_by_index::<String>(2)?,
+        "2020-01-01 00:00:05.000000000"
+    );
+    assert_eq!(rows[2].get_not_null_by_index::<i32>(3)?, 7);
+
+    assert!(pipeline.pop_non_blocking("q_full_outer_joined")?.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_feat_3_streams_join() {
+    setup_test_logger();
+
+    let source_trade = gen_source_trade();
+    let source_city_temperature = gen_source_city_temperature();
+    let source_city_humidity = vec![json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "city": "Tokyo",
+        "humidity": 40,
+    })];
+
+    let test_source_trade = ForeignSource::new().unwrap();
+    let test_source_city_temperature = ForeignSource::new().unwrap();
+    let test_source_city_humidity = ForeignSource::new().unwrap();
+
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_city_temperature (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          city TEXT NOT NULL,
+          temperature INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_city_humidity (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          city TEXT NOT NULL,
+          humidity INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_joined (
+          ts TIMESTAMP NOT NULL ROWTIME,    
+          amount INTEGER NOT NULL,
+          temperature INTEGER,
+          humidity INTEGER
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_join AS
+          INSERT INTO sink_joined (ts, amount, temperature, humidity)
+          SELECT STREAM
+            source_trade.ts,
+            source_trade.amount,
+            source_city_temperature.temperature,
+            source_city_humidity.humidity
+          FROM source_trade
+          LEFT OUTER JOIN source_city_temperature
+            ON source_trade.ts = source_city_temperature.ts
+          LEFT OUTER JOIN source_city_humidity
+            ON source_trade.ts = source_city_humidity.ts
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_joined FOR sink_joined
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_source_trade FOR source_trade
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source_trade.host_ip(),
+            remote_port = test_source_trade.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_source_city_temperature FOR source_city_temperature
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+      ",
+            remote_host = test_source_city_temperature.host_ip(),
+            remote_port = test_source_city_temperature.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_source_city_humidity FOR source_city_humidity
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+      ",
+            remote_host = test_source_city_humidity.host_ip(),
+            remote_port = test_source_city_humidity.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    test_source_trade.start(ForeignSourceInput::new_fifo_batch(source_trade));
+    test_source_city_temperature.start(ForeignSourceInput::new_fifo_batch(source_city_temperature));
+    test_source_city_humidity.start(ForeignSourceInput::new_fifo_batch(source_city_humidity));
+
+    let mut sink_received = drain_from_sink(&test_sink);
+    sink_received.sort_by_key(|r| {
+        let ts = &r["ts"];
+        ts.as_str().unwrap().to_string()
+    });
+
+    assert_eq!(sink_received.len(), 3);
+
+    let r0 = sink_received[0].clone();
+    assert_eq!(r0["ts"].as_str().unwrap(), "2020-01-01 00:00:00.000000000");
+    assert_eq!(r0["amount"].as_i64().unwrap(), 10);
+    assert!(r0["temperature"].is_null() || r0["temperature"].as_i64().unwrap() == -3);
+    assert!(r0["humidity"].is_null() || r0["humidity"].as_i64().unwrap() == 40);
+
+    let r1 = sink_received[1].clone();
+    assert_eq!(r1["ts"].as_str().unwrap(), "2020-01-01 00:00:09.999999999");
+    assert_eq!(r1["amount"].as_i64().unwrap(), 30);
+    assert!(r1["temperature"].is_null());
+    assert!(r1["humidity"].is_null());
+
+    let r2 = sink_received[2].clone();
+    assert_eq!(r2["ts"].as_str().unwrap(), "2020-01-01 00:00:10.000000000");
+    assert_eq!(r2["amount"].as_i64().unwrap(), 50);
+    assert!(r2["temperature"].is_null());
+    assert!(r2["humidity"].is_null());
+}
+
+#[test]
+fn test_feat_interval_join() {
+    setup_test_logger();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE STREAM source_quote (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          price INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_joined (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount INTEGER NOT NULL,
+          price INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_interval_join AS
+          INSERT INTO sink_joined (ts, amount, price)
+          SELECT STREAM
+            source_trade.ts,
+            source_trade.amount,
+            source_quote.price
+          FROM source_trade
+          JOIN source_quote
+            ON source_trade.ticker = source_quote.ticker
+              AND source_quote.ts BETWEEN source_trade.ts - DURATION_MILLIS(50)
+                AND source_trade.ts + DURATION_MILLIS(50)
+          FIXED ROWS WINDOW 5;
+        "
+        .to_string(),
+        "
+        CREATE SINK WRITER q_sink_joined FOR sink_joined
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_sink_interval_join'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_interval_join_trade'
+          );
+        "
+        .to_string(),
+        "
+        CREATE SOURCE READER q_source_quote FOR source_quote
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME 'q_source_interval_join_quote'
+          );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    for (queue, row) in [
+        (
+            "q_source_interval_join_trade",
+            json!({"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}),
+        ),
+        (
+            "q_source_interval_join_trade",
+            json!({"ts": "2020-01-01 00:00:01.000000000", "ticker": "GOOGL", "amount": 20}),
+        ),
+        (
+            "q_source_interval_join_quote",
+            json!({"ts": "2020-01-01 00:00:00.040000000", "ticker": "ORCL", "price": 100}),
+        ),
+        (
+            "q_source_interval_join_quote",
+            json!({"ts": "2020-01-01 00:00:00.100000000", "ticker": "ORCL", "price": 200}),
+        ),
+        (
+            "q_source_interval_join_quote",
+            json!({"ts": "2020-01-01 00:00:01.000000000", "ticker": "ORCL", "price": 300}),
+        ),
+    ] {
+        pipeline
+            .push(queue, SpringSourceRow::from_json(&row.to_string()).unwrap())
+            .unwrap();
+    }
+
+    // only the ORCL quote within 50ms is joined in the pane of 5 rows
+    let row = pipeline.pop("q_sink_interval_join").unwrap();
+    assert_eq!(
+        row.get_not_null_by_index::<String>(0).unwrap(),
+        "2020-01-01 00:00:00.000000000"
+    );
+    assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 10);
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 100);
+}
@@ -82,3 +82,96 @@ fn test_feat_and() {
     assert_eq!(r["answer_true_and_false"], false);
     assert_eq!(r["answer_false_and_true"], false);
 }
+
+#[test]
+fn test_feat_or_not_precedence() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+    });
+    let source_input = vec![json1];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          answer_or BOOLEAN NOT NULL,
+          answer_not BOOLEAN NOT NULL,
+          answer_and_before_or BOOLEAN NOT NULL,
+          answer_paren BOOLEAN NOT NULL,
+          answer_arith INTEGER NOT NULL,
+          answer_arith_paren INTEGER NOT NULL,
+          answer_null_and_false BOOLEAN,
+          answer_null_or_true BOOLEAN,
+          answer_not_null BOOLEAN
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_or_not AS
+          INSERT INTO sink_1 (ts, answer_or, answer_not, answer_and_before_or, answer_paren, answer_arith, answer_arith_paren, answer_null_and_false, answer_null_or_true, answer_not_null)
+          SELECT STREAM
+            source_1.ts,
+            FALSE OR TRUE,
+            NOT 1 = 2,
+            TRUE OR FALSE AND FALSE,
+            (TRUE OR FALSE) AND FALSE,
+            1 + 2 * 3,
+            (1 + 2) * 3,
+            NULL AND FALSE,
+            NULL OR TRUE,
+            NOT NULL
+          FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    let r = sink_received.first().unwrap();
+
+    assert_eq!(r["answer_or"], true);
+    assert_eq!(r["answer_not"], true);
+    assert_eq!(r["answer_and_before_or"], true);
+    assert_eq!(r["answer_paren"], false);
+    assert_eq!(r["answer_arith"], 7);
+    assert_eq!(r["answer_arith_paren"], 9);
+    assert_eq!(r["answer_null_and_false"], false);
+    assert_eq!(r["answer_null_or_true"], true);
+    assert_eq!(r["answer_not_null"], serde_json::Value::Null);
+}
@@ -0,0 +1,87 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use springql::{SpringError, SpringPipeline};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+fn pipeline(pump: &str, source_queue_name: &str, sink_queue_name: &str) -> SpringPipeline {
+    apply_ddls_with_in_memory_queues(
+        &[
+            "
+        CREATE SOURCE STREAM source_sensor (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          temperature FLOAT,
+          humidity FLOAT
+        );
+        ",
+            "
+        CREATE SINK STREAM sink_sensor (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          temperature FLOAT NOT NULL,
+          humidity FLOAT,
+          has_humidity BOOLEAN NOT NULL
+        );
+        ",
+        ],
+        pump,
+        ("source_sensor", source_queue_name),
+        ("sink_sensor", sink_queue_name),
+    )
+}
+
+fn push_sensor_rows(pipeline: &SpringPipeline, source_queue_name: &str) {
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "temperature": 20.5, "humidity": 40.0}"#,
+        r#"{"ts": "2020-01-01 00:00:01.000000000", "humidity": -1.0}"#,
+        r#"{"ts": "2020-01-01 00:00:02.000000000", "temperature": 21.5}"#,
+    ];
+    push_json_rows(pipeline, source_queue_name, &source_rows);
+}
+
+#[test]
+fn test_feat_null_handling() {
+    setup_test_logger();
+
+    let pipeline = pipeline(
+        "
+        CREATE PUMP pu_null_handling AS
+          INSERT INTO sink_sensor (ts, temperature, humidity, has_humidity)
+          SELECT STREAM
+            source_sensor.ts,
+            COALESCE(source_sensor.temperature, 0.0),
+            NULLIF(source_sensor.humidity, -1.0),
+            source_sensor.humidity IS NOT NULL
+          FROM source_sensor
+          WHERE source_sensor.temperature IS NULL OR source_sensor.temperature > 21.0 + 0 IS NOT NULL;
+        ",
+        "q_source_null_handling",
+        "q_sink_null_handling",
+    );
+    push_sensor_rows(&pipeline, "q_source_null_handling");
+
+    let row1 = pipeline.pop("q_sink_null_handling").unwrap();
+    let row2 = pipeline.pop("q_sink_null_handling").unwrap();
+    let row3 = pipeline.pop("q_sink_null_handling").unwrap();
+
+    assert_eq!(row1.get_not_null_by_index::<f32>(1).unwrap(), 20.5);
+    assert_eq!(row1.get_not_null_by_index::<f32>(2).unwrap(), 40.0);
+    assert!(row1.get_not_null_by_index::<bool>(3).unwrap());
+
+    assert_eq!(row2.get_not_null_by_index::<f32>(1).unwrap(), 0.0);
+    assert!(matches!(
+        row2.get_not_null_by_index::<f32>(2),
+        Err(SpringError::Null { i_col: 2 })
+    ));
+    assert!(row2.get_not_null_by_index::<bool>(3).unwrap());
+
+    assert_eq!(row3.get_not_null_by_index::<f32>(1).unwrap(), 21.5);
+    assert!(matches!(
+        row3.get_not_null_by_index::<f32>(2),
+        Err(SpringError::Null { i_col: 2 })
+    ));
+    assert!(!row3.get_not_null_by_index::<bool>(3).unwrap());
+}
@@ -215,3 +215,211 @@ fn test_feat_unsigned_integer() {
 
     assert_eq!(r["u32"], u32::MAX);
 }
+
+#[test]
+fn test_feat_sub_div_mod() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+    });
+    let source_input = vec![json1];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          answer_sub INTEGER NOT NULL,
+          answer_div INTEGER NOT NULL,
+          answer_mod INTEGER NOT NULL,
+          answer_sub_float FLOAT NOT NULL,
+          answer_div_float FLOAT NOT NULL,
+          answer_precedence INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_sub_div_mod AS
+          INSERT INTO sink_1 (ts, answer_sub, answer_div, answer_mod, answer_sub_float, answer_div_float, answer_precedence)
+          SELECT STREAM source_1.ts, 1 - 3, 7 / 2, 7 % 3, 1.5 - 2.0, 1.0 / 4.0, 10 - 4 - 3 * 2 / 4
+          FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    let r = sink_received.first().unwrap();
+
+    assert_eq!(r["answer_sub"], -2);
+    assert_eq!(r["answer_div"], 3);
+    assert_eq!(r["answer_mod"], 1);
+    assert!(approx_eq!(
+        f64,
+        r["answer_sub_float"].as_f64().unwrap(),
+        -0.5
+    ));
+    assert!(approx_eq!(
+        f64,
+        r["answer_div_float"].as_f64().unwrap(),
+        0.25
+    ));
+    assert_eq!(r["answer_precedence"], 5);
+}
+
+#[test]
+fn test_feat_math_functions() {
+    setup_test_logger();
+
+    let json1 = json!({
+        "ts": "2020-01-01 00:00:00.000000000",
+        "x": -2.25,
+        "y": 3,
+    });
+    let source_input = vec![json1];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          x FLOAT NOT NULL,
+          y INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          answer_abs FLOAT NOT NULL,
+          answer_abs_int BIGINT NOT NULL,
+          answer_round FLOAT NOT NULL,
+          answer_round_int BIGINT NOT NULL,
+          answer_ceil FLOAT NOT NULL,
+          answer_floor FLOAT NOT NULL,
+          answer_sqrt FLOAT NOT NULL,
+          answer_power FLOAT NOT NULL,
+          answer_ln FLOAT NOT NULL,
+          answer_log10 FLOAT NOT NULL,
+          answer_exp FLOAT NOT NULL,
+          answer_trig FLOAT NOT NULL,
+          answer_atan2 FLOAT NOT NULL,
+          answer_greatest FLOAT NOT NULL,
+          answer_least BIGINT NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_math AS
+          INSERT INTO sink_1 (ts, answer_abs, answer_abs_int, answer_round, answer_round_int, answer_ceil, answer_floor, answer_sqrt, answer_power, answer_ln, answer_log10, answer_exp, answer_trig, answer_atan2, answer_greatest, answer_least)
+          SELECT STREAM
+            source_1.ts,
+            ABS(source_1.x),
+            ABS(-source_1.y),
+            ROUND(source_1.x, 1),
+            ROUND(1250, -2),
+            CEIL(source_1.x),
+            FLOOR(source_1.x),
+            SQRT(ABS(source_1.x)),
+            POWER(source_1.y, 2),
+            LN(EXP(source_1.y)),
+            LOG10(1000),
+            EXP(0),
+            SIN(source_1.x) * SIN(source_1.x) + COS(source_1.x) * COS(source_1.x),
+            ATAN2(1, 1),
+            GREATEST(source_1.x, source_1.y, NULL),
+            LEAST(source_1.y, 10)
+          FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    let r = sink_received.first().unwrap();
+
+    let approx = |key: &str, expected: f64| {
+        assert!(
+            approx_eq!(f64, r[key].as_f64().unwrap(), expected, epsilon = 1e-5),
+            "{}: {}",
+            key,
+            r[key]
+        )
+    };
+    approx("answer_abs", 2.25);
+    assert_eq!(r["answer_abs_int"], 3);
+    approx("answer_round", -2.3);
+    assert_eq!(r["answer_round_int"], 1300);
+    approx("answer_ceil", -2.0);
+    approx("answer_floor", -3.0);
+    approx("answer_sqrt", 1.5);
+    approx("answer_power", 9.0);
+    approx("answer_ln", 3.0);
+    approx("answer_log10", 3.0);
+    approx("answer_exp", 1.0);
+    approx("answer_trig", 1.0);
+    approx("answer_atan2", std::f64::consts::FRAC_PI_4);
+    approx("answer_greatest", 3.0);
+    assert_eq!(r["answer_least"], 3);
+}
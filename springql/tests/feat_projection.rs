@@ -101,3 +101,158 @@ fn test_select_list_order_with_aggr() {
     );
     assert_eq!(row.get_not_null_by_index::<i32>(1).unwrap(), 20);
 }
+
+fn wildcard_pipeline(sink_columns: &str) -> SpringPipeline {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK STREAM sink_trade (
+          {}
+        );
+        ",
+            sink_columns
+        ),
+    ];
+    apply_ddls(&ddls, SpringConfig::default())
+}
+
+fn run_wildcard_pump(pipeline: &SpringPipeline, pump: &str, queue_suffix: &str) -> SpringSinkRow {
+    let source_queue = format!("q_source_{}", queue_suffix);
+    let sink_queue = format!("q_sink_{}", queue_suffix);
+
+    pipeline.command(pump).unwrap();
+    pipeline
+        .command(format!(
+            "
+        CREATE SINK WRITER q_sink_trade FOR sink_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+          );
+        ",
+            sink_queue
+        ))
+        .unwrap();
+    pipeline
+        .command(format!(
+            "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+          );
+        ",
+            source_queue
+        ))
+        .unwrap();
+
+    let row = json!({"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10});
+    pipeline
+        .push(
+            &source_queue,
+            SpringSourceRow::from_json(&row.to_string()).unwrap(),
+        )
+        .unwrap();
+    pipeline.pop(&sink_queue).unwrap()
+}
+
+#[test]
+fn test_select_wildcard() {
+    setup_test_logger();
+
+    let pipeline = wildcard_pipeline(
+        "ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL",
+    );
+    let row = run_wildcard_pump(
+        &pipeline,
+        "
+        CREATE PUMP pu_wildcard AS
+          INSERT INTO sink_trade (ts, ticker, amount)
+          SELECT STREAM * FROM source_trade;
+        ",
+        "wildcard",
+    );
+
+    assert_eq!(
+        row.get_not_null_by_index::<String>(0).unwrap(),
+        "2020-01-01 00:00:00.000000000"
+    );
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "ORCL");
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 10);
+}
+
+#[test]
+fn test_select_qualified_wildcard_with_expr() {
+    setup_test_logger();
+
+    let pipeline = wildcard_pipeline(
+        "ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL,
+          doubled_amount INTEGER NOT NULL",
+    );
+    let row = run_wildcard_pump(
+        &pipeline,
+        "
+        CREATE PUMP pu_qualified_wildcard AS
+          INSERT INTO sink_trade (ts, ticker, amount, doubled_amount)
+          SELECT STREAM source_trade.*, source_trade.amount * 2 FROM source_trade;
+        ",
+        "qualified_wildcard",
+    );
+
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "ORCL");
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 10);
+    assert_eq!(row.get_not_null_by_index::<i32>(3).unwrap(), 20);
+}
+
+#[test]
+fn test_select_wildcard_mismatch() {
+    setup_test_logger();
+
+    let pipeline = wildcard_pipeline(
+        "ts TIMESTAMP NOT NULL ROWTIME,
+          amount INTEGER NOT NULL",
+    );
+
+    // 3 fields into 2 columns
+    assert!(pipeline
+        .command(
+            "
+        CREATE PUMP pu_wildcard_too_many AS
+          INSERT INTO sink_trade (ts, amount)
+          SELECT STREAM * FROM source_trade;
+        ",
+        )
+        .is_err());
+
+    // column not in sink stream
+    assert!(pipeline
+        .command(
+            "
+        CREATE PUMP pu_wildcard_unknown_column AS
+          INSERT INTO sink_trade (ts, ticker, amount)
+          SELECT STREAM * FROM source_trade;
+        ",
+        )
+        .is_err());
+
+    // stream not in FROM clause
+    assert!(pipeline
+        .command(
+            "
+        CREATE PUMP pu_wildcard_unknown_stream AS
+          INSERT INTO sink_trade (ts, amount)
+          SELECT STREAM sink_trade.* FROM source_trade;
+        ",
+        )
+        .is_err());
+}
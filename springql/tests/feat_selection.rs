@@ -0,0 +1,166 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use springql::SpringPipeline;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+fn pipeline(pump: &str, source_queue_name: &str, sink_queue_name: &str) -> SpringPipeline {
+    apply_ddls_with_in_memory_queues(
+        &[
+            "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        ",
+            "
+        CREATE SINK STREAM sink_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          amount FLOAT NOT NULL
+        );
+        ",
+        ],
+        pump,
+        ("source_trade", source_queue_name),
+        ("sink_trade", sink_queue_name),
+    )
+}
+
+fn push_trades(pipeline: &SpringPipeline, source_queue_name: &str) {
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}"#,
+        r#"{"ts": "2020-01-01 00:00:01.000000000", "ticker": "GOOGL", "amount": 1000}"#,
+        r#"{"ts": "2020-01-01 00:00:02.000000000", "ticker": "ORCL", "amount": 30}"#,
+        r#"{"ts": "2020-01-01 00:00:10.000000000", "ticker": "IBM", "amount": 50}"#,
+        r#"{"ts": "2020-01-01 00:00:11.000000000", "ticker": "ORCL", "amount": 70}"#,
+    ];
+    push_json_rows(pipeline, source_queue_name, &source_rows);
+}
+
+#[test]
+fn test_feat_where() {
+    setup_test_logger();
+
+    let pipeline = pipeline(
+        "
+        CREATE PUMP pu_where AS
+          INSERT INTO sink_trade (ts, amount)
+          SELECT STREAM source_trade.ts, source_trade.amount
+            FROM source_trade
+            WHERE source_trade.ticker = 'ORCL';
+        ",
+        "q_source_where",
+        "q_sink_where",
+    );
+    push_trades(&pipeline, "q_source_where");
+
+    let row1 = pipeline.pop("q_sink_where").unwrap();
+    let row2 = pipeline.pop("q_sink_where").unwrap();
+    let row3 = pipeline.pop("q_sink_where").unwrap();
+    assert_eq!(
+        row1.get_not_null_by_index::<String>(0).unwrap(),
+        "2020-01-01 00:00:00.000000000"
+    );
+    assert_eq!(
+        row2.get_not_null_by_index::<String>(0).unwrap(),
+        "2020-01-01 00:00:02.000000000"
+    );
+    assert_eq!(
+        row3.get_not_null_by_index::<String>(0).unwrap(),
+        "2020-01-01 00:00:11.000000000"
+    );
+    assert!(pipeline.pop_non_blocking("q_sink_where").unwrap().is_none());
+}
+
+#[test]
+fn test_feat_where_before_window() {
+    setup_test_logger();
+
+    let pipeline = pipeline(
+        "
+        CREATE PUMP pu_where_aggr AS
+          INSERT INTO sink_trade (ts, amount)
+          SELECT STREAM
+            FLOOR_TIME(source_trade.ts, DURATION_SECS(10)) AS sampled_ts,
+            AVG(source_trade.amount) AS avg_amount
+          FROM source_trade
+          WHERE source_trade.ticker = 'ORCL'
+          GROUP BY sampled_ts
+          FIXED WINDOW DURATION_SECS(10), DURATION_SECS(0);
+        ",
+        "q_source_where_aggr",
+        "q_sink_where_aggr",
+    );
+    push_trades(&pipeline, "q_source_where_aggr");
+
+    let row = pipeline.pop("q_sink_where_aggr").unwrap();
+    assert_eq!(
+        row.get_not_null_by_index::<String>(0).unwrap(),
+        "2020-01-01 00:00:00.000000000"
+    );
+    assert_eq!(row.get_not_null_by_index::<f32>(1).unwrap(), 20.0);
+}
+
+#[test]
+fn test_feat_where_comparison() {
+    setup_test_logger();
+
+    let pipeline = pipeline(
+        "
+        CREATE PUMP pu_where_cmp AS
+          INSERT INTO sink_trade (ts, amount)
+          SELECT STREAM source_trade.ts, source_trade.amount
+            FROM source_trade
+            WHERE source_trade.amount >= 50;
+        ",
+        "q_source_where_cmp",
+        "q_sink_where_cmp",
+    );
+    push_trades(&pipeline, "q_source_where_cmp");
+
+    let row1 = pipeline.pop("q_sink_where_cmp").unwrap();
+    let row2 = pipeline.pop("q_sink_where_cmp").unwrap();
+    let row3 = pipeline.pop("q_sink_where_cmp").unwrap();
+    assert_eq!(row1.get_not_null_by_index::<f32>(1).unwrap(), 1000.0);
+    assert_eq!(row2.get_not_null_by_index::<f32>(1).unwrap(), 50.0);
+    assert_eq!(row3.get_not_null_by_index::<f32>(1).unwrap(), 70.0);
+    assert!(pipeline
+        .pop_non_blocking("q_sink_where_cmp")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_feat_where_logical_ops() {
+    setup_test_logger();
+
+    let pipeline = pipeline(
+        "
+        CREATE PUMP pu_where_logical AS
+          INSERT INTO sink_trade (ts, amount)
+          SELECT STREAM source_trade.ts, source_trade.amount
+            FROM source_trade
+            WHERE source_trade.ticker = 'ORCL' AND source_trade.amount > 20
+              OR NOT (source_trade.ticker = 'ORCL' OR source_trade.ticker = 'GOOGL');
+        ",
+        "q_source_where_logical",
+        "q_sink_where_logical",
+    );
+    push_trades(&pipeline, "q_source_where_logical");
+
+    let row1 = pipeline.pop("q_sink_where_logical").unwrap();
+    let row2 = pipeline.pop("q_sink_where_logical").unwrap();
+    let row3 = pipeline.pop("q_sink_where_logical").unwrap();
+    assert_eq!(row1.get_not_null_by_index::<f32>(1).unwrap(), 30.0);
+    assert_eq!(row2.get_not_null_by_index::<f32>(1).unwrap(), 50.0);
+    assert_eq!(row3.get_not_null_by_index::<f32>(1).unwrap(), 70.0);
+    assert!(pipeline
+        .pop_non_blocking("q_sink_where_logical")
+        .unwrap()
+        .is_none());
+}
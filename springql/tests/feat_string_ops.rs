@@ -0,0 +1,78 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use pretty_assertions::assert_eq;
+use springql::SpringPipeline;
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+fn pipeline(pump: &str, source_queue_name: &str, sink_queue_name: &str) -> SpringPipeline {
+    apply_ddls_with_in_memory_queues(
+        &[
+            "
+        CREATE SOURCE STREAM source_message (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          topic TEXT NOT NULL,
+          payload TEXT NOT NULL
+        );
+        ",
+            "
+        CREATE SINK STREAM sink_message (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          device TEXT NOT NULL,
+          kind TEXT NOT NULL,
+          payload TEXT NOT NULL,
+          payload_len INTEGER NOT NULL
+        );
+        ",
+        ],
+        pump,
+        ("source_message", source_queue_name),
+        ("sink_message", sink_queue_name),
+    )
+}
+
+fn push_messages(pipeline: &SpringPipeline, source_queue_name: &str) {
+    let source_rows = vec![
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "topic": "sensor/Room1/temp", "payload": "  21.5  "}"#,
+        r#"{"ts": "2020-01-01 00:00:01.000000000", "topic": "actuator/room1/fan", "payload": "on"}"#,
+        r#"{"ts": "2020-01-01 00:00:02.000000000", "topic": "sensor/Room2/humidity", "payload": "xx40xx"}"#,
+        r#"{"ts": "2020-01-01 00:00:03.000000000", "topic": "sensor/Room3/temp", "payload": "22"}"#,
+    ];
+    push_json_rows(pipeline, source_queue_name, &source_rows);
+}
+
+#[test]
+fn test_feat_string_ops() {
+    setup_test_logger();
+
+    let pipeline = pipeline(
+        "
+        CREATE PUMP pu_string_ops AS
+          INSERT INTO sink_message (ts, device, kind, payload, payload_len)
+          SELECT STREAM
+            source_message.ts,
+            LOWER(SUBSTRING(source_message.topic FROM 8 FOR 5)),
+            UPPER(REPLACE(SUBSTRING(source_message.topic, POSITION('/' IN SUBSTRING(source_message.topic, 8)) + 8), '/', '')),
+            CONCAT('[', TRIM(BOTH 'x' FROM TRIM(source_message.payload)), ']') || '!',
+            LENGTH(source_message.payload)
+          FROM source_message
+          WHERE source_message.topic LIKE 'sensor/%' AND source_message.topic NOT LIKE '%/Room_/temp';
+        ",
+        "q_source_string_ops",
+        "q_sink_string_ops",
+    );
+    push_messages(&pipeline, "q_source_string_ops");
+
+    let row = pipeline.pop("q_sink_string_ops").unwrap();
+    assert_eq!(row.get_not_null_by_index::<String>(1).unwrap(), "room2");
+    assert_eq!(row.get_not_null_by_index::<String>(2).unwrap(), "HUMIDITY");
+    assert_eq!(row.get_not_null_by_index::<String>(3).unwrap(), "[40]!");
+    assert_eq!(row.get_not_null_by_index::<i32>(4).unwrap(), 6);
+    assert!(pipeline
+        .pop_non_blocking("q_sink_string_ops")
+        .unwrap()
+        .is_none());
+}
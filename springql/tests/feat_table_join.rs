@@ -0,0 +1,258 @@
+// This file is part of https://github.com/SpringQL/SpringQL which is licensed under MIT OR Apache-2.0. See file LICENSE-MIT or LICENSE-APACHE for full license details.
+
+mod test_support;
+
+use std::io::Write;
+
+use pretty_assertions::assert_eq;
+use springql::{SpringConfig, SpringError, SpringPipeline, SpringSourceRow};
+use springql_test_logger::setup_test_logger;
+
+use crate::test_support::*;
+
+fn pipeline(
+    table_file: &tempfile::NamedTempFile,
+    pump: &str,
+    source_queue_name: &str,
+    sink_queue_name: &str,
+) -> SpringPipeline {
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE TABLE company (
+          ticker TEXT NOT NULL,
+          name TEXT NOT NULL
+        ) OPTIONS (
+          PATH '{}',
+          FORMAT 'CSV'
+        );
+        ",
+            table_file.path().display()
+        ),
+        "
+        CREATE SINK STREAM sink_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          name TEXT,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        pump.to_string(),
+        format!(
+            "
+        CREATE SINK WRITER q_sink_trade FOR sink_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+        );
+        ",
+            sink_queue_name
+        ),
+        format!(
+            "
+        CREATE SOURCE READER q_source_trade FOR source_trade
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{}'
+          );
+        ",
+            source_queue_name
+        ),
+    ];
+
+    apply_ddls(&ddls, SpringConfig::default())
+}
+
+fn company_csv() -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(
+        b"ticker,name
+ORCL,Oracle
+GOOGL,Alphabet
+",
+    )
+    .unwrap();
+    file
+}
+
+fn push_trade(pipeline: &SpringPipeline, source_queue_name: &str, row: &str) {
+    pipeline
+        .push(source_queue_name, SpringSourceRow::from_json(row).unwrap())
+        .unwrap();
+}
+
+#[test]
+fn test_feat_table_join() {
+    setup_test_logger();
+
+    let table_file = company_csv();
+    let pipeline = pipeline(
+        &table_file,
+        "
+        CREATE PUMP pu_table_join AS
+          INSERT INTO sink_trade (ts, name, amount)
+          SELECT STREAM source_trade.ts, company.name, source_trade.amount
+            FROM source_trade
+            JOIN company
+              ON source_trade.ticker = company.ticker;
+        ",
+        "q_source_table_join",
+        "q_sink_table_join",
+    );
+
+    push_trade(
+        &pipeline,
+        "q_source_table_join",
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "ORCL", "amount": 10}"#,
+    );
+    push_trade(
+        &pipeline,
+        "q_source_table_join",
+        r#"{"ts": "2020-01-01 00:00:01.000000000", "ticker": "IBM", "amount": 20}"#,
+    );
+    push_trade(
+        &pipeline,
+        "q_source_table_join",
+        r#"{"ts": "2020-01-01 00:00:02.000000000", "ticker": "GOOGL", "amount": 30}"#,
+    );
+
+    let row1 = pipeline.pop("q_sink_table_join").unwrap();
+    let row2 = pipeline.pop("q_sink_table_join").unwrap();
+    assert_eq!(row1.get_not_null_by_index::<String>(1).unwrap(), "Oracle");
+    assert_eq!(row1.get_not_null_by_index::<i32>(2).unwrap(), 10);
+    assert_eq!(row2.get_not_null_by_index::<String>(1).unwrap(), "Alphabet");
+    assert_eq!(row2.get_not_null_by_index::<i32>(2).unwrap(), 30);
+    assert!(pipeline
+        .pop_non_blocking("q_sink_table_join")
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_feat_table_left_outer_join_after_replace() {
+    setup_test_logger();
+
+    let table_file = company_csv();
+    let pipeline = pipeline(
+        &table_file,
+        "
+        CREATE PUMP pu_table_left_join AS
+          INSERT INTO sink_trade (ts, name, amount)
+          SELECT STREAM source_trade.ts, company.name, source_trade.amount
+            FROM source_trade
+            LEFT OUTER JOIN company
+              ON company.ticker = source_trade.ticker;
+        ",
+        "q_source_table_left_join",
+        "q_sink_table_left_join",
+    );
+
+    push_trade(
+        &pipeline,
+        "q_source_table_left_join",
+        r#"{"ts": "2020-01-01 00:00:00.000000000", "ticker": "IBM", "amount": 10}"#,
+    );
+    let row = pipeline.pop("q_sink_table_left_join").unwrap();
+    assert!(matches!(
+        row.get_not_null_by_index::<String>(1),
+        Err(SpringError::Null { i_col: 1 })
+    ));
+    assert_eq!(row.get_not_null_by_index::<i32>(2).unwrap(), 10);
+
+    pipeline
+        .replace_table(
+            "company",
+            vec![SpringSourceRow::from_json(r#"{"ticker": "IBM", "name": "IBM Corp."}"#).unwrap()],
+        )
+        .unwrap();
+
+    push_trade(
+        &pipeline,
+        "q_source_table_left_join",
+        r#"{"ts": "2020-01-01 00:00:01.000000000", "ticker": "IBM", "amount": 20}"#,
+    );
+    push_trade(
+        &pipeline,
+        "q_source_table_left_join",
+        r#"{"ts": "2020-01-01 00:00:02.000000000", "ticker": "ORCL", "amount": 30}"#,
+    );
+    let row1 = pipeline.pop("q_sink_table_left_join").unwrap();
+    let row2 = pipeline.pop("q_sink_table_left_join").unwrap();
+    assert_eq!(
+        row1.get_not_null_by_index::<String>(1).unwrap(),
+        "IBM Corp."
+    );
+    assert!(matches!(
+        row2.get_not_null_by_index::<String>(1),
+        Err(SpringError::Null { i_col: 1 })
+    ));
+    assert_eq!(row2.get_not_null_by_index::<i32>(2).unwrap(), 30);
+}
+
+#[test]
+fn test_feat_table_join_invalid_on_condition() {
+    setup_test_logger();
+
+    let table_file = company_csv();
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ticker TEXT NOT NULL,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE TABLE company (
+          ticker TEXT NOT NULL,
+          name TEXT NOT NULL
+        ) OPTIONS (
+          PATH '{}',
+          FORMAT 'CSV'
+        );
+        ",
+            table_file.path().display()
+        ),
+        "
+        CREATE SINK STREAM sink_trade (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          name TEXT,
+          amount INTEGER NOT NULL
+        );
+        "
+        .to_string(),
+    ];
+    let pipeline = apply_ddls(&ddls, SpringConfig::default());
+
+    let res = pipeline.command(
+        "
+        CREATE PUMP pu_table_join AS
+          INSERT INTO sink_trade (ts, name, amount)
+          SELECT STREAM source_trade.ts, company.name, source_trade.amount
+            FROM source_trade
+            JOIN company
+              ON source_trade.ticker <> company.ticker;
+        ",
+    );
+    assert!(res.is_err());
+
+    let res = pipeline.command(
+        "
+        CREATE PUMP pu_table_join AS
+          INSERT INTO sink_trade (ts, name, amount)
+          SELECT STREAM source_trade.ts, company.name, source_trade.amount
+            FROM source_trade
+            JOIN company
+              ON company.symbol = source_trade.ticker;
+        ",
+    );
+    assert!(res.is_err());
+}
@@ -83,3 +83,78 @@ fn test_feat_floor_time() {
 
     assert_eq!(r["ts"], "2020-01-01 23:59:59.000000000");
 }
+
+#[test]
+fn test_feat_timestamp_duration_arithmetic() {
+    setup_test_logger();
+
+    let json_oracle = json!({
+        "ts": "2020-01-01 23:59:59.999999999",
+    });
+    let source_input = vec![json_oracle];
+
+    let test_source = ForeignSource::new().unwrap();
+    let test_sink = ForeignSink::start().unwrap();
+
+    let ddls = vec![
+        "
+        CREATE SOURCE STREAM source_1 (
+          ts TIMESTAMP NOT NULL ROWTIME
+        );
+        "
+        .to_string(),
+        "
+        CREATE SINK STREAM sink_1 (
+          ts TIMESTAMP NOT NULL ROWTIME,
+          ts_plus TIMESTAMP NOT NULL,
+          ts_minus TIMESTAMP NOT NULL,
+          elapsed_in_minute_gt BOOLEAN NOT NULL
+        );
+        "
+        .to_string(),
+        "
+        CREATE PUMP pu_timestamp_arithmetic AS
+          INSERT INTO sink_1 (ts, ts_plus, ts_minus, elapsed_in_minute_gt)
+          SELECT STREAM
+            source_1.ts,
+            source_1.ts + DURATION_MILLIS(1),
+            source_1.ts - DURATION_SECS(60),
+            source_1.ts - FLOOR_TIME(source_1.ts, DURATION_SECS(60)) > DURATION_SECS(59)
+          FROM source_1;
+        "
+        .to_string(),
+        format!(
+            "
+        CREATE SINK WRITER tcp_sink_1 FOR sink_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+        );
+        ",
+            remote_host = test_sink.host_ip(),
+            remote_port = test_sink.port()
+        ),
+        format!(
+            "
+        CREATE SOURCE READER tcp_1 FOR source_1
+          TYPE NET_CLIENT OPTIONS (
+            PROTOCOL 'TCP',
+            REMOTE_HOST '{remote_host}',
+            REMOTE_PORT '{remote_port}'
+          );
+        ",
+            remote_host = test_source.host_ip(),
+            remote_port = test_source.port()
+        ),
+    ];
+
+    let _pipeline = apply_ddls(&ddls, SpringConfig::default());
+    test_source.start(ForeignSourceInput::new_fifo_batch(source_input));
+    let sink_received = drain_from_sink(&test_sink);
+    let r = sink_received.first().unwrap();
+
+    assert_eq!(r["ts_plus"], "2020-01-02 00:00:00.000999999");
+    assert_eq!(r["ts_minus"], "2020-01-01 23:58:59.999999999");
+    assert_eq!(r["elapsed_in_minute_gt"], true);
+}
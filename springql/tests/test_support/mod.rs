@@ -2,7 +2,7 @@
 
 use std::time::Duration;
 
-use springql::{SpringConfig, SpringPipeline};
+use springql::{SpringConfig, SpringPipeline, SpringSourceRow};
 use springql_foreign_service::sink::ForeignSink;
 
 pub mod request_body;
@@ -24,3 +24,47 @@ pub fn drain_from_sink(sink: &ForeignSink) -> Vec<serde_json::Value> {
     }
     received
 }
+
+/// Applies `stream_ddls` and `pump`, and connects `source_stream` to the in-memory queue `source_queue`
+/// and `sink_stream` to the in-memory queue `sink_queue`.
+#[allow(dead_code)]
+pub fn apply_ddls_with_in_memory_queues(
+    stream_ddls: &[&str],
+    pump: &str,
+    (source_stream, source_queue): (&str, &str),
+    (sink_stream, sink_queue): (&str, &str),
+) -> SpringPipeline {
+    let mut ddls: Vec<String> = stream_ddls.iter().map(|ddl| ddl.to_string()).collect();
+    ddls.push(pump.to_string());
+    ddls.push(format!(
+        "
+        CREATE SINK WRITER q_{sink_stream} FOR {sink_stream}
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{sink_queue}'
+          );
+        ",
+        sink_stream = sink_stream,
+        sink_queue = sink_queue
+    ));
+    ddls.push(format!(
+        "
+        CREATE SOURCE READER q_{source_stream} FOR {source_stream}
+          TYPE IN_MEMORY_QUEUE OPTIONS (
+            NAME '{source_queue}'
+          );
+        ",
+        source_stream = source_stream,
+        source_queue = source_queue
+    ));
+    apply_ddls(&ddls, SpringConfig::default())
+}
+
+/// Pushes JSON rows into the in-memory queue `queue`.
+#[allow(dead_code)]
+pub fn push_json_rows(pipeline: &SpringPipeline, queue: &str, rows: &[&str]) {
+    for row in rows {
+        pipeline
+            .push(queue, SpringSourceRow::from_json(row).unwrap())
+            .unwrap();
+    }
+}